@@ -0,0 +1,246 @@
+use criterion::{black_box, criterion_group, criterion_main, BatchSize, Criterion};
+
+use wolfram_expr::{
+    symbol::{SymbolRef, SymbolTable},
+    Expr, ExprKind, Normal, Symbol, Transform,
+};
+
+/// Construct a balanced binary tree of `f[_, _]` expressions with integer leaves.
+fn tree(depth: usize) -> Expr {
+    if depth == 0 {
+        return Expr::from(1);
+    }
+
+    Expr::normal(Symbol::new("Global`f"), vec![
+        tree(depth - 1),
+        tree(depth - 1),
+    ])
+}
+
+/// Rebuild `expr` so that it shares no allocations with the original.
+fn deep_copy(expr: &Expr) -> Expr {
+    match expr.kind() {
+        ExprKind::Normal(normal) => Expr::from(Normal::new(
+            deep_copy(normal.head()),
+            normal.elements().iter().map(deep_copy).collect(),
+        )),
+        kind => Expr::new(kind.clone()),
+    }
+}
+
+fn bench_clone(c: &mut Criterion) {
+    let expr = tree(12);
+
+    let list = Expr::list((0..10_000).map(Expr::from).collect());
+    let normal = list.try_as_normal().unwrap();
+
+    let mut group = c.benchmark_group("clone");
+    group.bench_function("clone", |b| b.iter(|| black_box(&expr).clone()));
+    group.bench_function("deep_copy", |b| b.iter(|| deep_copy(black_box(&expr))));
+    // Cloning a `Normal` shares its elements instead of copying them.
+    group.bench_function("normal", |b| b.iter(|| black_box(normal).clone()));
+    group.finish();
+}
+
+fn bench_to_kind(c: &mut Criterion) {
+    let expr = tree(12);
+
+    let mut group = c.benchmark_group("to_kind");
+    // The allocation is unique, so the `ExprKind` is moved out.
+    group.bench_function("unique", |b| {
+        b.iter_batched(
+            || deep_copy(&expr),
+            |expr| expr.to_kind(),
+            BatchSize::SmallInput,
+        )
+    });
+    // The allocation is shared, so the `ExprKind` is cloned.
+    group.bench_function("shared", |b| {
+        b.iter_batched(
+            || expr.clone(),
+            |expr| expr.to_kind(),
+            BatchSize::SmallInput,
+        )
+    });
+    group.finish();
+}
+
+fn bench_construction(c: &mut Criterion) {
+    const LEN: i64 = 1000;
+
+    let mut group = c.benchmark_group("construction");
+    // Appending an element to an existing expression requires copying its elements.
+    group.bench_function("append", |b| {
+        b.iter(|| {
+            let mut list = Expr::list(vec![]);
+            for i in 0..LEN {
+                let mut elements = list.try_as_normal().unwrap().elements().to_vec();
+                elements.push(Expr::from(i));
+                list = Expr::list(elements);
+            }
+            list
+        })
+    });
+    group.bench_function("collect", |b| {
+        b.iter(|| Expr::list((0..LEN).map(Expr::from).collect()))
+    });
+    group.finish();
+
+    // Compare with `--features smallvec`, which stores these elements inline.
+    let mut group = c.benchmark_group("small_normals");
+    group.sample_size(10);
+    group.bench_function("plus", |b| {
+        let plus = Expr::symbol(Symbol::new("System`Plus"));
+        let x = Expr::symbol(Symbol::new("Global`x"));
+        b.iter(|| {
+            (0..1_000_000)
+                .map(|i| Expr::normal(plus.clone(), vec![x.clone(), Expr::from(i)]))
+                .collect::<Vec<_>>()
+        })
+    });
+    group.finish();
+}
+
+fn bench_symbols(c: &mut Criterion) {
+    const LEN: usize = 1000;
+
+    let mut group = c.benchmark_group("symbols");
+    // Every element allocates its own copy of the symbol name.
+    group.bench_function("new", |b| {
+        b.iter(|| {
+            Expr::list(
+                (0..LEN)
+                    .map(|_| Expr::from(Symbol::new(black_box("System`Plus"))))
+                    .collect(),
+            )
+        })
+    });
+    // Every element shares the interned symbol name.
+    group.bench_function("intern", |b| {
+        let table = SymbolTable::new();
+        b.iter(|| {
+            Expr::list(
+                (0..LEN)
+                    .map(|_| Expr::from(table.intern(black_box("System`Plus"))))
+                    .collect(),
+            )
+        })
+    });
+    // Every element shares the cached symbol expression.
+    group.bench_function("symbol_cached", |b| {
+        b.iter(|| {
+            Expr::list(
+                (0..LEN)
+                    .map(|_| Expr::symbol_cached(Symbol::new(black_box("System`Plus"))))
+                    .collect(),
+            )
+        })
+    });
+    group.finish();
+}
+
+fn bench_heads(c: &mut Criterion) {
+    let expr = Expr::normal(Symbol::new("System`Plus"), vec![Expr::from(1)]);
+
+    let mut group = c.benchmark_group("heads");
+    // Allocates a `Symbol` for every comparison.
+    group.bench_function("has_normal_head", |b| {
+        b.iter(|| {
+            black_box(&expr).has_normal_head(&Symbol::new(black_box("System`Plus")))
+        })
+    });
+    // Compares against the borrowed string, without allocating.
+    group.bench_function("matches_head", |b| {
+        b.iter(|| {
+            let head = SymbolRef::try_new(black_box("System`Plus")).unwrap();
+            black_box(&expr).matches_head(head)
+        })
+    });
+    group.finish();
+}
+
+fn bench_eq(c: &mut Criterion) {
+    let expr = tree(12);
+    let shared = expr.clone();
+    let unshared = deep_copy(&expr);
+
+    let mut group = c.benchmark_group("eq");
+    group.bench_function("shared", |b| {
+        b.iter(|| black_box(&expr) == black_box(&shared))
+    });
+    group.bench_function("unshared", |b| {
+        b.iter(|| black_box(&expr) == black_box(&unshared))
+    });
+    group.finish();
+}
+
+fn bench_traversal(c: &mut Criterion) {
+    let expr = tree(12);
+    let f = Symbol::new("Global`f");
+
+    let mut group = c.benchmark_group("traversal");
+    group.bench_function("count_symbol", |b| {
+        b.iter(|| black_box(&expr).count_symbol(&f))
+    });
+    group.bench_function("transform", |b| {
+        b.iter(|| {
+            black_box(&expr).transform(|expr| match expr.kind() {
+                ExprKind::Normal(_) => Transform::Descend,
+                _ => Transform::Keep,
+            })
+        })
+    });
+    group.bench_function("walk_mut", |b| {
+        b.iter_batched(
+            || deep_copy(&expr),
+            |mut expr| {
+                expr.walk_mut(|kind| {
+                    black_box(kind);
+                });
+                expr
+            },
+            BatchSize::SmallInput,
+        )
+    });
+    group.finish();
+}
+
+fn bench_encode(c: &mut Criterion) {
+    let expr = tree(12);
+    let bytes = expr.to_bytes();
+    let mut wxf = Vec::new();
+    expr.write_wxf(&mut wxf).unwrap();
+
+    let mut group = c.benchmark_group("encode");
+    group.bench_function("to_bytes", |b| b.iter(|| black_box(&expr).to_bytes()));
+    group.bench_function("write_wxf", |b| {
+        b.iter(|| {
+            let mut wxf = Vec::new();
+            black_box(&expr).write_wxf(&mut wxf).unwrap();
+            wxf
+        })
+    });
+    group.finish();
+
+    let mut group = c.benchmark_group("decode");
+    group.bench_function("from_bytes", |b| {
+        b.iter(|| Expr::from_bytes(black_box(&bytes)).unwrap())
+    });
+    group.bench_function("read_wxf", |b| {
+        b.iter(|| Expr::read_wxf(black_box(&wxf[..])).unwrap())
+    });
+    group.finish();
+}
+
+criterion_group!(
+    benches,
+    bench_clone,
+    bench_to_kind,
+    bench_construction,
+    bench_symbols,
+    bench_heads,
+    bench_eq,
+    bench_traversal,
+    bench_encode
+);
+criterion_main!(benches);
@@ -0,0 +1,278 @@
+//! Arithmetic on numbers.
+
+use core::ops::{Add, Div, Mul, Neg, Sub};
+
+#[cfg(feature = "bigint")]
+use num_bigint::BigInt;
+#[cfg(feature = "rational")]
+use num_rational::BigRational;
+
+#[cfg(all(feature = "bigint", not(feature = "rational")))]
+use crate::big_integer::big_integer_to_f64;
+use crate::Number;
+
+
+/// Binary arithmetic operation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Op {
+    Add,
+    Sub,
+    Mul,
+    Div,
+}
+
+/// Apply `op` to `a` and `b`.
+///
+/// Returns `None` if the result is undefined: if an exact number is divided by an exact
+/// zero, or if the result of real arithmetic is NaN.
+pub(crate) fn arithmetic(op: Op, a: Number, b: Number) -> Option<Number> {
+    match (a, b) {
+        (Number::Integer(a), Number::Integer(b)) => op.integer(a, b),
+        (a, b) if is_approximate(&a) || is_approximate(&b) => {
+            Number::try_real(op.real(a.to_f64(), b.to_f64())).ok()
+        },
+        #[cfg(feature = "rational")]
+        (a, b) if is_rational(&a) || is_rational(&b) => {
+            op.rational(to_rational(a), to_rational(b))
+        },
+        #[cfg(feature = "bigint")]
+        (a, b) => op.big_integer(to_big_integer(a), to_big_integer(b)),
+        #[cfg(not(feature = "bigint"))]
+        _ => unreachable!("exact numbers are integers"),
+    }
+}
+
+impl Op {
+    fn real(self, a: f64, b: f64) -> f64 {
+        match self {
+            Op::Add => a + b,
+            Op::Sub => a - b,
+            Op::Mul => a * b,
+            Op::Div => a / b,
+        }
+    }
+
+    fn integer(self, a: i64, b: i64) -> Option<Number> {
+        if self == Op::Div && b == 0 {
+            return None;
+        }
+
+        let result = match self {
+            Op::Add => a.checked_add(b),
+            Op::Sub => a.checked_sub(b),
+            Op::Mul => a.checked_mul(b),
+            // `checked_rem()` and `checked_div()` only fail for `i64::MIN / -1`, which
+            // overflows.
+            Op::Div => match a.checked_rem(b) {
+                Some(0) | None => a.checked_div(b),
+                Some(_) => return Some(inexact_quotient(a, b)),
+            },
+        };
+
+        if let Some(int) = result {
+            return Some(Number::Integer(int));
+        }
+
+        // The result overflows `i64`.
+        #[cfg(feature = "bigint")]
+        return self.big_integer(BigInt::from(a), BigInt::from(b));
+
+        #[cfg(not(feature = "bigint"))]
+        return Number::try_real(self.real(a as f64, b as f64)).ok();
+    }
+
+    #[cfg(feature = "bigint")]
+    fn big_integer(self, a: BigInt, b: BigInt) -> Option<Number> {
+        use num_traits::Zero;
+
+        let result = match self {
+            Op::Add => a + b,
+            Op::Sub => a - b,
+            Op::Mul => a * b,
+            Op::Div if b.is_zero() => return None,
+            Op::Div if (&a % &b).is_zero() => a / b,
+            #[cfg(feature = "rational")]
+            Op::Div => return Some(Number::from(BigRational::new(a, b))),
+            #[cfg(not(feature = "rational"))]
+            Op::Div => {
+                let (a, b) = (big_integer_to_f64(&a), big_integer_to_f64(&b));
+                return Number::try_real(a / b).ok();
+            },
+        };
+
+        Some(Number::big_integer(result))
+    }
+
+    #[cfg(feature = "rational")]
+    fn rational(self, a: BigRational, b: BigRational) -> Option<Number> {
+        use num_traits::Zero;
+
+        let result = match self {
+            Op::Add => a + b,
+            Op::Sub => a - b,
+            Op::Mul => a * b,
+            Op::Div if b.is_zero() => return None,
+            Op::Div => a / b,
+        };
+
+        Some(Number::from(result))
+    }
+}
+
+/// The quotient of `a / b`, where `b` does not divide `a`.
+fn inexact_quotient(a: i64, b: i64) -> Number {
+    #[cfg(feature = "rational")]
+    return Number::rational(a, b);
+
+    #[cfg(not(feature = "rational"))]
+    return Number::real(a as f64 / b as f64);
+}
+
+/// Returns `true` if `number` is a machine or arbitrary-precision real.
+fn is_approximate(number: &Number) -> bool {
+    match number {
+        Number::Real(_) => true,
+        #[cfg(feature = "bigreal")]
+        Number::BigReal(_) => true,
+        _ => false,
+    }
+}
+
+#[cfg(feature = "rational")]
+fn is_rational(number: &Number) -> bool {
+    matches!(number, Number::Rational(_))
+}
+
+#[cfg(feature = "bigint")]
+fn to_big_integer(number: Number) -> BigInt {
+    match number {
+        Number::Integer(int) => BigInt::from(int),
+        Number::BigInteger(int) => int,
+        _ => unreachable!("expected an integer, got {:?}", number),
+    }
+}
+
+#[cfg(feature = "rational")]
+fn to_rational(number: Number) -> BigRational {
+    match number {
+        Number::Rational(rational) => rational,
+        number => BigRational::from(to_big_integer(number)),
+    }
+}
+
+//======================================
+// Operator impls
+//======================================
+
+macro_rules! binary_op {
+    ($trait:ident, $method:ident, $op:expr) => {
+        impl $trait for Number {
+            type Output = Number;
+
+            #[track_caller]
+            fn $method(self, other: Number) -> Number {
+                match arithmetic($op, self, other) {
+                    Some(result) => result,
+                    None => panic!(
+                        "Number::{}: result is undefined (division by zero or NaN)",
+                        stringify!($method)
+                    ),
+                }
+            }
+        }
+    };
+}
+
+binary_op!(Add, add, Op::Add);
+binary_op!(Sub, sub, Op::Sub);
+binary_op!(Mul, mul, Op::Mul);
+binary_op!(Div, div, Op::Div);
+
+impl Neg for Number {
+    type Output = Number;
+
+    fn neg(self) -> Number {
+        match self {
+            Number::Integer(int) => Number::Integer(0) - Number::Integer(int),
+            #[cfg(feature = "bigint")]
+            Number::BigInteger(int) => Number::big_integer(-int),
+            #[cfg(feature = "rational")]
+            Number::Rational(rational) => Number::Rational(-rational),
+            Number::Real(real) => Number::Real(-real),
+            #[cfg(feature = "bigreal")]
+            Number::BigReal(real) => {
+                let precision = crate::F64::new(real.precision())
+                    .expect("BigReal precision is not NaN");
+                Number::BigReal(crate::BigReal::new(-real.value().clone(), precision))
+            },
+        }
+    }
+}
+
+#[test]
+fn test_number_arithmetic() {
+    use Number::Integer;
+
+    let real = Number::real;
+
+    assert_eq!(Integer(2) + Integer(3), Integer(5));
+    assert_eq!(Integer(2) - Integer(3), Integer(-1));
+    assert_eq!(Integer(2) * Integer(3), Integer(6));
+    assert_eq!(Integer(6) / Integer(3), Integer(2));
+    assert_eq!(Integer(-6) / Integer(3), Integer(-2));
+    assert_eq!(-Integer(2), Integer(-2));
+
+    // Reals are contagious.
+    assert_eq!(Integer(2) + real(0.5), real(2.5));
+    assert_eq!(real(0.5) - Integer(2), real(-1.5));
+    assert_eq!(Integer(2) * real(1.0), real(2.0));
+    assert_eq!(Integer(1) / real(4.0), real(0.25));
+    assert_eq!(real(1.0) / real(0.0), real(f64::INFINITY));
+    assert_eq!(-real(0.5), real(-0.5));
+}
+
+#[test]
+fn test_number_inexact_division() {
+    #[cfg(feature = "rational")]
+    assert_eq!(
+        Number::Integer(1) / Number::Integer(4),
+        Number::rational(1, 4)
+    );
+    #[cfg(not(feature = "rational"))]
+    assert_eq!(Number::Integer(1) / Number::Integer(4), Number::real(0.25));
+}
+
+#[test]
+fn test_number_overflow() {
+    use Number::Integer;
+
+    let overflows = [
+        (Integer(i64::MAX) + Integer(1), 9223372036854775808.0),
+        (Integer(i64::MIN) - Integer(1), -9223372036854775809.0),
+        (Integer(i64::MAX) * Integer(2), 18446744073709551614.0),
+        (Integer(i64::MIN) / Integer(-1), 9223372036854775808.0),
+        (-Integer(i64::MIN), 9223372036854775808.0),
+    ];
+
+    for (result, expected) in overflows {
+        #[cfg(feature = "bigint")]
+        assert_eq!(result.to_f64(), expected);
+        #[cfg(feature = "bigint")]
+        assert!(matches!(result, Number::BigInteger(_)));
+
+        #[cfg(not(feature = "bigint"))]
+        assert_eq!(result, Number::real(expected));
+    }
+}
+
+#[test]
+#[should_panic(expected = "Number::div: result is undefined")]
+fn test_number_division_by_zero() {
+    let _ = Number::Integer(1) / Number::Integer(0);
+}
+
+#[test]
+#[should_panic(expected = "Number::add: result is undefined")]
+fn test_number_nan() {
+    let _ = Number::real(f64::INFINITY) + Number::real(f64::NEG_INFINITY);
+}
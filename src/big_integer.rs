@@ -0,0 +1,130 @@
+//! Arbitrary-precision integers.
+
+use core::{convert::TryFrom, str::FromStr};
+
+use num_bigint::{BigInt, Sign};
+use num_traits::ToPrimitive;
+
+use crate::{Expr, Number};
+
+
+impl Expr {
+    /// Construct an expression from an arbitrary-precision integer.
+    ///
+    /// If `int` is in the range of `i64`, this returns an
+    /// [`ExprKind::Integer`](crate::ExprKind::Integer) expression, so that each integer
+    /// has a single representation.
+    ///
+    /// This function requires the `bigint` crate feature.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use wolfram_expr::{BigInt, Expr, ExprKind};
+    ///
+    /// let big = Expr::big_integer(BigInt::from(i64::MAX) * 10u8);
+    /// assert!(matches!(big.kind(), ExprKind::BigInteger(_)));
+    /// assert_eq!(big.to_string(), "92233720368547758070");
+    ///
+    /// assert_eq!(Expr::big_integer(BigInt::from(5)), Expr::from(5));
+    /// ```
+    pub fn big_integer(int: BigInt) -> Expr {
+        Expr::number(Number::big_integer(int))
+    }
+}
+
+impl Number {
+    /// Construct a number from an arbitrary-precision integer.
+    ///
+    /// If `int` is in the range of `i64`, this returns [`Number::Integer`].
+    ///
+    /// This function requires the `bigint` crate feature.
+    pub fn big_integer(int: BigInt) -> Number {
+        match i64::try_from(&int) {
+            Ok(int) => Number::Integer(int),
+            Err(_) => Number::BigInteger(int),
+        }
+    }
+}
+
+impl From<BigInt> for Expr {
+    fn from(int: BigInt) -> Expr {
+        Expr::big_integer(int)
+    }
+}
+
+impl From<BigInt> for Number {
+    fn from(int: BigInt) -> Number {
+        Number::big_integer(int)
+    }
+}
+
+/// Convert `int` to the nearest `f64`, or to an infinity if it is out of range.
+pub(crate) fn big_integer_to_f64(int: &BigInt) -> f64 {
+    int.to_f64().unwrap_or(match int.sign() {
+        Sign::Minus => f64::NEG_INFINITY,
+        Sign::NoSign | Sign::Plus => f64::INFINITY,
+    })
+}
+
+/// Parse a decimal integer with an optional leading `-`, e.g. `-123`.
+///
+/// Unlike [`BigInt::from_str()`], this rejects a leading `+` and `_` digit separators.
+pub(crate) fn parse_big_integer(input: &str) -> Option<BigInt> {
+    let digits = input.strip_prefix('-').unwrap_or(input);
+
+    if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+
+    BigInt::from_str(input).ok()
+}
+
+#[test]
+fn test_big_integer() {
+    use crate::ExprKind;
+
+    let big = BigInt::from(i64::MAX) + 1u8;
+    assert_eq!(
+        Expr::big_integer(big.clone()).kind(),
+        &ExprKind::BigInteger(big.clone())
+    );
+    assert_eq!(Expr::from(big).to_string(), "9223372036854775808");
+
+    assert_eq!(
+        Expr::big_integer(BigInt::from(i64::MIN)),
+        Expr::from(i64::MIN)
+    );
+    assert_eq!(
+        Number::big_integer(BigInt::from(i64::MIN) - 1u8),
+        Number::BigInteger(BigInt::from(i64::MIN) - 1u8)
+    );
+    assert_eq!(Number::from(BigInt::from(-3)), Number::Integer(-3));
+}
+
+#[test]
+fn test_big_integer_to_f64() {
+    assert_eq!(
+        big_integer_to_f64(&(BigInt::from(1u8) << 64u32)),
+        18446744073709551616.0
+    );
+    assert_eq!(
+        big_integer_to_f64(&-(BigInt::from(1u8) << 2000u32)),
+        f64::NEG_INFINITY
+    );
+}
+
+#[test]
+fn test_parse_big_integer() {
+    assert_eq!(
+        parse_big_integer("-123456789012345678901234567890"),
+        Some(BigInt::from_str("-123456789012345678901234567890").unwrap())
+    );
+    assert_eq!(parse_big_integer("0"), Some(BigInt::from(0)));
+
+    assert_eq!(parse_big_integer(""), None);
+    assert_eq!(parse_big_integer("-"), None);
+    assert_eq!(parse_big_integer("+1"), None);
+    assert_eq!(parse_big_integer("1_000"), None);
+    assert_eq!(parse_big_integer("1.5"), None);
+}
@@ -0,0 +1,240 @@
+//! Arbitrary-precision real numbers.
+
+use alloc::{format, string::ToString};
+use core::{
+    fmt,
+    hash::{Hash, Hasher},
+    str::FromStr,
+};
+
+use dashu_float::DBig;
+
+use crate::{Expr, ExprKind, Number, F64};
+
+
+/// Arbitrary-precision real number, with a Wolfram Language precision.
+///
+/// This corresponds to a Wolfram Language real number whose
+/// [`Precision`](https://reference.wolfram.com/language/ref/Precision.html)<sub>WL</sub>
+/// is not `MachinePrecision`, such as the result of `N[Pi, 30]`. The precision is
+/// stored separately from the value, so that it is preserved exactly, including
+/// non-integer precisions produced by arithmetic in the Wolfram Language.
+///
+/// This type requires the `bigreal` crate feature.
+///
+/// # Example
+///
+/// ```
+/// use wolfram_expr::{BigReal, Expr};
+///
+/// let pi = BigReal::parse("3.14159265358979323846264338328`30.").unwrap();
+///
+/// assert_eq!(pi.precision(), 30.0);
+/// assert_eq!(
+///     Expr::big_real(pi).to_string(),
+///     "3.14159265358979323846264338328`30"
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd)]
+pub struct BigReal {
+    value: DBig,
+    precision: F64,
+}
+
+impl BigReal {
+    /// Construct a new arbitrary-precision real number with the specified precision, in
+    /// decimal digits.
+    pub fn new(value: DBig, precision: F64) -> Self {
+        BigReal { value, precision }
+    }
+
+    /// Parse a Wolfram Language arbitrary-precision real number with an explicit
+    /// precision mark, e.g. `` 1.5`30 `` or `` -2.25`20.5*^-10 ``.
+    ///
+    /// Returns `None` if `input` is not a number in this form.
+    pub fn parse(input: &str) -> Option<Self> {
+        let (mantissa, rest) = input.split_once('`')?;
+
+        let (precision, exponent) = match rest.split_once("*^") {
+            Some((precision, exponent)) => (precision, Some(exponent)),
+            None => (rest, None),
+        };
+
+        if !is_decimal(mantissa.strip_prefix('-').unwrap_or(mantissa)) {
+            return None;
+        }
+
+        // `f64::from_str` accepts forms such as `inf` and `1e5` that are not valid
+        // Wolfram Language precisions.
+        if !is_decimal(precision) {
+            return None;
+        }
+        let precision = F64::new(f64::from_str(precision).ok()?).ok()?;
+
+        let value = match exponent {
+            Some(exponent) => {
+                let digits = exponent.strip_prefix('-').unwrap_or(exponent);
+                if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit()) {
+                    return None;
+                }
+
+                DBig::from_str(&format!("{}e{}", mantissa, exponent)).ok()?
+            },
+            None => DBig::from_str(mantissa).ok()?,
+        };
+
+        Some(BigReal { value, precision })
+    }
+
+    /// The value of this number.
+    pub fn value(&self) -> &DBig {
+        &self.value
+    }
+
+    /// The precision of this number, in decimal digits.
+    pub fn precision(&self) -> f64 {
+        *self.precision
+    }
+
+    /// Convert this number to the nearest `f64`.
+    pub fn to_f64(&self) -> f64 {
+        self.value.to_f64().value()
+    }
+}
+
+/// Returns `true` if `string` is a non-empty sequence of digits with at most one `.`.
+fn is_decimal(string: &str) -> bool {
+    let digits = string.bytes().filter(u8::is_ascii_digit).count();
+    let points = string.bytes().filter(|&b| b == b'.').count();
+
+    digits > 0 && points <= 1 && digits + points == string.len()
+}
+
+impl Hash for BigReal {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        let BigReal { value, precision } = self;
+
+        // Equal `DBig` values have the same normalized representation.
+        value.repr().significand().hash(state);
+        value.repr().exponent().hash(state);
+        precision.hash(state);
+    }
+}
+
+impl Expr {
+    /// Construct a new arbitrary-precision real number expression.
+    ///
+    /// This function requires the `bigreal` crate feature.
+    pub fn big_real(real: BigReal) -> Expr {
+        Expr::new(ExprKind::BigReal(real))
+    }
+}
+
+impl From<BigReal> for Expr {
+    fn from(real: BigReal) -> Expr {
+        Expr::big_real(real)
+    }
+}
+
+impl From<BigReal> for Number {
+    fn from(real: BigReal) -> Number {
+        Number::BigReal(real)
+    }
+}
+
+/// Formats this number in Wolfram Language syntax, as a mantissa followed by a
+/// precision mark and an optional exponent, e.g. `` 1.5`30*^-10 ``.
+impl fmt::Display for BigReal {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let repr = self.value.repr();
+        let significand = repr.significand();
+
+        if *significand == 0.into() {
+            return write!(f, "0.`{}", self.precision);
+        }
+
+        let significand = significand.to_string();
+        let (sign, digits) = match significand.strip_prefix('-') {
+            Some(digits) => ("-", digits),
+            None => ("", significand.as_str()),
+        };
+
+        // Normalize the mantissa to a single digit before the decimal point.
+        let exponent = repr.exponent() + digits.len() as isize - 1;
+
+        write!(
+            f,
+            "{}{}.{}`{}",
+            sign,
+            &digits[..1],
+            &digits[1..],
+            self.precision
+        )?;
+
+        if exponent != 0 {
+            write!(f, "*^{}", exponent)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[test]
+fn test_big_real_parse() {
+    let parse = |input: &str| BigReal::parse(input).map(|real| real.to_string());
+
+    assert_eq!(parse("1.5`30"), Some("1.5`30".to_string()));
+    assert_eq!(parse("1.5`30."), Some("1.5`30".to_string()));
+    assert_eq!(parse("-1.50`20.5"), Some("-1.5`20.5".to_string()));
+    assert_eq!(parse("150.`10"), Some("1.5`10*^2".to_string()));
+    assert_eq!(parse("0.00125`10"), Some("1.25`10*^-3".to_string()));
+    assert_eq!(parse("1.5`30*^-10"), Some("1.5`30*^-10".to_string()));
+    assert_eq!(parse("3`5*^2"), Some("3.`5*^2".to_string()));
+    assert_eq!(parse("0.`30"), Some("0.`30".to_string()));
+
+    assert_eq!(parse("1.5"), None);
+    assert_eq!(parse("1.5``30"), None);
+    assert_eq!(parse("`30"), None);
+    assert_eq!(parse("1.5`"), None);
+    assert_eq!(parse("1.5`inf"), None);
+    assert_eq!(parse("1.5`1e5"), None);
+    assert_eq!(parse("1.5`30*^"), None);
+    assert_eq!(parse("1.5`30*^1.5"), None);
+    assert_eq!(parse("1e5`30"), None);
+}
+
+#[test]
+fn test_big_real_precision() {
+    let real = BigReal::parse("2.718281828459045235360287471352662`33.5").unwrap();
+
+    assert_eq!(real.precision(), 33.5);
+    assert_eq!(real.to_f64(), core::f64::consts::E);
+
+    // The precision is part of the value.
+    assert_ne!(
+        real,
+        BigReal::parse("2.718281828459045235360287471352662`30").unwrap()
+    );
+}
+
+#[test]
+fn test_big_real_round_trip() {
+    let expr = Expr::list(vec![
+        Expr::big_real(BigReal::parse("-1.25`40*^-100").unwrap()),
+        Expr::number(Number::from(BigReal::parse("3.`5").unwrap())),
+    ]);
+
+    assert_eq!(Expr::from_bytes(&expr.to_bytes()), Ok(expr.clone()));
+
+    #[cfg(feature = "std")]
+    {
+        let mut wxf = alloc::vec::Vec::new();
+        expr.write_wxf(&mut wxf).unwrap();
+        assert_eq!(Expr::read_wxf(&wxf[..]).unwrap(), expr);
+    }
+
+    assert_eq!(
+        expr.try_as_normal().unwrap().elements()[1].try_as_number(),
+        Some(Number::BigReal(BigReal::parse("3`5").unwrap()))
+    );
+}
@@ -0,0 +1,235 @@
+//! Incremental construction of normal expressions.
+
+use alloc::vec::Vec;
+
+use crate::{Expr, Normal};
+
+
+/// Builder for a normal expression whose elements are produced one at a time.
+///
+/// # Example
+///
+/// Construct `f[1, 2, 3]`:
+///
+/// ```
+/// use wolfram_expr::{Expr, NormalBuilder, Symbol};
+///
+/// let mut builder = NormalBuilder::new(Symbol::new("Global`f"));
+/// builder.push(Expr::from(1));
+/// builder.extend((2..=3).map(Expr::from));
+///
+/// assert_eq!(
+///     builder.build(),
+///     Expr::normal(Symbol::new("Global`f"), vec![
+///         Expr::from(1),
+///         Expr::from(2),
+///         Expr::from(3)
+///     ])
+/// );
+/// ```
+#[derive(Debug, Clone)]
+pub struct NormalBuilder {
+    head: Expr,
+    elements: Vec<Expr>,
+}
+
+impl NormalBuilder {
+    /// Construct a new builder for a normal expression with head `head` and no
+    /// elements.
+    pub fn new<H: Into<Expr>>(head: H) -> Self {
+        NormalBuilder {
+            head: head.into(),
+            elements: Vec::new(),
+        }
+    }
+
+    /// Construct a new builder for a normal expression with head `head`, with space
+    /// reserved for at least `capacity` elements.
+    pub fn with_capacity<H: Into<Expr>>(head: H, capacity: usize) -> Self {
+        NormalBuilder {
+            head: head.into(),
+            elements: Vec::with_capacity(capacity),
+        }
+    }
+
+    /// Reserve space for at least `additional` more elements.
+    pub fn reserve(&mut self, additional: usize) {
+        self.elements.reserve(additional)
+    }
+
+    /// Append `elem` to the elements of the expression being built.
+    pub fn push(&mut self, elem: Expr) {
+        self.elements.push(elem)
+    }
+
+    /// The elements that have been added to this builder so far.
+    pub fn elements(&self) -> &[Expr] {
+        &self.elements
+    }
+
+    /// Construct the normal expression from the head and the elements added to this
+    /// builder.
+    pub fn build(self) -> Expr {
+        Expr::from(self.build_normal())
+    }
+
+    /// Construct the [`Normal`] from the head and the elements added to this builder.
+    pub fn build_normal(self) -> Normal {
+        Normal::new(self.head, self.elements)
+    }
+}
+
+/// Append elements to the expression being built.
+///
+/// Space for the elements is reserved up front using the iterator's size hint.
+impl Extend<Expr> for NormalBuilder {
+    fn extend<I: IntoIterator<Item = Expr>>(&mut self, iter: I) {
+        self.elements.extend(iter)
+    }
+}
+
+//======================================
+// Decoding
+//======================================
+
+/// Builder for nested expressions read in prefix order, as in WXF and the binary
+/// encoding, without recursion.
+///
+/// Decoders use this instead of recursing into the head and elements of each normal
+/// expression, so that deeply nested input cannot overflow the stack.
+pub(crate) struct NestedBuilder {
+    /// The normal expressions that have not been completed yet, innermost last.
+    stack: Vec<Partial>,
+    /// Constructs the expression for each completed normal expression.
+    complete: fn(Normal) -> Expr,
+    /// The number of elements that can still be reserved up front, across every
+    /// expression on `stack`.
+    reserve_budget: usize,
+}
+
+/// A normal expression whose head or elements have not all been read yet.
+struct Partial {
+    /// The number of elements, not counting the head.
+    len: u64,
+    head: Option<Expr>,
+    elements: Vec<Expr>,
+    /// The number of elements reserved from [`NestedBuilder::reserve_budget`].
+    reserved: usize,
+}
+
+/// The maximum total number of elements reserved up front by a [`NestedBuilder`].
+///
+/// Element counts read from the input can't be trusted, and a small input can begin
+/// many nested expressions that each claim to be large. Beyond this budget, elements
+/// are only allocated as they are read.
+const RESERVE_BUDGET: usize = 4096;
+
+impl NestedBuilder {
+    pub fn new(complete: fn(Normal) -> Expr) -> Self {
+        NestedBuilder {
+            stack: Vec::new(),
+            complete,
+            reserve_budget: RESERVE_BUDGET,
+        }
+    }
+
+    /// Begin a normal expression with `len` elements. The next expression added is its
+    /// head.
+    ///
+    /// Space is reserved for up to `capacity` elements, within the total budget of
+    /// [`RESERVE_BUDGET`] elements for the expressions that have not been completed.
+    pub fn begin(&mut self, len: u64, capacity: usize) {
+        let reserved = capacity.min(self.reserve_budget);
+        self.reserve_budget -= reserved;
+
+        self.stack.push(Partial {
+            len,
+            head: None,
+            elements: Vec::with_capacity(reserved),
+            reserved,
+        });
+    }
+
+    /// Add `expr` to the innermost normal expression that has not been completed.
+    ///
+    /// Returns the outermost expression once it is complete.
+    pub fn add(&mut self, mut expr: Expr) -> Option<Expr> {
+        while let Some(mut partial) = self.stack.pop() {
+            if partial.head.is_none() {
+                partial.head = Some(expr);
+            } else {
+                partial.elements.push(expr);
+            }
+
+            match partial.head {
+                Some(head) if partial.elements.len() as u64 == partial.len => {
+                    self.reserve_budget += partial.reserved;
+                    expr = (self.complete)(Normal::new(head, partial.elements));
+                },
+                _ => {
+                    self.stack.push(partial);
+                    return None;
+                },
+            }
+        }
+
+        Some(expr)
+    }
+}
+
+#[test]
+fn test_normal_builder() {
+    use crate::{expr, Symbol};
+
+    let mut builder = NormalBuilder::new(Symbol::new("Global`f"));
+    for i in 1..=3 {
+        builder.push(Expr::from(i));
+    }
+    assert_eq!(builder.elements().len(), 3);
+    assert_eq!(
+        builder.build(),
+        Expr::normal(Symbol::new("Global`f"), vec![
+            Expr::from(1),
+            Expr::from(2),
+            Expr::from(3)
+        ])
+    );
+
+    // Expression heads.
+    let mut builder = NormalBuilder::with_capacity(expr!(f[x]), 2);
+    builder.extend(vec![expr!(1), expr!(g[2])]);
+    assert_eq!(builder.build(), expr!(f[x][1, g[2]]));
+
+    // No elements.
+    assert_eq!(
+        NormalBuilder::new(Symbol::new("Global`f")).build(),
+        expr!(f[])
+    );
+}
+
+#[test]
+fn test_nested_builder_reserve_budget() {
+    let mut builder = NestedBuilder::new(Expr::from);
+
+    // Many nested expressions that each claim to be large reserve at most the budget
+    // in total.
+    for _ in 0..1000 {
+        builder.begin(1024, 1024);
+    }
+    let reserved: usize = builder
+        .stack
+        .iter()
+        .map(|partial| partial.elements.capacity())
+        .sum();
+    assert!(reserved <= RESERVE_BUDGET);
+    assert_eq!(builder.reserve_budget, 0);
+
+    // Completed expressions return their reservation to the budget.
+    let mut builder = NestedBuilder::new(Expr::from);
+    builder.begin(1, 1024);
+    builder.begin(0, 1024);
+    assert_eq!(builder.add(Expr::from(1)), None);
+    assert_eq!(builder.reserve_budget, RESERVE_BUDGET - 1024);
+    assert!(builder.add(Expr::from(2)).is_some());
+    assert_eq!(builder.reserve_budget, RESERVE_BUDGET);
+}
@@ -0,0 +1,534 @@
+//! Compact binary encoding of expressions, for round-trips within this crate.
+//!
+//! # Format
+//!
+//! The encoding begins with a single version byte (currently `1`), followed by the
+//! encoded expression. Each expression begins with a tag byte:
+//!
+//! Tag            | Contents
+//! ---------------|----------------------------------------------------------------
+//! `0` (integer)  | 8 byte little-endian `i64`
+//! `1` (real)     | 8 byte little-endian `f64`
+//! `2` (string)   | varint byte length, followed by UTF-8 bytes
+//! `3` (symbol)   | varint byte length, followed by the UTF-8 fully qualified name
+//! `4` (normal)   | varint element count, followed by the head and then each element
+//! `5` (big real) | varint byte length, followed by the UTF-8 `Display` form
+//! `6` (big int)  | varint byte length, followed by the UTF-8 decimal digits
+//! `7` (rational) | varint byte length, followed by the UTF-8 fraction, e.g. `-2/3`
+//!
+//! Varints use the same representation as WXF lengths: 7 bits per byte, least
+//! significant group first, with the high bit set on every byte except the last.
+
+#[cfg(any(feature = "bigreal", feature = "bigint"))]
+use alloc::string::ToString;
+use alloc::{string::String, vec, vec::Vec};
+use core::fmt;
+
+use crate::{builder::NestedBuilder, Expr, ExprKind, Symbol};
+
+
+/// Version of the encoding produced by [`Expr::to_bytes()`].
+const VERSION: u8 = 1;
+
+//======================================
+// Tags
+//======================================
+
+const INTEGER: u8 = 0;
+const REAL: u8 = 1;
+const STRING: u8 = 2;
+const SYMBOL: u8 = 3;
+const NORMAL: u8 = 4;
+#[cfg(feature = "bigreal")]
+const BIG_REAL: u8 = 5;
+#[cfg(feature = "bigint")]
+const BIG_INTEGER: u8 = 6;
+#[cfg(feature = "rational")]
+const RATIONAL: u8 = 7;
+
+/// Error that can occur while decoding the output of [`Expr::to_bytes()`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum DecodeError {
+    /// The input was empty, or began with an unsupported format version.
+    UnsupportedVersion(Option<u8>),
+    /// The input ended in the middle of an expression.
+    UnexpectedEnd,
+    /// The input contained a byte that is not a valid tag.
+    UnknownTag(u8),
+    /// A length prefix was malformed or did not fit in 64 bits.
+    InvalidLength,
+    /// A string or symbol contained bytes that are not valid UTF-8.
+    InvalidUtf8,
+    /// A symbol contained a string that is not a valid fully qualified symbol.
+    InvalidSymbol(String),
+    /// A real number was NaN.
+    NaN,
+    /// An arbitrary-precision real number contained a string that is not a valid real
+    /// number.
+    InvalidBigReal(String),
+    /// An arbitrary-precision integer contained a string that is not a valid integer.
+    InvalidBigInteger(String),
+    /// A rational number contained a string that is not a valid fraction.
+    InvalidRational(String),
+    /// The input contained bytes after the end of the expression.
+    TrailingBytes,
+}
+
+impl Expr {
+    /// Encode this expression in a compact binary format.
+    ///
+    /// The encoding round-trips exactly through [`Expr::from_bytes()`], and is faster
+    /// to produce and decode than WXF. It is intended for caching
+    /// expressions within programs that use this crate; use WXF to exchange
+    /// expressions with the Wolfram Language.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use wolfram_expr::{expr, Expr};
+    ///
+    /// let expr = expr!(f[1, 2.5, "three"]);
+    ///
+    /// assert_eq!(Expr::from_bytes(&expr.to_bytes()), Ok(expr));
+    /// ```
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = vec![VERSION];
+        encode(&mut bytes, self);
+        bytes
+    }
+
+    /// Decode an expression produced by [`Expr::to_bytes()`].
+    ///
+    /// Returns an error if `bytes` contains anything other than exactly one encoded
+    /// expression.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Expr, DecodeError> {
+        let mut decoder = Decoder { bytes };
+
+        match decoder.byte() {
+            Ok(VERSION) => (),
+            Ok(other) => return Err(DecodeError::UnsupportedVersion(Some(other))),
+            Err(_) => return Err(DecodeError::UnsupportedVersion(None)),
+        }
+
+        let expr = decoder.expr()?;
+
+        if !decoder.bytes.is_empty() {
+            return Err(DecodeError::TrailingBytes);
+        }
+
+        Ok(expr)
+    }
+}
+
+//======================================
+// Encoding
+//======================================
+
+fn encode(bytes: &mut Vec<u8>, expr: &Expr) {
+    match expr.kind() {
+        ExprKind::Integer(int) => {
+            bytes.push(INTEGER);
+            bytes.extend_from_slice(&int.to_le_bytes());
+        },
+        ExprKind::Real(real) => {
+            bytes.push(REAL);
+            bytes.extend_from_slice(&real.into_inner().to_le_bytes());
+        },
+        #[cfg(feature = "bigint")]
+        ExprKind::BigInteger(int) => encode_str(bytes, BIG_INTEGER, &int.to_string()),
+        #[cfg(feature = "rational")]
+        ExprKind::Rational(rational) => {
+            encode_str(bytes, RATIONAL, &rational.to_string())
+        },
+        #[cfg(feature = "bigreal")]
+        ExprKind::BigReal(real) => encode_str(bytes, BIG_REAL, &real.to_string()),
+        ExprKind::String(string) => encode_str(bytes, STRING, string),
+        ExprKind::Symbol(symbol) => encode_str(bytes, SYMBOL, symbol.as_str()),
+        ExprKind::Normal(normal) => {
+            bytes.push(NORMAL);
+            encode_varint(bytes, normal.elements().len() as u64);
+
+            encode(bytes, normal.head());
+            for elem in normal.elements() {
+                encode(bytes, elem);
+            }
+        },
+    }
+}
+
+fn encode_str(bytes: &mut Vec<u8>, tag: u8, string: &str) {
+    bytes.push(tag);
+    encode_varint(bytes, string.len() as u64);
+    bytes.extend_from_slice(string.as_bytes());
+}
+
+fn encode_varint(bytes: &mut Vec<u8>, mut value: u64) {
+    loop {
+        let byte = (value & 0x7F) as u8;
+        value >>= 7;
+
+        if value == 0 {
+            bytes.push(byte);
+            return;
+        }
+
+        bytes.push(byte | 0x80);
+    }
+}
+
+//======================================
+// Decoding
+//======================================
+
+struct Decoder<'b> {
+    /// The bytes that have not been decoded yet.
+    bytes: &'b [u8],
+}
+
+impl<'b> Decoder<'b> {
+    /// Decode one expression.
+    ///
+    /// Normal expressions are decoded using a [`NestedBuilder`] rather than by
+    /// recursion, so that deeply nested input cannot overflow the stack.
+    fn expr(&mut self) -> Result<Expr, DecodeError> {
+        let mut builder = NestedBuilder::new(Expr::from);
+
+        loop {
+            let expr = match self.byte()? {
+                INTEGER => Expr::from(i64::from_le_bytes(self.array()?)),
+                REAL => {
+                    let real = f64::from_le_bytes(self.array()?);
+                    let real =
+                        ordered_float::NotNan::new(real).map_err(|_| DecodeError::NaN)?;
+
+                    Expr::new(ExprKind::Real(real))
+                },
+                STRING => Expr::string(self.str()?),
+                SYMBOL => {
+                    let name = self.str()?;
+
+                    match Symbol::try_new(name) {
+                        Some(symbol) => Expr::symbol(symbol),
+                        None => {
+                            return Err(DecodeError::InvalidSymbol(String::from(name)))
+                        },
+                    }
+                },
+                NORMAL => {
+                    let len = self.varint()?;
+
+                    // Every element takes at least 2 bytes, so this bounds the
+                    // pre-allocation of each level by the size of the input. The builder
+                    // bounds the total across all levels.
+                    let capacity = len.min(self.bytes.len() as u64 / 2) as usize;
+
+                    builder.begin(len, capacity);
+                    continue;
+                },
+                #[cfg(feature = "bigreal")]
+                BIG_REAL => {
+                    let string = self.str()?;
+
+                    match crate::BigReal::parse(string) {
+                        Some(real) => Expr::big_real(real),
+                        None => {
+                            return Err(DecodeError::InvalidBigReal(String::from(string)))
+                        },
+                    }
+                },
+                #[cfg(feature = "bigint")]
+                BIG_INTEGER => {
+                    let string = self.str()?;
+
+                    match crate::big_integer::parse_big_integer(string) {
+                        Some(int) => Expr::big_integer(int),
+                        None => {
+                            return Err(DecodeError::InvalidBigInteger(String::from(
+                                string,
+                            )))
+                        },
+                    }
+                },
+                #[cfg(feature = "rational")]
+                RATIONAL => {
+                    let string = self.str()?;
+
+                    match crate::rational::parse_rational(string) {
+                        Some(rational) => Expr::from(rational),
+                        None => {
+                            return Err(DecodeError::InvalidRational(String::from(
+                                string,
+                            )))
+                        },
+                    }
+                },
+                other => return Err(DecodeError::UnknownTag(other)),
+            };
+
+            if let Some(expr) = builder.add(expr) {
+                return Ok(expr);
+            }
+        }
+    }
+
+    fn str(&mut self) -> Result<&'b str, DecodeError> {
+        let len = self.varint()?;
+
+        if len > self.bytes.len() as u64 {
+            return Err(DecodeError::UnexpectedEnd);
+        }
+
+        let (string, rest) = self.bytes.split_at(len as usize);
+        self.bytes = rest;
+
+        core::str::from_utf8(string).map_err(|_| DecodeError::InvalidUtf8)
+    }
+
+    fn varint(&mut self) -> Result<u64, DecodeError> {
+        let mut value: u64 = 0;
+
+        for shift in (0..64).step_by(7) {
+            let byte = self.byte()?;
+
+            value |= u64::from(byte & 0x7F) << shift;
+
+            if byte & 0x80 == 0 {
+                return Ok(value);
+            }
+        }
+
+        Err(DecodeError::InvalidLength)
+    }
+
+    fn byte(&mut self) -> Result<u8, DecodeError> {
+        let [byte] = self.array()?;
+        Ok(byte)
+    }
+
+    fn array<const N: usize>(&mut self) -> Result<[u8; N], DecodeError> {
+        if self.bytes.len() < N {
+            return Err(DecodeError::UnexpectedEnd);
+        }
+
+        let (array, rest) = self.bytes.split_at(N);
+        self.bytes = rest;
+
+        let mut bytes = [0; N];
+        bytes.copy_from_slice(array);
+        Ok(bytes)
+    }
+}
+
+//======================================
+// Error impls
+//======================================
+
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            DecodeError::UnsupportedVersion(Some(version)) => {
+                write!(f, "unsupported expression encoding version: {}", version)
+            },
+            DecodeError::UnsupportedVersion(None) => {
+                write!(f, "missing expression encoding version")
+            },
+            DecodeError::UnexpectedEnd => {
+                write!(f, "unexpected end of encoded expression")
+            },
+            DecodeError::UnknownTag(tag) => {
+                write!(f, "unknown encoded expression tag: 0x{:02X}", tag)
+            },
+            DecodeError::InvalidLength => {
+                write!(f, "malformed encoded expression length")
+            },
+            DecodeError::InvalidUtf8 => write!(f, "encoded string is not valid UTF-8"),
+            DecodeError::InvalidSymbol(string) => {
+                write!(f, "encoded symbol is not a valid symbol: {:?}", string)
+            },
+            DecodeError::NaN => write!(f, "encoded real is NaN"),
+            DecodeError::InvalidBigReal(string) => {
+                write!(
+                    f,
+                    "encoded big real is not a valid real number: {:?}",
+                    string
+                )
+            },
+            DecodeError::InvalidBigInteger(string) => {
+                write!(
+                    f,
+                    "encoded big integer is not a valid integer: {:?}",
+                    string
+                )
+            },
+            DecodeError::InvalidRational(string) => {
+                write!(f, "encoded rational is not a valid fraction: {:?}", string)
+            },
+            DecodeError::TrailingBytes => {
+                write!(f, "unexpected bytes after encoded expression")
+            },
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for DecodeError {}
+
+#[test]
+fn test_to_bytes() {
+    use crate::expr;
+
+    assert_eq!(
+        expr!(5).to_bytes(),
+        b"\x01\x00\x05\x00\x00\x00\x00\x00\x00\x00"
+    );
+    assert_eq!(
+        expr!(1.5).to_bytes(),
+        b"\x01\x01\x00\x00\x00\x00\x00\x00\xF8\x3F"
+    );
+    assert_eq!(expr!("ab").to_bytes(), b"\x01\x02\x02ab");
+    assert_eq!(
+        expr!(f[x]).to_bytes(),
+        b"\x01\x04\x01\x03\x08Global`f\x03\x08Global`x"
+    );
+}
+
+#[test]
+fn test_from_bytes_errors() {
+    let decode = Expr::from_bytes;
+
+    assert_eq!(decode(b""), Err(DecodeError::UnsupportedVersion(None)));
+    assert_eq!(
+        decode(b"\x02\x00"),
+        Err(DecodeError::UnsupportedVersion(Some(2)))
+    );
+    assert_eq!(decode(b"\x01"), Err(DecodeError::UnexpectedEnd));
+    assert_eq!(decode(b"\x01\x00\x05"), Err(DecodeError::UnexpectedEnd));
+    assert_eq!(decode(b"\x01\x09"), Err(DecodeError::UnknownTag(9)));
+    assert_eq!(
+        decode(b"\x01\x01\x00\x00\x00\x00\x00\x00\xF8\x7F"),
+        Err(DecodeError::NaN)
+    );
+    assert_eq!(decode(b"\x01\x02\x05ab"), Err(DecodeError::UnexpectedEnd));
+    assert_eq!(decode(b"\x01\x02\x01\xFF"), Err(DecodeError::InvalidUtf8));
+    assert_eq!(
+        decode(b"\x01\x03\x01x"),
+        Err(DecodeError::InvalidSymbol(String::from("x")))
+    );
+    assert_eq!(
+        decode(b"\x01\x04\xFF\xFF\xFF\xFF\xFF\xFF\xFF\xFF\xFF\xFF\x01"),
+        Err(DecodeError::InvalidLength)
+    );
+    assert_eq!(decode(b"\x01\x02\x00\x00"), Err(DecodeError::TrailingBytes));
+
+    // A huge element count doesn't cause a huge allocation.
+    assert_eq!(
+        decode(b"\x01\x04\xFF\xFF\xFF\xFF\x0F\x02\x00"),
+        Err(DecodeError::UnexpectedEnd)
+    );
+}
+
+#[test]
+fn test_from_bytes_deep_nesting() {
+    // Each `[NORMAL, 0]` is a normal expression with no elements, whose head is the next
+    // expression.
+    let mut bytes = vec![VERSION];
+    bytes.extend([NORMAL, 0].repeat(1_000_000));
+
+    assert_eq!(Expr::from_bytes(&bytes), Err(DecodeError::UnexpectedEnd));
+
+    bytes.push(INTEGER);
+    bytes.extend(1i64.to_le_bytes());
+    assert_eq!(Expr::from_bytes(&bytes).unwrap().depth(), 1_000_001);
+}
+
+#[test]
+fn test_from_bytes_nested_large_counts() {
+    // Each `[NORMAL, 0x80, 0x08]` claims 1024 elements, but only its head follows.
+    let mut bytes = vec![VERSION];
+    bytes.extend([NORMAL, 0x80, 0x08].repeat(300_000));
+
+    assert_eq!(Expr::from_bytes(&bytes), Err(DecodeError::UnexpectedEnd));
+}
+
+/// A random expression nested at most `depth` levels deep, for the fuzz-style tests
+/// below.
+#[cfg(test)]
+fn random_expr(rng: &mut crate::tests::Rng, depth: u32) -> Expr {
+    const SYMBOLS: &[&str] = &["System`List", "Global`x", "A`B`c", "$Ctx`$y1"];
+    const STRINGS: &[&str] = &["", "a", "multi\nline \"string\"", "∑ ∞ 🦀"];
+
+    let kind = if depth == 0 {
+        rng.below(4)
+    } else {
+        rng.below(6)
+    };
+
+    match kind {
+        0 => Expr::from(rng.next_u64() as i64),
+        1 => {
+            let real = f64::from_bits(rng.next_u64());
+            Expr::real(if real.is_nan() { 0.0 } else { real })
+        },
+        2 => Expr::from(rng.pick(STRINGS)),
+        3 => Expr::symbol(Symbol::new(rng.pick(SYMBOLS))),
+        _ => {
+            let head = random_expr(rng, depth - 1);
+            let elements = (0..rng.below(5))
+                .map(|_| random_expr(rng, depth - 1))
+                .collect();
+
+            Expr::normal(head, elements)
+        },
+    }
+}
+
+#[test]
+fn test_bytes_round_trip() {
+    use crate::Number;
+
+    let mut rng = crate::tests::Rng(0x2545_F491_4F6C_DD1D);
+
+    for _ in 0..2000 {
+        let expr = random_expr(&mut rng, 4);
+        let bytes = expr.to_bytes();
+
+        assert_eq!(Expr::from_bytes(&bytes).as_ref(), Ok(&expr), "{}", expr);
+    }
+
+    // Exact reals are preserved, including signed zeros and infinities.
+    for real in [-0.0, f64::MIN_POSITIVE, f64::INFINITY, 0.1 + 0.2] {
+        let decoded = Expr::from_bytes(&Expr::real(real).to_bytes()).unwrap();
+
+        match decoded.try_as_number() {
+            Some(Number::Real(decoded)) => assert_eq!(decoded.to_bits(), real.to_bits()),
+            other => panic!("expected real, got {:?}", other),
+        }
+    }
+
+    // Lengths that need multi-byte varints.
+    let expr = Expr::list(vec![Expr::from("a".repeat(1000)); 300]);
+    assert_eq!(Expr::from_bytes(&expr.to_bytes()), Ok(expr));
+}
+
+#[test]
+fn test_bytes_corrupted_input() {
+    let mut rng = crate::tests::Rng(0x9E37_79B9_7F4A_7C15);
+
+    for _ in 0..2000 {
+        let mut bytes = random_expr(&mut rng, 3).to_bytes();
+
+        // Truncated input is always an error.
+        let len = rng.below(bytes.len());
+        assert!(Expr::from_bytes(&bytes[..len]).is_err());
+
+        // Corrupted input must not panic, and must either fail or decode to some
+        // expression that re-encodes to the same bytes.
+        let index = rng.below(bytes.len());
+        bytes[index] = rng.next_u64() as u8;
+
+        if let Ok(expr) = Expr::from_bytes(&bytes) {
+            assert_eq!(expr.to_bytes(), bytes);
+        }
+    }
+}
@@ -0,0 +1,86 @@
+//! Global cache of symbol expressions.
+
+use std::{
+    collections::HashMap,
+    sync::{Mutex, MutexGuard},
+};
+
+use crate::{Expr, Symbol};
+
+
+/// `HashMap::new()` is not a `const fn`, so the map is created on first use.
+static SYMBOL_CACHE: Mutex<Option<HashMap<Symbol, Expr>>> = Mutex::new(None);
+
+fn symbol_cache() -> MutexGuard<'static, Option<HashMap<Symbol, Expr>>> {
+    // The map is never left in an inconsistent state, so a panic in another thread
+    // while holding the lock can be ignored.
+    SYMBOL_CACHE
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+impl Expr {
+    /// Construct a new expression from a [`Symbol`], reusing a cached allocation if this
+    /// function has previously been called with the same symbol.
+    ///
+    /// The returned expression is equal to [`Expr::symbol(s)`](Expr::symbol), but
+    /// repeated calls share a single allocation, which avoids allocating a new expression
+    /// for each occurrence of commonly used symbols like `` System`List ``.
+    ///
+    /// Cached expressions are kept alive until [`Expr::clear_symbol_cache()`] is called.
+    ///
+    /// This function requires the `std` crate feature.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use wolfram_expr::{Expr, Symbol};
+    ///
+    /// let a = Expr::symbol_cached(Symbol::new("System`Plus"));
+    /// let b = Expr::symbol_cached(Symbol::new("System`Plus"));
+    ///
+    /// assert_eq!(a, Expr::symbol(Symbol::new("System`Plus")));
+    /// assert_eq!(a, b);
+    /// ```
+    pub fn symbol_cached<S: Into<Symbol>>(s: S) -> Expr {
+        let symbol = s.into();
+
+        symbol_cache()
+            .get_or_insert_with(HashMap::new)
+            .entry(symbol)
+            .or_insert_with_key(|symbol| Expr::symbol(symbol.clone()))
+            .clone()
+    }
+
+    /// Remove all expressions cached by [`Expr::symbol_cached()`].
+    ///
+    /// Expressions previously returned by [`Expr::symbol_cached()`] are unaffected;
+    /// subsequent calls will allocate new cached expressions.
+    ///
+    /// This function requires the `std` crate feature.
+    pub fn clear_symbol_cache() {
+        *symbol_cache() = None;
+    }
+}
+
+#[test]
+fn test_symbol_cached() {
+    use std::sync::Arc;
+
+    // Use a symbol no other test caches, so that clearing the cache here can't race
+    // with another test.
+    let symbol = Symbol::new("Global`testSymbolCached");
+
+    let a = Expr::symbol_cached(symbol.clone());
+    let b = Expr::symbol_cached(symbol.clone());
+
+    assert_eq!(a, Expr::symbol(symbol.clone()));
+    assert!(Arc::ptr_eq(&a.inner, &b.inner));
+
+    Expr::clear_symbol_cache();
+
+    let c = Expr::symbol_cached(symbol);
+
+    assert_eq!(a, c);
+    assert!(!Arc::ptr_eq(&a.inner, &c.inner));
+}
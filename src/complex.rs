@@ -0,0 +1,121 @@
+//! Complex numbers, represented as `` System`Complex[re, im] `` normal expressions.
+
+use alloc::vec;
+
+use crate::{Expr, Number, Symbol};
+
+
+impl Expr {
+    /// Construct the complex number expression `` System`Complex[re, im] ``.
+    ///
+    /// Complex numbers have no dedicated [`ExprKind`](crate::ExprKind) variant; this is a
+    /// normal expression with head
+    /// [`Complex`](https://reference.wolfram.com/language/ref/Complex.html)<sub>WL</sub>.
+    ///
+    /// This function requires the `complex` crate feature.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use wolfram_expr::{Expr, Number};
+    ///
+    /// let z = Expr::complex(Number::Integer(1), Number::real(-2.5));
+    ///
+    /// assert_eq!(z.to_string(), "System`Complex[1, -2.5]");
+    /// assert_eq!(z.try_complex(), Some((Number::Integer(1), Number::real(-2.5))));
+    /// ```
+    pub fn complex(re: Number, im: Number) -> Expr {
+        Expr::normal(Symbol::new("System`Complex"), vec![
+            Expr::number(re),
+            Expr::number(im),
+        ])
+    }
+
+    /// If this is `` System`Complex[re, im] `` with number arguments, return the real and
+    /// imaginary parts. Otherwise return None.
+    ///
+    /// This function requires the `complex` crate feature.
+    pub fn try_complex(&self) -> Option<(Number, Number)> {
+        let normal = self.try_as_normal()?;
+
+        if !normal.has_head(&Symbol::new("System`Complex")) {
+            return None;
+        }
+
+        match normal.elements() {
+            [re, im] => Some((re.try_as_number()?, im.try_as_number()?)),
+            _ => None,
+        }
+    }
+}
+
+#[test]
+fn test_complex() {
+    let z = Expr::complex(Number::Integer(3), Number::Integer(-4));
+    assert_eq!(z.to_string(), "System`Complex[3, -4]");
+    assert_eq!(
+        z.try_complex(),
+        Some((Number::Integer(3), Number::Integer(-4)))
+    );
+
+    assert_eq!(
+        Expr::normal(Symbol::new("System`Complex"), vec![
+            Expr::real(0.5),
+            Expr::from(2)
+        ])
+        .try_complex(),
+        Some((Number::real(0.5), Number::Integer(2)))
+    );
+}
+
+#[test]
+fn test_try_complex_rejects_other_exprs() {
+    let complex = Symbol::new("System`Complex");
+
+    // Not `Complex`.
+    assert_eq!(Expr::from(1).try_complex(), None);
+    assert_eq!(Expr::from(complex.clone()).try_complex(), None);
+    assert_eq!(
+        Expr::normal(Symbol::new("Global`f"), vec![Expr::from(1), Expr::from(2)])
+            .try_complex(),
+        None
+    );
+    assert_eq!(
+        Expr::normal(Symbol::new("Global`Complex"), vec![
+            Expr::from(1),
+            Expr::from(2)
+        ])
+        .try_complex(),
+        None
+    );
+
+    // Wrong number of arguments.
+    assert_eq!(Expr::normal(complex.clone(), vec![]).try_complex(), None);
+    assert_eq!(
+        Expr::normal(complex.clone(), vec![Expr::from(1)]).try_complex(),
+        None
+    );
+    assert_eq!(
+        Expr::normal(complex.clone(), vec![
+            Expr::from(1),
+            Expr::from(2),
+            Expr::from(3)
+        ])
+        .try_complex(),
+        None
+    );
+
+    // Non-numeric arguments.
+    assert_eq!(
+        Expr::normal(complex.clone(), vec![
+            Expr::from(Symbol::new("Global`x")),
+            Expr::from(2)
+        ])
+        .try_complex(),
+        None
+    );
+    assert_eq!(
+        Expr::normal(complex, vec![Expr::from(1), Expr::string("2")]).try_complex(),
+        None
+    );
+}
@@ -1,7 +1,117 @@
+use core::{convert::TryFrom, iter::FromIterator};
+
 use super::*;
 
 
 impl Expr {
+    /// Returns `true` if this is not a [`Normal`] expression.
+    ///
+    /// ```
+    /// use wolfram_expr::expr;
+    ///
+    /// assert!(expr!(x).is_atom());
+    /// assert!(!expr!(f[x]).is_atom());
+    /// ```
+    #[inline]
+    pub fn is_atom(&self) -> bool {
+        !self.is_normal()
+    }
+
+    /// Returns `true` if this is a [`Normal`] expression.
+    ///
+    /// ```
+    /// use wolfram_expr::expr;
+    ///
+    /// assert!(expr!(f[]).is_normal());
+    /// assert!(!expr!(f).is_normal());
+    /// ```
+    #[inline]
+    pub fn is_normal(&self) -> bool {
+        matches!(self.kind(), ExprKind::Normal(_))
+    }
+
+    /// Returns `true` if this is a [`Symbol`] expression.
+    ///
+    /// ```
+    /// use wolfram_expr::expr;
+    ///
+    /// assert!(expr!(x).is_symbol());
+    /// assert!(!expr!("x").is_symbol());
+    /// ```
+    #[inline]
+    pub fn is_symbol(&self) -> bool {
+        matches!(self.kind(), ExprKind::Symbol(_))
+    }
+
+    /// Returns `true` if this is an integer expression, including an
+    /// [`ExprKind::BigInteger`] if the `bigint` feature is enabled.
+    ///
+    /// ```
+    /// use wolfram_expr::expr;
+    ///
+    /// assert!(expr!(5).is_integer());
+    /// assert!(!expr!(5.0).is_integer());
+    /// ```
+    #[inline]
+    pub fn is_integer(&self) -> bool {
+        match self.kind() {
+            ExprKind::Integer(_) => true,
+            #[cfg(feature = "bigint")]
+            ExprKind::BigInteger(_) => true,
+            _ => false,
+        }
+    }
+
+    /// Returns `true` if this is a real expression, including an
+    /// [`ExprKind::BigReal`] if the `bigreal` feature is enabled.
+    ///
+    /// ```
+    /// use wolfram_expr::expr;
+    ///
+    /// assert!(expr!(5.0).is_real());
+    /// assert!(!expr!(5).is_real());
+    /// ```
+    #[inline]
+    pub fn is_real(&self) -> bool {
+        match self.kind() {
+            ExprKind::Real(_) => true,
+            #[cfg(feature = "bigreal")]
+            ExprKind::BigReal(_) => true,
+            _ => false,
+        }
+    }
+
+    /// Returns `true` if this is an [`ExprKind::String`] expression.
+    ///
+    /// ```
+    /// use wolfram_expr::expr;
+    ///
+    /// assert!(expr!("x").is_string());
+    /// assert!(!expr!(x).is_string());
+    /// ```
+    #[inline]
+    pub fn is_string(&self) -> bool {
+        matches!(self.kind(), ExprKind::String(_))
+    }
+
+    /// Returns `true` if this is a [`Number`] expression, i.e. if
+    /// [`Expr::try_as_number()`] would succeed.
+    ///
+    /// ```
+    /// use wolfram_expr::expr;
+    ///
+    /// assert!(expr!(5).is_number());
+    /// assert!(expr!(5.0).is_number());
+    /// assert!(!expr!(x).is_number());
+    /// ```
+    #[inline]
+    pub fn is_number(&self) -> bool {
+        !matches!(
+            self.kind(),
+            ExprKind::Normal(_) | ExprKind::String(_) | ExprKind::Symbol(_)
+        )
+    }
+
     /// If this is a [`Normal`] expression, return that. Otherwise return None.
     pub fn try_as_normal(&self) -> Option<&Normal> {
         let ExprKind::Normal(ref normal) = self.kind() else {
@@ -41,7 +151,63 @@ impl Expr {
     pub fn try_as_number(&self) -> Option<Number> {
         match self.kind() {
             ExprKind::Integer(int) => Some(Number::Integer(*int)),
+            #[cfg(feature = "bigint")]
+            ExprKind::BigInteger(int) => Some(Number::BigInteger(int.clone())),
+            #[cfg(feature = "rational")]
+            ExprKind::Rational(rational) => Some(Number::Rational(rational.clone())),
             ExprKind::Real(real) => Some(Number::Real(*real)),
+            #[cfg(feature = "bigreal")]
+            ExprKind::BigReal(real) => Some(Number::BigReal(real.clone())),
+            ExprKind::Normal(_) | ExprKind::String(_) | ExprKind::Symbol(_) => None,
+        }
+    }
+
+    /// If this is an [`Integer`](ExprKind::Integer) expression, return its value.
+    /// Otherwise return None.
+    ///
+    /// Real numbers are never converted, even if they have an integral value.
+    ///
+    /// ```
+    /// use wolfram_expr::Expr;
+    ///
+    /// assert_eq!(Expr::from(-7).as_i64(), Some(-7));
+    /// assert_eq!(Expr::real(2.0).as_i64(), None);
+    /// ```
+    pub fn as_i64(&self) -> Option<i64> {
+        match self.kind() {
+            ExprKind::Integer(int) => Some(*int),
+            _ => None,
+        }
+    }
+
+    /// If this is a number, return its value as an [`f64`]. Otherwise return None.
+    ///
+    /// The conversion may lose precision: integers with a magnitude greater than
+    /// 2<sup>53</sup> and arbitrary-precision reals are rounded to the nearest `f64`,
+    /// and arbitrary-precision integers outside the range of `f64` are converted to an
+    /// infinity. Rational numbers are converted to the nearest `f64`.
+    ///
+    /// ```
+    /// use wolfram_expr::Expr;
+    ///
+    /// assert_eq!(Expr::from(2).as_f64(), Some(2.0));
+    /// assert_eq!(Expr::real(2.5).as_f64(), Some(2.5));
+    /// assert_eq!(Expr::from("2").as_f64(), None);
+    /// ```
+    pub fn as_f64(&self) -> Option<f64> {
+        match self.kind() {
+            ExprKind::Integer(int) => Some(*int as f64),
+            #[cfg(feature = "bigint")]
+            ExprKind::BigInteger(int) => {
+                Some(crate::big_integer::big_integer_to_f64(int))
+            },
+            #[cfg(feature = "rational")]
+            ExprKind::Rational(rational) => {
+                Some(crate::rational::rational_to_f64(rational))
+            },
+            ExprKind::Real(real) => Some(**real),
+            #[cfg(feature = "bigreal")]
+            ExprKind::BigReal(real) => Some(real.to_f64()),
             ExprKind::Normal(_) | ExprKind::String(_) | ExprKind::Symbol(_) => None,
         }
     }
@@ -93,6 +259,16 @@ impl From<Normal> for Expr {
     }
 }
 
+/// Convert `true` and `false` to the symbols `` System`True `` and `` System`False ``.
+///
+/// [`Expr::try_as_bool()`] performs the inverse conversion.
+///
+/// ```
+/// use wolfram_expr::Expr;
+///
+/// assert_eq!(Expr::from(true).to_string(), "System`True");
+/// assert_eq!(Expr::from(false).try_as_bool(), Some(false));
+/// ```
 impl From<bool> for Expr {
     fn from(value: bool) -> Self {
         Self::symbol(Symbol::new(if value { "System`True" } else { "System`False" }))
@@ -113,6 +289,54 @@ macro_rules! string_like {
 
 string_like!(&str, &String, String);
 
+/// Collect an iterator of expressions into a `` System`List `` expression.
+///
+/// ```
+/// use wolfram_expr::{expr, Expr};
+///
+/// let list: Expr = (1..=3).map(Expr::from).collect();
+///
+/// assert_eq!(list, expr!(List[1, 2, 3]));
+/// ```
+impl FromIterator<Expr> for Expr {
+    fn from_iter<I: IntoIterator<Item = Expr>>(iter: I) -> Self {
+        Self::list(iter.into_iter().collect())
+    }
+}
+
+/// Construct a `` System`List `` of the elements, like [`Expr::list()`].
+///
+/// The head is always `List`; use [`Expr::normal()`] to construct an expression with a
+/// different head.
+///
+/// ```
+/// use wolfram_expr::{expr, Expr};
+///
+/// assert_eq!(Expr::from(vec![Expr::from(1), Expr::from(2)]), expr!(List[1, 2]));
+/// assert_eq!(Expr::from(Vec::new()), expr!(List[]));
+/// ```
+impl From<Vec<Expr>> for Expr {
+    fn from(elements: Vec<Expr>) -> Self {
+        Self::list(elements)
+    }
+}
+
+/// Construct a `` System`List `` of the elements, like [`Expr::list()`].
+///
+/// The head is always `List`; use [`Expr::normal()`] to construct an expression with a
+/// different head.
+///
+/// ```
+/// use wolfram_expr::{expr, Expr};
+///
+/// assert_eq!(Expr::from([expr!(x), expr!("a")]), expr!(List[x, "a"]));
+/// ```
+impl<const N: usize> From<[Expr; N]> for Expr {
+    fn from(elements: [Expr; N]) -> Self {
+        Self::list(Vec::from(elements))
+    }
+}
+
 //--------------------
 // Integer conversions
 //--------------------
@@ -137,6 +361,196 @@ impl From<i64> for Expr {
     }
 }
 
+/// Construct a machine real, like [`Expr::real()`].
+///
+/// # Panics
+///
+/// This conversion panics if the value is NaN, which cannot be represented by an
+/// expression. Use [`Expr::try_real()`] to handle NaN values without panicking.
+///
+/// ```
+/// use wolfram_expr::Expr;
+///
+/// assert_eq!(Expr::from(2.5), Expr::real(2.5));
+/// ```
+impl From<f64> for Expr {
+    fn from(real: f64) -> Self {
+        Self::real(real)
+    }
+}
+
+/// Construct a machine real, like [`Expr::real()`]. The conversion to `f64` is exact.
+///
+/// # Panics
+///
+/// This conversion panics if the value is NaN, which cannot be represented by an
+/// expression. Use [`Expr::try_real()`] to handle NaN values without panicking.
+impl From<f32> for Expr {
+    fn from(real: f32) -> Self {
+        Self::real(f64::from(real))
+    }
+}
+
+//------------------------------
+// Conversions to Rust types
+//------------------------------
+
+/// Error returned by the [`TryFrom`] conversions from an [`Expr`] to a Rust type when
+/// the expression has a different [`ExprKind`].
+///
+/// ```
+/// use std::convert::TryFrom;
+///
+/// use wolfram_expr::Expr;
+///
+/// assert_eq!(i64::try_from(Expr::from(5)), Ok(5));
+///
+/// let error = i64::try_from(Expr::real(5.0)).unwrap_err();
+/// assert_eq!(error.to_string(), "expected an Integer expression, got a Real expression: 5.0");
+/// assert_eq!(error.into_expr(), Expr::real(5.0));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TryFromExprError {
+    expected: &'static str,
+    expr: Expr,
+}
+
+impl TryFromExprError {
+    /// The expression that could not be converted.
+    pub fn expr(&self) -> &Expr {
+        &self.expr
+    }
+
+    /// Consume this error, returning the expression that could not be converted.
+    pub fn into_expr(self) -> Expr {
+        self.expr
+    }
+}
+
+impl fmt::Display for TryFromExprError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let found = match self.expr.kind() {
+            ExprKind::Integer(_) => "an Integer",
+            #[cfg(feature = "bigint")]
+            ExprKind::BigInteger(_) => "a BigInteger",
+            #[cfg(feature = "rational")]
+            ExprKind::Rational(_) => "a Rational",
+            ExprKind::Real(_) => "a Real",
+            #[cfg(feature = "bigreal")]
+            ExprKind::BigReal(_) => "a BigReal",
+            ExprKind::String(_) => "a String",
+            ExprKind::Symbol(_) => "a Symbol",
+            ExprKind::Normal(_) => "a Normal",
+        };
+
+        write!(
+            f,
+            "expected {} expression, got {} expression: {}",
+            self.expected, found, self.expr
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for TryFromExprError {}
+
+/// Succeeds only for [`ExprKind::Integer`] expressions.
+impl TryFrom<&Expr> for i64 {
+    type Error = TryFromExprError;
+
+    fn try_from(expr: &Expr) -> Result<i64, TryFromExprError> {
+        match expr.kind() {
+            ExprKind::Integer(int) => Ok(*int),
+            _ => Err(mismatch("an Integer", expr.clone())),
+        }
+    }
+}
+
+/// Succeeds only for [`ExprKind::Integer`] expressions.
+impl TryFrom<Expr> for i64 {
+    type Error = TryFromExprError;
+
+    fn try_from(expr: Expr) -> Result<i64, TryFromExprError> {
+        i64::try_from(&expr)
+    }
+}
+
+/// Succeeds only for [`ExprKind::Real`] expressions.
+impl TryFrom<&Expr> for f64 {
+    type Error = TryFromExprError;
+
+    fn try_from(expr: &Expr) -> Result<f64, TryFromExprError> {
+        match expr.kind() {
+            ExprKind::Real(real) => Ok(**real),
+            _ => Err(mismatch("a Real", expr.clone())),
+        }
+    }
+}
+
+/// Succeeds only for [`ExprKind::Real`] expressions.
+impl TryFrom<Expr> for f64 {
+    type Error = TryFromExprError;
+
+    fn try_from(expr: Expr) -> Result<f64, TryFromExprError> {
+        f64::try_from(&expr)
+    }
+}
+
+/// Succeeds only for [`ExprKind::String`] expressions.
+impl TryFrom<&Expr> for String {
+    type Error = TryFromExprError;
+
+    fn try_from(expr: &Expr) -> Result<String, TryFromExprError> {
+        match expr.try_as_str() {
+            Some(string) => Ok(String::from(string)),
+            None => Err(mismatch("a String", expr.clone())),
+        }
+    }
+}
+
+/// Succeeds only for [`ExprKind::String`] expressions.
+///
+/// The string is moved out of `expr` without copying if `expr` is not shared.
+impl TryFrom<Expr> for String {
+    type Error = TryFromExprError;
+
+    fn try_from(expr: Expr) -> Result<String, TryFromExprError> {
+        if expr.try_as_str().is_none() {
+            return Err(mismatch("a String", expr));
+        }
+
+        match expr.to_kind() {
+            ExprKind::String(string) => Ok(string),
+            _ => unreachable!(),
+        }
+    }
+}
+
+/// Succeeds only for [`ExprKind::Symbol`] expressions.
+impl TryFrom<&Expr> for Symbol {
+    type Error = TryFromExprError;
+
+    fn try_from(expr: &Expr) -> Result<Symbol, TryFromExprError> {
+        match expr.try_as_symbol() {
+            Some(symbol) => Ok(symbol.clone()),
+            None => Err(mismatch("a Symbol", expr.clone())),
+        }
+    }
+}
+
+/// Succeeds only for [`ExprKind::Symbol`] expressions.
+impl TryFrom<Expr> for Symbol {
+    type Error = TryFromExprError;
+
+    fn try_from(expr: Expr) -> Result<Symbol, TryFromExprError> {
+        Symbol::try_from(&expr)
+    }
+}
+
+fn mismatch(expected: &'static str, expr: Expr) -> TryFromExprError {
+    TryFromExprError { expected, expr }
+}
+
 // impl From<Normal> for ExprKind {
 //     fn from(normal: Normal) -> ExprKind {
 //         ExprKind::Normal(Box::new(normal))
@@ -153,7 +567,197 @@ impl From<Number> for ExprKind {
     fn from(number: Number) -> Self {
         match number {
             Number::Integer(int) => Self::Integer(int),
+            #[cfg(feature = "bigint")]
+            Number::BigInteger(int) => Self::BigInteger(int),
+            #[cfg(feature = "rational")]
+            Number::Rational(rational) => Self::Rational(rational),
             Number::Real(real) => Self::Real(real),
+            #[cfg(feature = "bigreal")]
+            Number::BigReal(real) => Self::BigReal(real),
         }
     }
 }
+
+#[test]
+fn test_kind_predicates() {
+    use crate::expr;
+
+    let integer = expr!(1);
+    let real = expr!(1.5);
+    let string = expr!("a");
+    let symbol = expr!(x);
+    let normal = expr!(f[x]);
+
+    assert!(integer.is_number() && integer.is_integer() && !integer.is_real());
+    assert!(real.is_number() && real.is_real() && !real.is_integer());
+    assert!(!symbol.is_number() && symbol.is_symbol());
+    assert!(!string.is_number() && string.is_string() && !string.is_symbol());
+    assert!(!normal.is_number() && normal.is_normal() && !normal.is_atom());
+
+    for atom in [&integer, &real, &string, &symbol] {
+        assert!(atom.is_atom() && !atom.is_normal());
+        assert_eq!(atom.is_number(), atom.try_as_number().is_some());
+    }
+
+    #[cfg(feature = "bigint")]
+    {
+        let big = Expr::big_integer(crate::BigInt::from(i64::MAX) + 1u8);
+        assert!(big.is_integer() && big.is_number());
+    }
+    #[cfg(feature = "rational")]
+    {
+        let rational = Expr::rational(1, 2);
+        assert!(rational.is_number() && !rational.is_integer() && !rational.is_real());
+    }
+}
+
+#[test]
+fn test_bool_conversion() {
+    use crate::expr;
+
+    for value in [true, false] {
+        assert_eq!(Expr::from(value).try_as_bool(), Some(value));
+    }
+
+    assert_eq!(Expr::from(true), expr!(System::True));
+    assert_eq!(Expr::from(false), expr!(System::False));
+    assert_eq!(expr!(System::True).try_as_bool(), Some(true));
+    assert_eq!(expr!(System::False).try_as_bool(), Some(false));
+
+    // Only the `System` symbols are booleans.
+    assert_eq!(expr!(Global::True).try_as_bool(), None);
+    assert_eq!(expr!("True").try_as_bool(), None);
+    assert_eq!(expr!(1).try_as_bool(), None);
+    assert_eq!(expr!(System::True[]).try_as_bool(), None);
+}
+
+#[test]
+fn test_from_float() {
+    assert_eq!(Expr::from(2.5), Expr::real(2.5));
+    assert_eq!(Expr::from(-0.0_f64), Expr::real(-0.0));
+    assert_eq!(Expr::from(f64::INFINITY), Expr::real(f64::INFINITY));
+    assert_eq!(Expr::from(0.1_f32), Expr::real(f64::from(0.1_f32)));
+    assert_eq!(Expr::from(f32::MAX).as_f64(), Some(f32::MAX as f64));
+    assert_eq!(Expr::from(f32::NEG_INFINITY), Expr::real(f64::NEG_INFINITY));
+}
+
+#[test]
+#[should_panic(expected = "Number::real: got NaN")]
+fn test_from_f64_nan() {
+    let _ = Expr::from(f64::NAN);
+}
+
+#[test]
+#[should_panic(expected = "Number::real: got NaN")]
+fn test_from_f32_nan() {
+    let _ = Expr::from(f32::NAN);
+}
+
+#[test]
+fn test_from_list_elements() {
+    use crate::expr;
+
+    assert_eq!(Expr::from(Vec::new()), expr!(System::List[]));
+    assert_eq!(Expr::from([]), expr!(System::List[]));
+    assert_eq!(
+        Expr::from(vec![expr!(1), expr!(f[x])]),
+        expr!(System::List[1, f[x]])
+    );
+    assert_eq!(
+        Expr::from([expr!(1), expr!(f[x])]),
+        expr!(System::List[1, f[x]])
+    );
+
+    // Nested lists.
+    let nested = Expr::from([Expr::from([expr!(1)]), Expr::from(vec![])]);
+    assert_eq!(nested, expr!(System::List[System::List[1], System::List[]]));
+    assert!(nested.has_head_str("System`List"));
+}
+
+#[test]
+fn test_as_f64() {
+    use crate::expr;
+
+    assert_eq!(expr!(-3).as_f64(), Some(-3.0));
+    assert_eq!(expr!(0.125).as_f64(), Some(0.125));
+    assert_eq!(Expr::from(i64::MAX).as_f64(), Some(9223372036854775807.0));
+
+    assert_eq!(expr!(x).as_f64(), None);
+    assert_eq!(expr!("1.5").as_f64(), None);
+    assert_eq!(expr!(f[1]).as_f64(), None);
+}
+
+#[test]
+fn test_as_i64() {
+    use crate::expr;
+
+    assert_eq!(expr!(-3).as_i64(), Some(-3));
+    assert_eq!(Expr::from(i64::MIN).as_i64(), Some(i64::MIN));
+
+    assert_eq!(expr!(3.0).as_i64(), None);
+    assert_eq!(expr!("3").as_i64(), None);
+    assert_eq!(expr!(x).as_i64(), None);
+    assert_eq!(expr!(f[3]).as_i64(), None);
+}
+
+#[test]
+fn test_try_from_expr() {
+    use crate::expr;
+
+    assert_eq!(i64::try_from(expr!(-3)), Ok(-3));
+    assert_eq!(i64::try_from(&expr!(-3)), Ok(-3));
+    assert_eq!(f64::try_from(expr!(2.5)), Ok(2.5));
+    assert_eq!(f64::try_from(&expr!(2.5)), Ok(2.5));
+    assert_eq!(String::try_from(expr!("a")), Ok(String::from("a")));
+    assert_eq!(String::try_from(&expr!("a")), Ok(String::from("a")));
+    assert_eq!(Symbol::try_from(expr!(x)), Ok(Symbol::new("Global`x")));
+    assert_eq!(Symbol::try_from(&expr!(x)), Ok(Symbol::new("Global`x")));
+
+    // A shared string is copied.
+    let string = expr!("shared");
+    assert_eq!(String::try_from(string.clone()), Ok(String::from("shared")));
+    assert_eq!(string, expr!("shared"));
+}
+
+#[test]
+fn test_try_from_expr_errors() {
+    use crate::expr;
+
+    let error = |result: Result<(), TryFromExprError>| result.unwrap_err().to_string();
+
+    assert_eq!(
+        error(i64::try_from(expr!(2.0)).map(drop)),
+        "expected an Integer expression, got a Real expression: 2.0"
+    );
+    assert_eq!(
+        error(i64::try_from(&expr!("2")).map(drop)),
+        "expected an Integer expression, got a String expression: \"2\""
+    );
+    assert_eq!(
+        error(f64::try_from(expr!(2)).map(drop)),
+        "expected a Real expression, got an Integer expression: 2"
+    );
+    assert_eq!(
+        error(String::try_from(expr!(x)).map(drop)),
+        "expected a String expression, got a Symbol expression: Global`x"
+    );
+    assert_eq!(
+        error(Symbol::try_from(expr!(f[x])).map(drop)),
+        "expected a Symbol expression, got a Normal expression: Global`f[Global`x]"
+    );
+
+    let error = String::try_from(&expr!(f[1])).unwrap_err();
+    assert_eq!(error.expr(), &expr!(f[1]));
+    assert_eq!(error.into_expr(), expr!(f[1]));
+}
+
+#[test]
+fn test_from_iterator() {
+    let list: Expr = (1..=3).map(Expr::from).collect();
+
+    assert!(list.has_normal_head(&Symbol::new("System`List")));
+    assert_eq!(list.to_string(), "System`List[1, 2, 3]");
+
+    let empty: Expr = core::iter::empty().collect();
+    assert_eq!(empty, Expr::list(vec![]));
+}
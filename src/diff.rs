@@ -0,0 +1,245 @@
+//! Structural comparison of expressions.
+
+use alloc::{string::String, sync::Arc, vec::Vec};
+use core::fmt::{self, Write};
+
+use crate::{Expr, ExprKind};
+
+
+/// A position at which two expressions differ, as returned by [`Expr::diff()`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Difference {
+    /// Path to the differing subexpressions, using
+    /// [`Part`](https://reference.wolfram.com/language/ref/Part.html)<sub>WL</sub>
+    /// numbering: `0` is the head of an expression, and `1` is its first element.
+    ///
+    /// An empty path refers to the whole expression.
+    pub position: Vec<usize>,
+    /// How the subexpressions at `position` differ.
+    pub kind: DifferenceKind,
+}
+
+/// How two subexpressions differ. See [`Difference`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DifferenceKind {
+    /// The subexpressions are different atoms, or only one of them is a normal
+    /// expression.
+    Mismatch {
+        /// The subexpression of the expression `diff()` was called on.
+        expected: Expr,
+        /// The subexpression of the expression passed to `diff()`.
+        actual: Expr,
+    },
+    /// The subexpressions are normal expressions with different numbers of elements.
+    Length {
+        /// The number of elements in the subexpression of the expression `diff()` was
+        /// called on.
+        expected: usize,
+        /// The number of elements in the subexpression of the expression passed to
+        /// `diff()`.
+        actual: usize,
+    },
+}
+
+impl Expr {
+    /// Find the positions at which this expression differs from `other`.
+    ///
+    /// The two expressions are compared top-down. Once two subexpressions are found
+    /// to differ, a single [`Difference`] is reported for them and their contents are
+    /// not compared. The heads of normal expressions with the same number of elements
+    /// are compared like their elements, at position `0`.
+    ///
+    /// Returns an empty vector if the expressions are equal.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use wolfram_expr::{expr, Expr};
+    ///
+    /// let expected = expr!(f[1, g[2, 3], h[]]);
+    /// let actual = expr!(f[1, g[5, 3], h[x]]);
+    ///
+    /// let diffs: Vec<String> = expected.diff(&actual).iter().map(ToString::to_string).collect();
+    ///
+    /// assert_eq!(diffs, [
+    ///     "at {2, 1}: expected 2, got 5",
+    ///     "at {3}: expected 0 elements, got 1",
+    /// ]);
+    /// ```
+    pub fn diff(&self, other: &Expr) -> Vec<Difference> {
+        let mut diffs = Vec::new();
+        diff(self, other, &mut Vec::new(), &mut diffs);
+        diffs
+    }
+
+    /// Assert that this expression is equal to `actual`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the expressions are not equal, with a message listing each
+    /// [`Difference`] returned by [`Expr::diff()`].
+    ///
+    /// # Example
+    ///
+    /// ```should_panic
+    /// use wolfram_expr::expr;
+    ///
+    /// // Panics with the message:
+    /// //
+    /// //     expressions are not equal:
+    /// //       at {1}: expected 1, got 2
+    /// expr!(f[1]).assert_eq_pretty(&expr!(f[2]));
+    /// ```
+    #[track_caller]
+    pub fn assert_eq_pretty(&self, actual: &Expr) {
+        let diffs = self.diff(actual);
+
+        if diffs.is_empty() {
+            return;
+        }
+
+        let mut message = String::from("expressions are not equal:");
+        for diff in &diffs {
+            // Writing to a String can't fail.
+            let _ = write!(message, "\n  {}", diff);
+        }
+
+        panic!("{}", message)
+    }
+}
+
+fn diff(
+    expected: &Expr,
+    actual: &Expr,
+    position: &mut Vec<usize>,
+    diffs: &mut Vec<Difference>,
+) {
+    if Arc::ptr_eq(&expected.inner, &actual.inner) {
+        return;
+    }
+
+    let (expected_normal, actual_normal) = match (expected.kind(), actual.kind()) {
+        (ExprKind::Normal(expected), ExprKind::Normal(actual)) => (expected, actual),
+        (expected_kind, actual_kind) => {
+            if expected_kind != actual_kind {
+                diffs.push(Difference {
+                    position: position.clone(),
+                    kind: DifferenceKind::Mismatch {
+                        expected: expected.clone(),
+                        actual: actual.clone(),
+                    },
+                });
+            }
+            return;
+        },
+    };
+
+    let (expected_elements, actual_elements) =
+        (expected_normal.elements(), actual_normal.elements());
+
+    if expected_elements.len() != actual_elements.len() {
+        diffs.push(Difference {
+            position: position.clone(),
+            kind: DifferenceKind::Length {
+                expected: expected_elements.len(),
+                actual: actual_elements.len(),
+            },
+        });
+        return;
+    }
+
+    position.push(0);
+    diff(
+        expected_normal.head(),
+        actual_normal.head(),
+        position,
+        diffs,
+    );
+
+    for (index, (expected, actual)) in
+        expected_elements.iter().zip(actual_elements).enumerate()
+    {
+        *position.last_mut().unwrap() = index + 1;
+        diff(expected, actual, position, diffs);
+    }
+
+    position.pop();
+}
+
+/// Formats as e.g. `at {2, 1}: expected 2, got 5`.
+impl fmt::Display for Difference {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "at {{")?;
+        for (idx, part) in self.position.iter().enumerate() {
+            if idx != 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{}", part)?;
+        }
+        write!(f, "}}: ")?;
+
+        match &self.kind {
+            DifferenceKind::Mismatch { expected, actual } => {
+                write!(f, "expected {}, got {}", expected, actual)
+            },
+            DifferenceKind::Length { expected, actual } => {
+                write!(f, "expected {} elements, got {}", expected, actual)
+            },
+        }
+    }
+}
+
+#[test]
+fn test_diff() {
+    use crate::expr;
+
+    let mismatch = |position: &[usize], expected: Expr, actual: Expr| Difference {
+        position: position.to_vec(),
+        kind: DifferenceKind::Mismatch { expected, actual },
+    };
+
+    let expr = expr!(f[1, g[2, 3]]);
+    assert_eq!(expr.diff(&expr), []);
+    assert_eq!(expr.diff(&expr!(f[1, g[2, 3]])), []);
+
+    assert_eq!(expr!(1).diff(&expr!(2)), [mismatch(
+        &[],
+        expr!(1),
+        expr!(2)
+    )]);
+
+    // Each differing subexpression is reported, including heads.
+    assert_eq!(expr.diff(&expr!(h[1, g[2, x]])), [
+        mismatch(&[0], expr!(f), expr!(h)),
+        mismatch(&[2, 2], expr!(3), expr!(x)),
+    ]);
+
+    // Descent stops at the first differing node.
+    assert_eq!(expr.diff(&expr!(f[1, 5])), [mismatch(
+        &[2],
+        expr!(g[2, 3]),
+        expr!(5)
+    )]);
+    assert_eq!(expr.diff(&expr!(f[1, g[2, 3, 4]])), [Difference {
+        position: vec![2],
+        kind: DifferenceKind::Length {
+            expected: 2,
+            actual: 3
+        },
+    }]);
+}
+
+#[test]
+fn test_difference_display() {
+    use crate::expr;
+
+    let diffs = expr!(f[x, "a"]).diff(&expr!(f[y]));
+    assert_eq!(diffs[0].to_string(), "at {}: expected 2 elements, got 1");
+
+    let diffs = expr!(f[x, "a"]).diff(&expr!(f[y, "b"]));
+    assert_eq!(
+        diffs[0].to_string(),
+        "at {1}: expected Global`x, got Global`y"
+    );
+    assert_eq!(diffs[1].to_string(), r#"at {2}: expected "a", got "b""#);
+}
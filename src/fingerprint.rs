@@ -0,0 +1,197 @@
+//! Stable structural fingerprints of expressions.
+
+#[cfg(any(feature = "bigreal", feature = "bigint"))]
+use alloc::string::ToString;
+
+use crate::{Expr, ExprKind};
+
+
+// 128-bit FNV-1a parameters.
+const FNV_OFFSET_BASIS: u128 = 0x6c62272e_07bb0142_62b82175_6295c58d;
+const FNV_PRIME: u128 = 0x00000000_01000000_00000000_0000013b;
+
+const INTEGER: u8 = 0;
+const REAL: u8 = 1;
+const STRING: u8 = 2;
+const SYMBOL: u8 = 3;
+const NORMAL: u8 = 4;
+#[cfg(feature = "bigreal")]
+const BIG_REAL: u8 = 5;
+#[cfg(feature = "bigint")]
+const BIG_INTEGER: u8 = 6;
+#[cfg(feature = "rational")]
+const RATIONAL: u8 = 7;
+
+impl Expr {
+    /// Compute a 128-bit fingerprint of the structure of this expression.
+    ///
+    /// Unlike the [`Hash`](core::hash::Hash) implementation of [`Expr`], the
+    /// fingerprint is **stable**: it depends only on the contents of the expression,
+    /// and is the same across processes, platforms, and versions of this crate. This
+    /// makes it suitable as the key of a persistent cache.
+    ///
+    /// Equal expressions always have the same fingerprint.
+    ///
+    /// # Algorithm
+    ///
+    /// The fingerprint is the 128-bit [FNV-1a] hash of a byte encoding of the
+    /// expression. Each subexpression is encoded, in prefix order, as a tag byte
+    /// followed by its contents:
+    ///
+    /// Subexpression | Tag | Contents
+    /// --------------|-----|------------------------------------------------------------
+    /// Integer       | `0` | 8 byte little-endian `i64`
+    /// Real          | `1` | 8 byte little-endian IEEE 754 bits; `-0.0` is encoded as `0.0`
+    /// String        | `2` | 8 byte little-endian byte length, followed by UTF-8 bytes
+    /// Symbol        | `3` | 8 byte little-endian byte length, followed by the UTF-8 fully qualified name
+    /// Normal        | `4` | 8 byte little-endian element count, followed by the head and then each element
+    /// Big real      | `5` | 8 byte little-endian byte length, followed by the UTF-8 [`Display`](core::fmt::Display) form
+    /// Big integer   | `6` | 8 byte little-endian byte length, followed by the UTF-8 decimal digits, with a leading `-` if negative
+    /// Rational      | `7` | 8 byte little-endian byte length, followed by the UTF-8 reduced fraction, e.g. `-2/3`
+    ///
+    /// [FNV-1a]: http://www.isthe.com/chongo/tech/comp/fnv/index.html
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use wolfram_expr::expr;
+    ///
+    /// assert_eq!(expr!(f[1, 2]).fingerprint(), expr!(f[1, 2]).fingerprint());
+    /// assert_ne!(expr!(f[1, 2]).fingerprint(), expr!(f[2, 1]).fingerprint());
+    /// ```
+    pub fn fingerprint(&self) -> u128 {
+        let mut hasher = Fnv1a(FNV_OFFSET_BASIS);
+        hasher.expr(self);
+        hasher.0
+    }
+
+    /// Compute a stable 64-bit hash of the structure of this expression.
+    ///
+    /// This is the [`fingerprint()`](Expr::fingerprint) of this expression, with its
+    /// upper and lower 64 bits combined using XOR. Like the fingerprint, it depends only
+    /// on the contents of the expression, and is the same across processes, platforms,
+    /// and versions of this crate.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use wolfram_expr::{expr, Expr, Symbol};
+    ///
+    /// let built = Expr::normal(Symbol::new("Global`f"), vec![Expr::from(1)]);
+    ///
+    /// assert_eq!(built.content_hash(), expr!(f[1]).content_hash());
+    /// ```
+    pub fn content_hash(&self) -> u64 {
+        let fingerprint = self.fingerprint();
+
+        (fingerprint >> 64) as u64 ^ fingerprint as u64
+    }
+}
+
+struct Fnv1a(u128);
+
+impl Fnv1a {
+    fn expr(&mut self, expr: &Expr) {
+        match expr.kind() {
+            ExprKind::Integer(int) => {
+                self.write(&[INTEGER]);
+                self.write(&int.to_le_bytes());
+            },
+            ExprKind::Real(real) => {
+                // `-0.0 == 0.0`, so they must have the same fingerprint.
+                let real = if **real == 0.0 { 0.0 } else { **real };
+
+                self.write(&[REAL]);
+                self.write(&real.to_bits().to_le_bytes());
+            },
+            #[cfg(feature = "bigint")]
+            ExprKind::BigInteger(int) => self.str(BIG_INTEGER, &int.to_string()),
+            #[cfg(feature = "rational")]
+            ExprKind::Rational(rational) => self.str(RATIONAL, &rational.to_string()),
+            #[cfg(feature = "bigreal")]
+            ExprKind::BigReal(real) => self.str(BIG_REAL, &real.to_string()),
+            ExprKind::String(string) => self.str(STRING, string),
+            ExprKind::Symbol(symbol) => self.str(SYMBOL, symbol.as_str()),
+            ExprKind::Normal(normal) => {
+                self.write(&[NORMAL]);
+                self.write(&(normal.elements().len() as u64).to_le_bytes());
+
+                self.expr(normal.head());
+                for elem in normal.elements() {
+                    self.expr(elem);
+                }
+            },
+        }
+    }
+
+    fn str(&mut self, tag: u8, string: &str) {
+        self.write(&[tag]);
+        self.write(&(string.len() as u64).to_le_bytes());
+        self.write(string.as_bytes());
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 ^= u128::from(*byte);
+            self.0 = self.0.wrapping_mul(FNV_PRIME);
+        }
+    }
+}
+
+#[test]
+fn test_fingerprint_fnv1a() {
+    // Test vectors from the FNV reference implementation.
+    let fnv1a = |bytes: &[u8]| {
+        let mut hasher = Fnv1a(FNV_OFFSET_BASIS);
+        hasher.write(bytes);
+        hasher.0
+    };
+
+    assert_eq!(fnv1a(b""), 0x6c62272e_07bb0142_62b82175_6295c58d);
+    assert_eq!(fnv1a(b"a"), 0xd228cb69_6f1a8caf_78912b70_4e4a8964);
+}
+
+#[test]
+fn test_fingerprint() {
+    use crate::expr;
+
+    // The fingerprint must never change; see the documentation of `fingerprint()`.
+    assert_eq!(
+        expr!(System::List[1, -2.5, "a", Global::x, f[]]).fingerprint(),
+        0xd43a6fe2_a46d7298_a8b341d1_05ab57bd
+    );
+
+    assert_eq!(expr!(0.0).fingerprint(), expr!(-0.0).fingerprint());
+    assert_ne!(expr!(1).fingerprint(), expr!(1.0).fingerprint());
+    assert_ne!(expr!("x").fingerprint(), expr!(x).fingerprint());
+    assert_ne!(expr!(f[g][]).fingerprint(), expr!(f[g]).fingerprint());
+}
+
+#[test]
+fn test_content_hash() {
+    use alloc::{string::String, vec};
+
+    use crate::{expr, Symbol};
+
+    // The hash must never change; see the documentation of `content_hash()`.
+    assert_eq!(
+        expr!(System::List[1, -2.5, "a", Global::x, f[]]).content_hash(),
+        0x7c892e33_a1c62525
+    );
+
+    // Independently constructed equal expressions have the same hash.
+    let built = Expr::normal(Symbol::new("System`List"), vec![
+        Expr::from(1),
+        Expr::real(-2.5),
+        Expr::string(String::from("a")),
+        Expr::symbol(Symbol::new("Global`x")),
+        Expr::normal(Symbol::new("Global`f"), vec![]),
+    ]);
+    assert_eq!(
+        built.content_hash(),
+        expr!(System::List[1, -2.5, "a", Global::x, f[]]).content_hash()
+    );
+
+    assert_ne!(expr!(f[1, 2]).content_hash(), expr!(f[2, 1]).content_hash());
+    assert_ne!(expr!(1).content_hash(), expr!(1.0).content_hash());
+}
@@ -0,0 +1,984 @@
+//! Alternative textual forms of expressions.
+
+use alloc::{
+    format,
+    string::{String, ToString},
+};
+use core::fmt::{self, Write};
+
+use crate::{Expr, ExprKind, Normal, Symbol};
+
+
+/// Formats an [`Expr`] in
+/// [`FullForm`](https://reference.wolfram.com/language/ref/FullForm.html)<sub>WL</sub>.
+///
+/// Construct this type using [`Expr::full_form()`].
+#[derive(Debug, Clone, Copy)]
+pub struct FullForm<'e>(&'e Expr);
+
+/// Formats an [`Expr`] in an approximation of
+/// [`InputForm`](https://reference.wolfram.com/language/ref/InputForm.html)<sub>WL</sub>.
+///
+/// Construct this type using [`Expr::input_form()`].
+#[derive(Debug, Clone, Copy)]
+pub struct InputForm<'e>(&'e Expr);
+
+impl Expr {
+    /// Format this expression in
+    /// [`FullForm`](https://reference.wolfram.com/language/ref/FullForm.html)<sub>WL</sub>.
+    ///
+    /// Every normal expression is formatted as `head[elem1, elem2, ...]`, symbols are
+    /// formatted with their full context, and strings are quoted and escaped. Machine
+    /// reals are always formatted with a decimal point, using `*^` for exponents, so
+    /// that they are distinct from integers: `1.0` is formatted as `1.`, and `1e-15` as
+    /// `1.*^-15`. Infinite reals are formatted as `` System`DirectedInfinity[1] `` or
+    /// `` System`DirectedInfinity[-1] ``.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use wolfram_expr::expr;
+    ///
+    /// let expr = expr!(System::Plus[1.0, System::Times[2, x], "a"]);
+    ///
+    /// assert_eq!(
+    ///     expr.full_form().to_string(),
+    ///     r#"System`Plus[1., System`Times[2, Global`x], "a"]"#
+    /// );
+    /// ```
+    pub fn full_form(&self) -> FullForm<'_> {
+        FullForm(self)
+    }
+
+    /// Format this expression in an approximation of
+    /// [`InputForm`](https://reference.wolfram.com/language/ref/InputForm.html)<sub>WL</sub>,
+    /// for human-readable output.
+    ///
+    /// Normal expressions with the following `` System` `` heads are formatted using
+    /// operator syntax, with parentheses added where precedence requires them:
+    ///
+    /// Head                                                     | Syntax
+    /// ---------------------------------------------------------|----------------------
+    /// `Power`                                                  | `a^b`
+    /// `Times`                                                  | `a b c`, `-a`
+    /// `Plus`                                                   | `a + b - c`
+    /// `Equal`, `Unequal`, `Less`, `Greater`, `LessEqual`, `GreaterEqual` | `a == b`, `a != b`, `a < b`, ...
+    /// `Not`                                                    | `!a`
+    /// `And`, `Or`                                              | `a && b`, `a \|\| b`
+    /// `Rule`, `RuleDelayed`                                    | `a -> b`, `a :> b`
+    /// `Set`, `SetDelayed`                                      | `a = b`, `a := b`
+    /// `List`                                                   | `{a, b}`
+    /// `Association`                                            | `<\|a -> b\|>`
+    ///
+    /// Any other normal expression, or an expression with one of these heads but an
+    /// unsupported number of elements, is formatted as `head[elem1, elem2, ...]`.
+    /// Symbols in the `` System` `` and `` Global` `` contexts are formatted without
+    /// their context.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use wolfram_expr::expr;
+    ///
+    /// let expr = expr!(System::Times[System::Plus[a, b], c]);
+    ///
+    /// assert_eq!(expr.input_form().to_string(), "(a + b) c");
+    /// ```
+    pub fn input_form(&self) -> InputForm<'_> {
+        InputForm(self)
+    }
+}
+
+impl fmt::Display for FullForm<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let FullForm(expr) = *self;
+
+        match expr.kind() {
+            ExprKind::Normal(normal) => {
+                write!(f, "{}[", normal.head().full_form())?;
+                for (idx, elem) in normal.elements().iter().enumerate() {
+                    if idx != 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{}", elem.full_form())?;
+                }
+                write!(f, "]")
+            },
+            ExprKind::Real(real) => fmt_real(f, **real),
+            ExprKind::Integer(_) | ExprKind::String(_) | ExprKind::Symbol(_) => {
+                write!(f, "{}", expr)
+            },
+            #[cfg(feature = "bigint")]
+            ExprKind::BigInteger(_) => write!(f, "{}", expr),
+            #[cfg(feature = "rational")]
+            ExprKind::Rational(_) => write!(f, "{}", expr),
+            #[cfg(feature = "bigreal")]
+            ExprKind::BigReal(_) => write!(f, "{}", expr),
+        }
+    }
+}
+
+//======================================
+// InputForm
+//======================================
+
+// Operator precedences, following `Precedence` in the Wolfram Language. Operators with
+// larger precedences bind more tightly.
+const SET: u16 = 40;
+const RULE: u16 = 120;
+const OR: u16 = 210;
+const AND: u16 = 215;
+const NOT: u16 = 230;
+const COMPARISON: u16 = 290;
+const PLUS: u16 = 310;
+const TIMES: u16 = 400;
+const NEGATIVE: u16 = 480;
+const POWER: u16 = 590;
+/// Precedence of atoms and of expressions formatted using brackets.
+const ATOM: u16 = u16::MAX;
+
+/// How an operator groups repeated uses of itself.
+#[derive(Clone, Copy)]
+enum Associativity {
+    /// `a op b op c` is `op[a, b, c]`.
+    Flat,
+    /// `a op b op c` is `op[a, op[b, c]]`.
+    Right,
+    /// `a op b op c` is not `op[a, op[b, c]]` or `op[op[a, b], c]`.
+    None,
+}
+
+/// Operator syntax used to format a normal expression.
+#[derive(Clone, Copy)]
+enum Syntax {
+    /// Like `Infix` with a `+` separator, but formats negative terms using `-`.
+    Sum,
+    Infix {
+        separator: &'static str,
+        precedence: u16,
+        associativity: Associativity,
+    },
+    Prefix {
+        operator: &'static str,
+        precedence: u16,
+    },
+    Brackets {
+        open: &'static str,
+        close: &'static str,
+    },
+}
+
+/// The operator syntax used to format `normal`, or `None` if it is formatted as
+/// `head[...]`.
+fn syntax(normal: &Normal) -> Option<Syntax> {
+    use self::Associativity::{Flat, None as NonAssociative, Right};
+
+    let infix = |separator, precedence, associativity| Syntax::Infix {
+        separator,
+        precedence,
+        associativity,
+    };
+
+    let name = normal
+        .head()
+        .try_as_symbol()?
+        .as_str()
+        .strip_prefix("System`")?;
+    let len = normal.elements().len();
+
+    let syntax = match name {
+        "Power" if len == 2 => infix("^", POWER, Right),
+        "Times" if len >= 2 => infix(" ", TIMES, Flat),
+        "Plus" if len >= 2 => Syntax::Sum,
+        "Equal" if len >= 2 => infix(" == ", COMPARISON, NonAssociative),
+        "Unequal" if len >= 2 => infix(" != ", COMPARISON, NonAssociative),
+        "Less" if len >= 2 => infix(" < ", COMPARISON, NonAssociative),
+        "Greater" if len >= 2 => infix(" > ", COMPARISON, NonAssociative),
+        "LessEqual" if len >= 2 => infix(" <= ", COMPARISON, NonAssociative),
+        "GreaterEqual" if len >= 2 => infix(" >= ", COMPARISON, NonAssociative),
+        "Not" if len == 1 => Syntax::Prefix {
+            operator: "!",
+            precedence: NOT,
+        },
+        "And" if len >= 2 => infix(" && ", AND, Flat),
+        "Or" if len >= 2 => infix(" || ", OR, Flat),
+        "Rule" if len == 2 => infix(" -> ", RULE, Right),
+        "RuleDelayed" if len == 2 => infix(" :> ", RULE, Right),
+        "Set" if len == 2 => infix(" = ", SET, Right),
+        "SetDelayed" if len == 2 => infix(" := ", SET, Right),
+        "List" => Syntax::Brackets {
+            open: "{",
+            close: "}",
+        },
+        "Association" => Syntax::Brackets {
+            open: "<|",
+            close: "|>",
+        },
+        _ => return None,
+    };
+
+    Some(syntax)
+}
+
+/// The precedence of the syntax used to format `expr`.
+fn precedence(expr: &Expr) -> u16 {
+    match expr.kind() {
+        ExprKind::Normal(normal) => match syntax(normal) {
+            Some(Syntax::Sum) => PLUS,
+            Some(Syntax::Infix { precedence, .. }) => precedence,
+            Some(Syntax::Prefix { precedence, .. }) => precedence,
+            Some(Syntax::Brackets { .. }) | None => ATOM,
+        },
+        // e.g. `2/3`, which is a division.
+        #[cfg(feature = "rational")]
+        ExprKind::Rational(_) => TIMES,
+        _ if is_negative_number(expr) => NEGATIVE,
+        _ => ATOM,
+    }
+}
+
+fn is_negative_number(expr: &Expr) -> bool {
+    match expr.kind() {
+        ExprKind::Integer(int) => *int < 0,
+        #[cfg(feature = "bigint")]
+        ExprKind::BigInteger(int) => int.sign() == num_bigint::Sign::Minus,
+        #[cfg(feature = "rational")]
+        ExprKind::Rational(rational) => {
+            rational.numer().sign() == num_bigint::Sign::Minus
+        },
+        ExprKind::Real(real) => real.is_sign_negative(),
+        #[cfg(feature = "bigreal")]
+        ExprKind::BigReal(real) => real.to_f64().is_sign_negative(),
+        _ => false,
+    }
+}
+
+/// If `expr` is `Times[-1, factors...]`, return `factors`.
+///
+/// Returns `None` if the first factor starts with a `-`, to avoid formatting e.g.
+/// `Times[-1, -2]` as `--2`.
+fn negated_factors(expr: &Expr) -> Option<&[Expr]> {
+    let normal = expr.try_as_normal()?;
+
+    if normal.head().try_as_symbol().map(Symbol::as_str) != Some("System`Times") {
+        return None;
+    }
+
+    match normal.elements() {
+        [first, factors @ ..]
+            if *first.kind() == ExprKind::Integer(-1)
+                && !factors.is_empty()
+                && !starts_with_minus(&factors[0]) =>
+        {
+            Some(factors)
+        },
+        _ => None,
+    }
+}
+
+/// Returns `true` if the formatted form of `expr` starts with a `-`.
+fn starts_with_minus(expr: &Expr) -> bool {
+    is_negative_number(expr) || negated_factors(expr).is_some()
+}
+
+impl fmt::Display for InputForm<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let InputForm(expr) = *self;
+
+        let normal = match expr.kind() {
+            ExprKind::Normal(normal) => normal,
+            ExprKind::Symbol(symbol) => return fmt_symbol(f, symbol),
+            #[cfg(feature = "rational")]
+            ExprKind::Rational(rational) => {
+                return write!(f, "{}/{}", rational.numer(), rational.denom())
+            },
+            _ => return write!(f, "{}", expr.full_form()),
+        };
+
+        if let Some(factors) = negated_factors(expr) {
+            write!(f, "-")?;
+            return fmt_operands(f, factors, " ", TIMES, Associativity::Flat);
+        }
+
+        match syntax(normal) {
+            Some(Syntax::Sum) => fmt_sum(f, normal.elements()),
+            Some(Syntax::Infix {
+                separator,
+                precedence,
+                associativity,
+            }) => {
+                fmt_operands(f, normal.elements(), separator, precedence, associativity)
+            },
+            Some(Syntax::Prefix {
+                operator,
+                precedence,
+            }) => {
+                write!(f, "{}", operator)?;
+                fmt_operand(f, &normal.elements()[0], precedence)
+            },
+            Some(Syntax::Brackets { open, close }) => {
+                write!(f, "{}", open)?;
+                fmt_sequence(f, normal.elements())?;
+                write!(f, "{}", close)
+            },
+            None => {
+                fmt_operand(f, normal.head(), ATOM)?;
+                write!(f, "[")?;
+                fmt_sequence(f, normal.elements())?;
+                write!(f, "]")
+            },
+        }
+    }
+}
+
+/// Format `symbol`, omitting the `` System` `` and `` Global` `` contexts.
+fn fmt_symbol(f: &mut fmt::Formatter, symbol: &Symbol) -> fmt::Result {
+    match symbol.context().as_str() {
+        "System`" | "Global`" => write!(f, "{}", symbol.symbol_name()),
+        _ => write!(f, "{}", symbol),
+    }
+}
+
+/// Format `elements` separated by commas.
+fn fmt_sequence(f: &mut fmt::Formatter, elements: &[Expr]) -> fmt::Result {
+    for (idx, elem) in elements.iter().enumerate() {
+        if idx != 0 {
+            write!(f, ", ")?;
+        }
+        write!(f, "{}", elem.input_form())?;
+    }
+    Ok(())
+}
+
+/// Format `operands` of an infix operator.
+fn fmt_operands(
+    f: &mut fmt::Formatter,
+    operands: &[Expr],
+    separator: &str,
+    precedence: u16,
+    associativity: Associativity,
+) -> fmt::Result {
+    for (idx, operand) in operands.iter().enumerate() {
+        if idx != 0 {
+            write!(f, "{}", separator)?;
+        }
+
+        let is_last = idx == operands.len() - 1;
+
+        // Operands that group the wrong way for this operator must be parenthesized.
+        let min_precedence = match associativity {
+            Associativity::Flat => precedence,
+            Associativity::Right if is_last => precedence,
+            Associativity::Right | Associativity::None => precedence + 1,
+        };
+
+        // e.g. `2 (-1)`, which would otherwise be formatted as `2 -1`.
+        if idx != 0 && starts_with_minus(operand) {
+            write!(f, "({})", operand.input_form())?;
+        } else {
+            fmt_operand(f, operand, min_precedence)?;
+        }
+    }
+    Ok(())
+}
+
+/// Format the terms of a `Plus`, using `-` for negative terms.
+fn fmt_sum(f: &mut fmt::Formatter, terms: &[Expr]) -> fmt::Result {
+    let (first, rest) = terms.split_first().expect("empty Plus");
+
+    fmt_operand(f, first, PLUS)?;
+
+    for term in rest {
+        if let Some(factors) = negated_factors(term) {
+            write!(f, " - ")?;
+            fmt_operands(f, factors, " ", TIMES, Associativity::Flat)?;
+            continue;
+        }
+
+        match term.kind() {
+            ExprKind::Integer(int) if *int < 0 && int.checked_neg().is_some() => {
+                write!(f, " - {}", -int)?
+            },
+            ExprKind::Real(real) if real.is_sign_negative() => {
+                write!(f, " - ")?;
+                fmt_real(f, -**real)?
+            },
+            #[cfg(feature = "rational")]
+            ExprKind::Rational(rational) if is_negative_number(term) => {
+                write!(f, " - {}/{}", -rational.numer(), rational.denom())?
+            },
+            _ => {
+                write!(f, " + ")?;
+                fmt_operand(f, term, PLUS)?
+            },
+        }
+    }
+
+    Ok(())
+}
+
+/// Format `operand`, parenthesizing it if its precedence is less than
+/// `min_precedence`.
+fn fmt_operand(
+    f: &mut fmt::Formatter,
+    operand: &Expr,
+    min_precedence: u16,
+) -> fmt::Result {
+    if precedence(operand) < min_precedence {
+        write!(f, "({})", operand.input_form())
+    } else {
+        write!(f, "{}", operand.input_form())
+    }
+}
+
+//======================================
+// Pretty printing
+//======================================
+
+/// Maximum width of a line, including indentation, on which
+/// [`Expr::to_string_pretty()`] formats an expression without splitting it.
+const PRETTY_WIDTH: usize = 80;
+
+impl Expr {
+    /// Format this expression like its [`Display`](fmt::Display) form, but split across
+    /// multiple lines, indenting each level of nesting by `indent` spaces.
+    ///
+    /// An expression is formatted on a single line if that line, including its
+    /// indentation, is at most 80 characters wide. Otherwise, a normal expression is
+    /// formatted with each element on a line of its own, indented one level deeper than
+    /// its head, and the closing `]` on a separate line. Atoms are never split.
+    ///
+    /// Whitespace between elements is insignificant, so like the `Display` form, the
+    /// result can be parsed to reconstruct the expression.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use wolfram_expr::expr;
+    ///
+    /// let expr = expr!(f[g["a long string argument", "another long string argument"], 100]);
+    ///
+    /// assert_eq!(expr.to_string_pretty(2), r#"Global`f[
+    ///   Global`g["a long string argument", "another long string argument"],
+    ///   100
+    /// ]"#);
+    ///
+    /// assert_eq!(expr!(f[1, 2]).to_string_pretty(2), "Global`f[1, 2]");
+    /// ```
+    ///
+    /// The alternate `Display` form of an expression, `{:#}`, is the pretty-printed form
+    /// with an indent of 4 spaces.
+    pub fn to_string_pretty(&self, indent: usize) -> String {
+        pretty_string(self, indent, None)
+    }
+}
+
+/// Indent used by the alternate `Display` form of [`Expr`].
+pub(crate) const PRETTY_INDENT: usize = 4;
+
+/// The pretty-printed form of `expr`, showing at most `max_elements` elements of each
+/// normal expression.
+pub(crate) fn pretty_string(
+    expr: &Expr,
+    indent: usize,
+    max_elements: Option<usize>,
+) -> String {
+    let mut output = String::new();
+    write_pretty(&mut output, expr, indent, 0, max_elements);
+    output
+}
+
+/// The single-line `Display` form of an expression, showing at most `max_elements`
+/// elements of each normal expression.
+struct SingleLine<'e> {
+    expr: &'e Expr,
+    max_elements: Option<usize>,
+}
+
+/// The bracketed elements of a normal expression, as in its single-line `Display` form,
+/// showing at most `max_elements` elements of each normal expression.
+struct Elements<'e> {
+    normal: &'e Normal,
+    max_elements: Option<usize>,
+}
+
+/// Counts the characters written to it, failing once there are more than `limit`.
+struct WidthCounter {
+    width: usize,
+    limit: usize,
+}
+
+/// The number of characters in the `Display` form of `value`, or `None` if there are
+/// more than `limit`.
+///
+/// Formatting stops as soon as `limit` is exceeded, so the cost is bounded by `limit`
+/// rather than by the size of `value`.
+fn width_within<T: fmt::Display>(value: &T, limit: usize) -> Option<usize> {
+    let mut counter = WidthCounter { width: 0, limit };
+
+    match write!(counter, "{}", value) {
+        Ok(()) => Some(counter.width),
+        Err(_) => None,
+    }
+}
+
+/// Append the pretty-printed form of `expr` to `output`, where the current line is
+/// indented by `level` levels of `indent` spaces.
+///
+/// Each call measures the single-line form of `expr` only up to the remaining width of
+/// the line, so pretty-printing takes time linear in the size of the output.
+fn write_pretty(
+    output: &mut String,
+    expr: &Expr,
+    indent: usize,
+    level: usize,
+    max_elements: Option<usize>,
+) {
+    let single_line = SingleLine { expr, max_elements };
+    let limit = PRETTY_WIDTH.saturating_sub(indent * level);
+
+    let normal = match expr.kind() {
+        ExprKind::Normal(normal) if width_within(&single_line, limit).is_none() => normal,
+        _ => return output.push_str(&single_line.to_string()),
+    };
+
+    write_pretty(output, normal.head(), indent, level, max_elements);
+
+    // If the head was split across lines, the elements may still fit after its closing
+    // `]`, e.g. `f[\n  ...\n][x]`.
+    let elements = Elements {
+        normal,
+        max_elements,
+    };
+    let column = output.rsplit('\n').next().unwrap_or("").chars().count();
+    let limit = PRETTY_WIDTH.saturating_sub(column);
+
+    if normal.elements().is_empty() || width_within(&elements, limit).is_some() {
+        return output.push_str(&elements.to_string());
+    }
+
+    let shown = max_elements.map_or(normal.elements().len(), |max| {
+        usize::min(max, normal.elements().len())
+    });
+    let hidden = normal.elements().len() - shown;
+
+    output.push('[');
+    for (idx, elem) in normal.elements()[..shown].iter().enumerate() {
+        if idx != 0 {
+            output.push(',');
+        }
+        push_line(output, indent * (level + 1));
+        write_pretty(output, elem, indent, level + 1, max_elements);
+    }
+    if hidden != 0 {
+        if shown != 0 {
+            output.push(',');
+        }
+        push_line(output, indent * (level + 1));
+        output.push_str(&format!("<{} more>", hidden));
+    }
+    push_line(output, indent * level);
+    output.push(']');
+}
+
+/// Start a new line in `output`, indented by `width` spaces.
+fn push_line(output: &mut String, width: usize) {
+    output.push('\n');
+    for _ in 0..width {
+        output.push(' ');
+    }
+}
+
+/// Format `expr` like its `Display` form, showing at most `max_elements` elements of
+/// each normal expression, followed by a `<N more>` marker if any are omitted.
+pub(crate) fn fmt_truncated(
+    f: &mut fmt::Formatter,
+    expr: &Expr,
+    max_elements: usize,
+) -> fmt::Result {
+    let ExprKind::Normal(normal) = expr.kind() else {
+        return write!(f, "{}", expr);
+    };
+
+    fmt_truncated(f, normal.head(), max_elements)?;
+    fmt::Display::fmt(
+        &Elements {
+            normal,
+            max_elements: Some(max_elements),
+        },
+        f,
+    )
+}
+
+impl fmt::Display for SingleLine<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.max_elements {
+            Some(max_elements) => fmt_truncated(f, self.expr, max_elements),
+            None => fmt::Display::fmt(self.expr, f),
+        }
+    }
+}
+
+impl fmt::Display for Elements<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let elements = self.normal.elements();
+        let shown = self
+            .max_elements
+            .map_or(elements.len(), |max| usize::min(max, elements.len()));
+
+        write!(f, "[")?;
+        for (idx, elem) in elements[..shown].iter().enumerate() {
+            if idx != 0 {
+                write!(f, ", ")?;
+            }
+            fmt::Display::fmt(
+                &SingleLine {
+                    expr: elem,
+                    max_elements: self.max_elements,
+                },
+                f,
+            )?;
+        }
+        if shown < elements.len() {
+            if shown != 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "<{} more>", elements.len() - shown)?;
+        }
+        write!(f, "]")
+    }
+}
+
+impl fmt::Write for WidthCounter {
+    fn write_str(&mut self, string: &str) -> fmt::Result {
+        for _ in string.chars() {
+            self.width += 1;
+
+            if self.width > self.limit {
+                return Err(fmt::Error);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Format a machine real with a decimal point and an optional `*^` exponent.
+fn fmt_real(f: &mut fmt::Formatter, real: f64) -> fmt::Result {
+    if real.is_infinite() {
+        let sign = if real > 0.0 { 1 } else { -1 };
+        return write!(f, "System`DirectedInfinity[{}]", sign);
+    }
+
+    fmt_finite_real(f, real, true)
+}
+
+/// Format a finite machine real with a decimal point and an optional `*^` exponent,
+/// e.g. `2.5` or `1.*^-15`.
+///
+/// If `trim_zero` is true, a zero fractional part is omitted, e.g. `1.` instead of
+/// `1.0`.
+pub(crate) fn fmt_finite_real(
+    f: &mut fmt::Formatter,
+    real: f64,
+    trim_zero: bool,
+) -> fmt::Result {
+    // The `Debug` form of an `f64` is the shortest string that parses back to the same
+    // value, e.g. `1.0`, `2.5`, or `1e-15`.
+    let debug = format!("{:?}", real);
+
+    let (mantissa, exponent) = match debug.split_once('e') {
+        Some((mantissa, exponent)) => (mantissa, Some(exponent)),
+        None => (debug.as_str(), None),
+    };
+
+    let mantissa = match mantissa.strip_suffix(".0") {
+        Some(integral) if trim_zero => integral,
+        _ => mantissa,
+    };
+
+    write!(f, "{}", mantissa)?;
+    if !mantissa.contains('.') {
+        write!(f, ".")?;
+    }
+
+    if let Some(exponent) = exponent {
+        write!(f, "*^{}", exponent)?;
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_full_form() {
+    use alloc::string::ToString;
+
+    use crate::expr;
+
+    let full_form = |expr: Expr| expr.full_form().to_string();
+
+    assert_eq!(full_form(expr!(5)), "5");
+    assert_eq!(full_form(expr!(-5)), "-5");
+    assert_eq!(full_form(expr!(1.0)), "1.");
+    assert_eq!(full_form(expr!(-2.5)), "-2.5");
+    assert_eq!(full_form(expr!(-0.0)), "-0.");
+    assert_eq!(full_form(expr!(1e-15)), "1.*^-15");
+    assert_eq!(full_form(expr!(2.5e30)), "2.5*^30");
+    assert_eq!(full_form(expr!(123456.0)), "123456.");
+    assert_eq!(
+        full_form(Expr::real(f64::NEG_INFINITY)),
+        "System`DirectedInfinity[-1]"
+    );
+    assert_eq!(full_form(expr!("a\"b\n")), r#""a\"b\n""#);
+    assert_eq!(full_form(expr!(x)), "Global`x");
+    assert_eq!(
+        full_form(expr!(f[1.0, g[x]][System::List[]])),
+        "Global`f[1., Global`g[Global`x]][System`List[]]"
+    );
+}
+
+#[cfg(feature = "unstable_parse")]
+#[test]
+fn test_full_form_round_trip() {
+    use alloc::string::ToString;
+
+    use crate::{expr, parse::parse_expr};
+
+    let expr = expr!(f[1, 1.0, -0.5, 1e-15, 2.5e30, "a\"\t", System::List[x, g[]][y]]);
+
+    assert_eq!(parse_expr(&expr.full_form().to_string()), Ok(expr));
+}
+
+#[test]
+fn test_input_form() {
+    use alloc::string::ToString;
+
+    use crate::expr;
+
+    let input_form = |expr: Expr| expr.input_form().to_string();
+
+    assert_eq!(input_form(expr!(x)), "x");
+    assert_eq!(input_form(expr!(System::Pi)), "Pi");
+    assert_eq!(input_form(expr!(MyPackage::x)), "MyPackage`x");
+    assert_eq!(input_form(expr!(1.0)), "1.");
+    assert_eq!(input_form(expr!("a\"b")), r#""a\"b""#);
+
+    // Precedence.
+    assert_eq!(
+        input_form(expr!(System::Times[System::Plus[a, b], c])),
+        "(a + b) c"
+    );
+    assert_eq!(
+        input_form(expr!(System::Plus[a, System::Times[b, c]])),
+        "a + b c"
+    );
+    assert_eq!(
+        input_form(expr!(System::Power[System::Plus[a, 1], System::Times[2, b]])),
+        "(a + 1)^(2 b)"
+    );
+    assert_eq!(
+        input_form(expr!(System::Not[System::And[a, System::Or[b, c]]])),
+        "!(a && (b || c))"
+    );
+    assert_eq!(
+        input_form(expr!(System::Rule[x, System::Equal[System::Plus[a, b], 0]])),
+        "x -> a + b == 0"
+    );
+
+    // Associativity.
+    assert_eq!(
+        input_form(expr!(System::Power[a, System::Power[b, c]])),
+        "a^b^c"
+    );
+    assert_eq!(
+        input_form(expr!(System::Power[System::Power[a, b], c])),
+        "(a^b)^c"
+    );
+    assert_eq!(
+        input_form(expr!(System::Rule[System::Rule[a, b], c])),
+        "(a -> b) -> c"
+    );
+    assert_eq!(
+        input_form(expr!(System::Plus[System::Plus[a, b], c])),
+        "a + b + c"
+    );
+    assert_eq!(
+        input_form(expr!(System::Equal[System::Less[a, b], c])),
+        "(a < b) == c"
+    );
+
+    // Negative numbers and terms.
+    assert_eq!(
+        input_form(expr!(System::Plus[a, -2, System::Times[-1, b], -1.5])),
+        "a - 2 - b - 1.5"
+    );
+    assert_eq!(input_form(expr!(System::Plus[-1, a])), "-1 + a");
+    assert_eq!(input_form(expr!(System::Times[-1, a, b])), "-a b");
+    assert_eq!(input_form(expr!(System::Times[2, -1])), "2 (-1)");
+    assert_eq!(input_form(expr!(System::Times[-1, -2])), "-1 (-2)");
+    assert_eq!(input_form(expr!(System::Power[-2, x])), "(-2)^x");
+    assert_eq!(
+        input_form(expr!(System::Power[System::Times[-1, x], 2])),
+        "(-x)^2"
+    );
+
+    // Brackets and function application.
+    assert_eq!(
+        input_form(expr!(System::List[1, System::Plus[x, 1], System::List[]])),
+        "{1, x + 1, {}}"
+    );
+    assert_eq!(
+        input_form(expr!(System::Association[System::Rule["a", 1]])),
+        r#"<|"a" -> 1|>"#
+    );
+    assert_eq!(
+        input_form(expr!(f[System::Plus[a, b], g[x][y]])),
+        "f[a + b, g[x][y]]"
+    );
+    assert_eq!(input_form(expr!(System::Plus[a, b][x])), "(a + b)[x]");
+
+    // Unsupported numbers of elements use `head[...]` syntax.
+    assert_eq!(input_form(expr!(System::Plus[])), "Plus[]");
+    assert_eq!(input_form(expr!(System::Times[a])), "Times[a]");
+    assert_eq!(input_form(expr!(System::Power[a, b, c])), "Power[a, b, c]");
+}
+
+#[cfg(feature = "rational")]
+#[test]
+fn test_rational_forms() {
+    use alloc::string::ToString;
+
+    use crate::expr;
+
+    let two_thirds = Expr::rational(2, 3);
+    let minus_two_thirds = Expr::rational(-2, 3);
+
+    assert_eq!(two_thirds.full_form().to_string(), "System`Rational[2, 3]");
+    assert_eq!(
+        expr!(f[#minus_two_thirds]).full_form().to_string(),
+        "Global`f[System`Rational[-2, 3]]"
+    );
+
+    let input_form = |expr: Expr| expr.input_form().to_string();
+
+    assert_eq!(input_form(two_thirds.clone()), "2/3");
+    assert_eq!(input_form(minus_two_thirds.clone()), "-2/3");
+    assert_eq!(input_form(expr!(System::Power[x, #two_thirds])), "x^(2/3)");
+    assert_eq!(input_form(expr!(System::Times[#two_thirds, x])), "2/3 x");
+    assert_eq!(
+        input_form(expr!(System::Times[x, #minus_two_thirds])),
+        "x (-2/3)"
+    );
+    assert_eq!(
+        input_form(expr!(System::Plus[x, #minus_two_thirds])),
+        "x - 2/3"
+    );
+    assert_eq!(input_form(expr!(System::Plus[x, #two_thirds])), "x + 2/3");
+}
+
+#[test]
+fn test_to_string_pretty() {
+    use crate::expr;
+
+    let expr = expr!(System::Association[
+        System::Rule["name", "wolfram-expr"],
+        System::Rule[
+            "dependencies",
+            System::List["ordered-float", "num-bigint", "num-rational", "dashu-float"]
+        ],
+        System::Rule[
+            "tree",
+            f[
+                g[h[1, 2, 3], h[4, 5, 6]],
+                g[h["a long string argument"], h["another long string argument"]][x]
+            ]
+        ],
+        System::Rule["empty", System::List[]]
+    ]);
+
+    let pretty = expr.to_string_pretty(2);
+
+    assert_eq!(
+        pretty,
+        r#"System`Association[
+  System`Rule["name", "wolfram-expr"],
+  System`Rule[
+    "dependencies",
+    System`List["ordered-float", "num-bigint", "num-rational", "dashu-float"]
+  ],
+  System`Rule[
+    "tree",
+    Global`f[
+      Global`g[Global`h[1, 2, 3], Global`h[4, 5, 6]],
+      Global`g[
+        Global`h["a long string argument"],
+        Global`h["another long string argument"]
+      ][Global`x]
+    ]
+  ],
+  System`Rule["empty", System`List[]]
+]"#
+    );
+
+    // Deeper indentation splits more levels.
+    assert!(expr
+        .to_string_pretty(4)
+        .contains("\n            \"ordered-float\",\n"));
+
+    // Short expressions and atoms are never split.
+    assert_eq!(expr!(f[1, "a"]).to_string_pretty(2), "Global`f[1, \"a\"]");
+    let long_string = Expr::string("x".repeat(100));
+    assert_eq!(long_string.to_string_pretty(2), long_string.to_string());
+    let long_head =
+        Expr::normal(Symbol::new(&format!("Global`{}", "f".repeat(100))), vec![]);
+    assert_eq!(long_head.to_string_pretty(2), long_head.to_string());
+
+    #[cfg(feature = "unstable_parse")]
+    assert_eq!(crate::parse::parse_expr(&pretty), Ok(expr));
+}
+
+#[test]
+fn test_display_flags() {
+    use crate::expr;
+
+    let big_list = Expr::list((1..=10).map(Expr::from).collect());
+
+    assert_eq!(format!("{:.3}", big_list), "System`List[1, 2, 3, <7 more>]");
+    assert_eq!(format!("{:.10}", big_list), big_list.to_string());
+    assert_eq!(format!("{:.0}", big_list), "System`List[<10 more>]");
+    assert_eq!(format!("{:.3}", expr!(f[])), "Global`f[]");
+    assert_eq!(format!("{:.3}", expr!("abcdef")), "\"abcdef\"");
+
+    // The precision applies to every level, including heads.
+    assert_eq!(
+        format!("{:.1}", expr!(f[a, b][g[1, 2, 3], h[]])),
+        "Global`f[Global`a, <1 more>][Global`g[1, <2 more>], <1 more>]"
+    );
+
+    // The alternate form is the pretty-printed form.
+    let expr = expr!(f[g["a long string argument", "another long string argument"], 100]);
+    assert_eq!(format!("{:#}", expr), expr.to_string_pretty(4));
+    assert_eq!(
+        format!("{:#}", expr),
+        r#"Global`f[
+    Global`g["a long string argument", "another long string argument"],
+    100
+]"#
+    );
+    assert_eq!(format!("{:#}", expr!(f[1])), "Global`f[1]");
+
+    // Both flags can be combined.
+    let nested = Expr::list((0..30).map(|_| big_list.clone()).collect());
+    assert_eq!(
+        format!("{:#.2}", nested),
+        "System`List[System`List[1, 2, <8 more>], System`List[1, 2, <8 more>], <28 more>]"
+    );
+    let nested = Expr::list((0..30).map(|_| Expr::string("x".repeat(40))).collect());
+    assert_eq!(
+        format!("{:#.2}", nested),
+        format!(
+            "System`List[\n    \"{x}\",\n    \"{x}\",\n    <28 more>\n]",
+            x = "x".repeat(40)
+        )
+    );
+}
@@ -1,31 +1,126 @@
 //! Efficient and ergonomic representation of Wolfram expressions in Rust.
+//!
+//! # Crate features
+//!
+//! * `std` *(enabled by default)* — depend on the Rust standard library. When this
+//!   feature is disabled, this crate is `#![no_std]` and requires only the [`alloc`]
+//!   crate. The following APIs require this feature:
+//!   - the [`pattern`] and [`wxf`] modules,
+//!   - [`SymbolTable`](symbol::SymbolTable), [`Expr::symbol_cached()`], and
+//!     [`Expr::clear_symbol_cache()`],
+//!   - [`Expr::tally()`] and [`Expr::replace_symbols()`],
+//!   - the [`std::error::Error`] implementations of this crate's error types.
+//!
+//!   Everything else, including [`Expr::to_bytes()`], [`Display`](core::fmt::Display)
+//!   formatting, and the `parse` module, is available without `std`.
+//! * `unstable_parse` — export unstable parsing functionality, including the `parse`
+//!   module and the [`FromStr`](core::str::FromStr) implementation of [`Expr`].
+//! * `bigreal` — add the `BigReal` arbitrary-precision real number type, and the
+//!   `ExprKind::BigReal` and `Number::BigReal` variants. Enabling this feature removes
+//!   the [`Copy`] implementation of [`Number`].
+//! * `bigint` — add the `ExprKind::BigInteger` and `Number::BigInteger` variants for
+//!   integers outside the range of `i64`, backed by
+//!   [`num-bigint`](https://docs.rs/num-bigint). Enabling this feature removes the
+//!   [`Copy`] implementation of [`Number`].
+//! * `rational` — add the `ExprKind::Rational` and `Number::Rational` variants for exact
+//!   rational numbers, backed by [`num-rational`](https://docs.rs/num-rational).
+//!   Enables the `bigint` feature.
+//! * `complex` — add [`Expr::complex()`] and [`Expr::try_complex()`], for constructing
+//!   and recognizing `` System`Complex[re, im] `` expressions.
+//! * `serde` — implement [`serde`](https://serde.rs) serialization. See the `serde`
+//!   module.
+//! * `smallvec` — store the elements of small normal expressions inline. See
+//!   [`Normal`] for the trade-offs.
 
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
 #![allow(clippy::let_and_return)]
 #![warn(missing_docs)]
 
+extern crate alloc;
+
+mod arith;
+#[cfg(feature = "bigint")]
+mod big_integer;
+#[cfg(feature = "bigreal")]
+mod big_real;
+mod builder;
+mod bytes;
+#[cfg(feature = "std")]
+mod cache;
+#[cfg(feature = "complex")]
+mod complex;
 mod conversion;
+mod diff;
+mod fingerprint;
+mod format;
+#[doc(hidden)]
+pub mod macros;
+mod numeric;
+mod order;
+#[cfg(feature = "unstable_parse")]
 mod ptr_cmp;
+mod query;
+#[cfg(feature = "rational")]
+mod rational;
+mod replace;
+mod structure;
+mod traversal;
 
+#[cfg(feature = "unstable_parse")]
+pub mod parse;
+#[cfg(feature = "std")]
+pub mod pattern;
+#[cfg(feature = "serde")]
+pub mod serde;
 pub mod symbol;
+pub mod visit;
+#[cfg(feature = "std")]
+pub mod wxf;
 
 #[cfg(test)]
 mod tests;
 
+// Ensure that doc tests in the README.md file get run.
+//
+// The README matches exhaustively over `ExprKind`, which doesn't cover the variants
+// added by optional features.
+#[cfg(not(any(feature = "bigreal", feature = "bigint")))]
 #[doc(hidden)]
-mod test_readme {
-    // Ensure that doc tests in the README.md file get run.
-    #![doc = include_str ! ("../README.md")]
-}
+#[doc = include_str!("../README.md")]
+mod test_readme {}
 
 
-use std::fmt;
-use std::mem;
-use std::sync::Arc;
+use alloc::{string::String, sync::Arc, vec, vec::Vec};
+use core::{
+    convert::TryFrom,
+    fmt,
+    hash::{Hash, Hasher},
+    mem::{self, ManuallyDrop},
+    ops::Index,
+    ptr,
+};
 
 
 #[doc(inline)]
 pub use self::symbol::Symbol;
 
+#[cfg(feature = "bigreal")]
+pub use self::big_real::BigReal;
+#[cfg(feature = "bigint")]
+pub use num_bigint::BigInt;
+#[cfg(feature = "rational")]
+pub use num_rational::BigRational;
+
+pub use self::{
+    builder::NormalBuilder,
+    bytes::DecodeError,
+    conversion::TryFromExprError,
+    diff::{Difference, DifferenceKind},
+    format::{FullForm, InputForm},
+    order::wolfram_order,
+    traversal::{LevelSpec, Transform},
+};
+
 #[cfg(feature = "unstable_parse")]
 pub use self::ptr_cmp::ExprRefCmp;
 
@@ -49,7 +144,11 @@ pub use self::ptr_cmp::ExprRefCmp;
 ///
 /// Internally, `Expr` is an atomically reference-counted [`ExprKind`]. This makes cloning
 /// an expression computationally inexpensive.
-#[derive(Clone, PartialEq, Eq, Hash)]
+///
+/// Comparing two `Expr` instances that share the same allocation (for example, an
+/// expression and its clone) is an *O(1)* pointer comparison. Otherwise, the expressions
+/// are compared structurally.
+#[derive(Clone, Eq)]
 pub struct Expr {
     inner: Arc<ExprKind>,
 }
@@ -86,7 +185,13 @@ impl Expr {
     // must be done.
     #[allow(clippy::wrong_self_convention)]
     pub fn to_kind(self) -> ExprKind {
-        match Arc::try_unwrap(self.inner) {
+        // `Expr` implements `Drop`, so `inner` cannot be moved out of `self` directly.
+        let this = ManuallyDrop::new(self);
+        // SAFETY: `this` is never used or dropped after this read, so ownership of the
+        //         `Arc` is transferred to `inner` exactly once.
+        let inner = unsafe { ptr::read(&this.inner) };
+
+        match Arc::try_unwrap(inner) {
             Ok(kind) => kind,
             Err(self_) => (*self_).clone(),
         }
@@ -94,7 +199,7 @@ impl Expr {
 
     /// Get the [`ExprKind`] representing this expression.
     pub fn kind(&self) -> &ExprKind {
-        &*self.inner
+        &self.inner
     }
 
     /// Get mutable access to the [`ExprKind`] that represents this expression.
@@ -112,14 +217,13 @@ impl Expr {
 
     /// Construct a new normal expression from the head and elements.
     pub fn normal<H: Into<Expr>>(head: H, contents: Vec<Expr>) -> Self {
-        let head = head.into();
-        // let contents = contents.into();
-        ExprKind::Normal(Normal { head, contents }).into()
+        ExprKind::Normal(Normal::new(head, contents)).into()
     }
 
-    // TODO: Should Expr's be cached? Especially Symbol exprs? Would certainly save
-    //       a lot of allocations.
     /// Construct a new expression from a [`Symbol`].
+    ///
+    /// See also [`Expr::symbol_cached()`], which reuses a single allocation for
+    /// repeated symbols.
     pub fn symbol<S: Into<Symbol>>(s: S) -> Self {
         ExprKind::Symbol(s.into()).into()
     }
@@ -143,11 +247,24 @@ impl Expr {
     ///
     /// # Panics
     ///
-    /// This function will panic if `real` is NaN.
+    /// This function will panic if `real` is NaN. Use [`Expr::try_real()`] to handle NaN
+    /// values without panicking.
     pub fn real(real: f64) -> Self {
         Self::number(Number::real(real))
     }
 
+    /// Construct an expression from a floating-point number, or return an error if
+    /// `real` is NaN.
+    ///
+    /// ```
+    /// # use wolfram_expr::{Expr, NanError};
+    /// assert_eq!(Expr::try_real(2.5), Ok(Expr::real(2.5)));
+    /// assert_eq!(Expr::try_real(f64::NAN), Err(NanError));
+    /// ```
+    pub fn try_real(real: f64) -> Result<Self, NanError> {
+        Number::try_real(real).map(Self::number)
+    }
+
     /// Returns the outer-most symbol "tag" used in this expression.
     ///
     /// To illustrate:
@@ -167,6 +284,12 @@ impl Expr {
     pub fn tag(&self) -> Option<Symbol> {
         match *self.inner {
             ExprKind::Integer(_) | ExprKind::Real(_) | ExprKind::String(_) => None,
+            #[cfg(feature = "bigint")]
+            ExprKind::BigInteger(_) => None,
+            #[cfg(feature = "rational")]
+            ExprKind::Rational(_) => None,
+            #[cfg(feature = "bigreal")]
+            ExprKind::BigReal(_) => None,
             ExprKind::Normal(ref normal) => normal.head.tag(),
             ExprKind::Symbol(ref sym) => Some(sym.clone()),
         }
@@ -196,6 +319,86 @@ impl Expr {
         normal.contents.get(index_0)
     }
 
+    /// Get the part of this expression at `path`.
+    ///
+    /// This is equivalent to
+    /// [`Part`](https://reference.wolfram.com/language/ref/Part.html)<sub>WL</sub>
+    /// (`expr[[i, j, ...]]`): each index in `path` selects a part of the result of the
+    /// previous index. Unlike [`Expr::normal_part()`], indices are 1-based:
+    ///
+    /// * `1` through `n` select the elements of a normal expression with `n` elements.
+    /// * `-1` through `-n` select the elements counting from the end, so `-1` is the
+    ///   last element.
+    /// * `0` selects the head.
+    ///
+    /// An empty `path` selects `self`. Returns `None` if any index is out of range, or
+    /// selects into an atom.
+    ///
+    /// ```
+    /// use wolfram_expr::expr;
+    ///
+    /// let expr = expr!(f[g[1, 2, 3], x]);
+    ///
+    /// assert_eq!(expr.part(&[1, -1]), Some(&expr!(3)));
+    /// assert_eq!(expr.part(&[1, 0]), Some(&expr!(g)));
+    /// assert_eq!(expr.part(&[2, 1]), None);
+    /// ```
+    pub fn part(&self, path: &[i64]) -> Option<&Expr> {
+        path.iter().try_fold(self, |expr, &index| {
+            let normal = expr.try_as_normal()?;
+            let len = normal.elements().len();
+
+            let index = match index {
+                0 => return Some(normal.head()),
+                1.. => usize::try_from(index - 1).ok()?,
+                _ => len.checked_sub(usize::try_from(index.unsigned_abs()).ok()?)?,
+            };
+
+            normal.elements().get(index)
+        })
+    }
+
+    /// The elements of this expression if it is a [`Normal`] expression, or an empty
+    /// slice if it is an atom.
+    ///
+    /// ```
+    /// use wolfram_expr::expr;
+    ///
+    /// assert_eq!(expr!(f[1, x]).elements(), [expr!(1), expr!(x)]);
+    /// assert!(expr!(5).elements().is_empty());
+    /// ```
+    pub fn elements(&self) -> &[Expr] {
+        match self.kind() {
+            ExprKind::Normal(normal) => normal.elements(),
+            _ => &[],
+        }
+    }
+
+    /// The number of elements of this expression.
+    ///
+    /// This is equivalent to
+    /// [`Length`](https://reference.wolfram.com/language/ref/Length.html)<sub>WL</sub>:
+    /// atoms have length 0, and the head of a normal expression is not counted.
+    ///
+    /// ```
+    /// use wolfram_expr::expr;
+    ///
+    /// assert_eq!(expr!(f[1, g[2, 3]]).len(), 2);
+    /// assert_eq!(expr!(f[]).len(), 0);
+    /// assert_eq!(expr!("abc").len(), 0);
+    /// ```
+    pub fn len(&self) -> usize {
+        self.elements().len()
+    }
+
+    /// Returns `true` if this expression has no elements.
+    ///
+    /// This is `true` for atoms, and for normal expressions with no elements, like
+    /// `f[]`.
+    pub fn is_empty(&self) -> bool {
+        self.elements().is_empty()
+    }
+
     /// Returns `true` if `self` is a `Normal` expr with the head `sym`.
     pub fn has_normal_head(&self, sym: &Symbol) -> bool {
         match *self.kind() {
@@ -204,6 +407,90 @@ impl Expr {
         }
     }
 
+    /// Returns `true` if `self` is a `Normal` expr whose head is the symbol named
+    /// `symbol`.
+    ///
+    /// This is equivalent to [`Expr::has_normal_head()`], but compares against a borrowed
+    /// absolute symbol string, without constructing a [`Symbol`].
+    ///
+    /// ```
+    /// use wolfram_expr::expr;
+    ///
+    /// let list = expr!(System::List[1, 2]);
+    ///
+    /// assert!(list.has_head_str("System`List"));
+    /// assert!(!list.has_head_str("List"));
+    /// ```
+    pub fn has_head_str(&self, symbol: &str) -> bool {
+        match self.try_as_normal().map(|normal| normal.head().kind()) {
+            Some(ExprKind::Symbol(head)) => head.as_str() == symbol,
+            _ => false,
+        }
+    }
+
+    /// Returns `true` if `self` is a `Normal` expr with the head `head`.
+    ///
+    /// This is equivalent to [`Expr::has_normal_head()`], but takes a borrowed
+    /// [`SymbolRef`](crate::symbol::SymbolRef), so checking the head against a string
+    /// literal does not allocate.
+    ///
+    /// ```
+    /// use wolfram_expr::{expr, symbol::SymbolRef};
+    ///
+    /// let list = SymbolRef::try_new("System`List").unwrap();
+    ///
+    /// assert!(expr!(System::List[1, 2]).matches_head(list));
+    /// assert!(!expr!(Global::List[1, 2]).matches_head(list));
+    /// ```
+    pub fn matches_head(&self, head: symbol::SymbolRef) -> bool {
+        self.has_head_str(head.as_str())
+    }
+
+    /// If this is an `Association[...]` expression, look up the value associated with
+    /// `key`.
+    ///
+    /// Returns `None` if this is not an association, or if no `Rule` or `RuleDelayed`
+    /// element of the association has a left-hand side equal to `key`. If several
+    /// elements have the same key, the value of the last one is returned, matching the
+    /// behavior of an evaluated association.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use wolfram_expr::Expr;
+    ///
+    /// let assoc = Expr::association(vec![(Expr::from("a"), Expr::from(1))]);
+    ///
+    /// assert_eq!(assoc.association_lookup(&Expr::from("a")), Some(&Expr::from(1)));
+    /// assert_eq!(assoc.association_lookup(&Expr::from("b")), None);
+    /// ```
+    pub fn association_lookup(&self, key: &Expr) -> Option<&Expr> {
+        let ExprKind::Normal(ref normal) = self.kind() else {
+            return None;
+        };
+
+        if normal.head().try_as_symbol().map(Symbol::as_str) != Some("System`Association")
+        {
+            return None;
+        }
+
+        normal.elements().iter().rev().find_map(|rule| {
+            let ExprKind::Normal(ref rule) = rule.kind() else {
+                return None;
+            };
+
+            let is_rule = matches!(
+                rule.head().try_as_symbol().map(Symbol::as_str),
+                Some("System`Rule" | "System`RuleDelayed")
+            );
+
+            match rule.elements() {
+                [lhs, rhs] if is_rule && lhs == key => Some(rhs),
+                _ => None,
+            }
+        })
+    }
+
     //==================================
     // Common values
     //==================================
@@ -265,6 +552,59 @@ impl Expr {
     pub fn list(elements: Vec<Self>) -> Self {
         Self::normal(Symbol::new("System`List"), elements)
     }
+
+    /// Construct a new `Association[...]`(`<|...|>`) expression from key-value pairs.
+    ///
+    /// Each pair becomes a `Rule[key, value]` element of the association.
+    ///
+    /// # Example
+    ///
+    /// Construct the expression `<|"x" -> 1, "y" -> 2|>`:
+    ///
+    /// ```
+    /// use wolfram_expr::Expr;
+    ///
+    /// let assoc = Expr::association(vec![
+    ///     (Expr::from("x"), Expr::from(1)),
+    ///     (Expr::from("y"), Expr::from(2)),
+    /// ]);
+    /// ```
+    pub fn association(pairs: Vec<(Self, Self)>) -> Self {
+        let rules = pairs
+            .into_iter()
+            .map(|(key, value)| Self::rule(key, value))
+            .collect();
+
+        Self::normal(Symbol::new("System`Association"), rules)
+    }
+
+    /// Construct a new `Hold[_]` expression.
+    ///
+    /// # Example
+    ///
+    /// Construct the expression `Hold[1 + 1]`:
+    ///
+    /// ```
+    /// use wolfram_expr::{Expr, Symbol};
+    ///
+    /// let held = Expr::hold(Expr::normal(
+    ///     Symbol::new("System`Plus"),
+    ///     vec![Expr::from(1), Expr::from(1)],
+    /// ));
+    /// ```
+    pub fn hold(expr: Self) -> Self {
+        Self::normal(Symbol::new("System`Hold"), vec![expr])
+    }
+
+    /// Construct a new `HoldComplete[_]` expression.
+    pub fn hold_complete(expr: Self) -> Self {
+        Self::normal(Symbol::new("System`HoldComplete"), vec![expr])
+    }
+
+    /// Construct a new `HoldForm[_]` expression.
+    pub fn hold_form(expr: Self) -> Self {
+        Self::normal(Symbol::new("System`HoldForm"), vec![expr])
+    }
 }
 
 /// Wolfram Language expression variants.
@@ -272,7 +612,21 @@ impl Expr {
 #[derive(Clone, PartialEq, Eq, Hash)]
 pub enum ExprKind<E = Expr> {
     Integer(i64),
+    /// Arbitrary-precision integer outside the range of `i64`. Requires the `bigint`
+    /// crate feature.
+    #[cfg(feature = "bigint")]
+    BigInteger(BigInt),
+    /// Exact rational number that is not an integer. Requires the `rational` crate
+    /// feature.
+    ///
+    /// Construct rational numbers using [`Expr::rational()`], which reduces them to
+    /// lowest terms.
+    #[cfg(feature = "rational")]
+    Rational(BigRational),
     Real(F64),
+    /// Arbitrary-precision real number. Requires the `bigreal` crate feature.
+    #[cfg(feature = "bigreal")]
+    BigReal(BigReal),
     String(String),
     Symbol(Symbol),
     Normal(Normal<E>),
@@ -282,6 +636,16 @@ pub enum ExprKind<E = Expr> {
 ///
 /// A *normal* expression is any expression that consists of a head and zero or
 /// more arguments.
+///
+/// # Element storage
+///
+/// By default, the elements are stored in a shared allocation, so cloning a `Normal`
+/// does not copy its elements.
+///
+/// If the `smallvec` crate feature is enabled, up to four elements are instead stored
+/// inline, which avoids allocating a separate buffer for the elements of small
+/// expressions like `Plus[x, 1]`. This makes cloning a `Normal` copy its elements, and
+/// increases the size of [`ExprKind`].
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct Normal<E = Expr> {
     /// The head of this normal expression.
@@ -291,19 +655,79 @@ pub struct Normal<E = Expr> {
     ///
     /// If `head` conceptually represents a function, these are the arguments that are
     /// being applied to `head`.
-    contents: Vec<E>,
+    contents: Elements<E>,
 }
 
+/// Storage for the elements of a [`Normal`] expression.
+///
+/// The elements are shared between clones of a normal expression, and are only copied
+/// when they are mutated.
+#[cfg(not(feature = "smallvec"))]
+type Elements<E> = Arc<[E]>;
+
+/// Storage for the elements of a [`Normal`] expression.
+///
+/// Small numbers of elements are stored inline, without a separate allocation.
+#[cfg(feature = "smallvec")]
+type Elements<E> = smallvec::SmallVec<[E; 4]>;
+
 /// Subset of [`ExprKind`] that covers number-type expression values.
+///
+/// `Number` implements [`Copy`] unless the `bigreal` or `bigint` crate feature is
+/// enabled.
+///
+/// # Arithmetic
+///
+/// `Number` implements the [`Add`](core::ops::Add), [`Sub`](core::ops::Sub),
+/// [`Mul`](core::ops::Mul), [`Div`](core::ops::Div), and [`Neg`](core::ops::Neg)
+/// operators, following the Wolfram Language's rules for combining number types:
+///
+/// * If either operand is a real, the result is a machine real. Arbitrary-precision
+///   reals are converted to machine reals first.
+/// * Otherwise, the result is exact. Integer results that overflow `i64` are promoted
+///   to arbitrary-precision integers if the `bigint` crate feature is enabled, and to
+///   reals otherwise.
+/// * Dividing two integers gives an integer if the division is exact. Otherwise, it
+///   gives a rational number if the `rational` crate feature is enabled, and a real
+///   otherwise.
+///
+/// The operators panic if an exact number is divided by an exact zero, or if the
+/// result of real arithmetic is NaN.
+///
+/// ```
+/// use wolfram_expr::Number;
+///
+/// assert_eq!(Number::Integer(2) * Number::Integer(3), Number::Integer(6));
+/// assert_eq!(Number::Integer(1) + Number::real(0.5), Number::real(1.5));
+/// assert_eq!(Number::Integer(6) / Number::Integer(3), Number::Integer(2));
+/// ```
+///
+/// # Ordering
+///
+/// `Number` implements [`Ord`], ordering numbers by value across kinds, with the kind of
+/// number as a tiebreak for numbers with the same value. See the
+/// [`Ord` implementation](#impl-Ord-for-Number) for details.
 #[allow(missing_docs)]
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Hash)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(not(any(feature = "bigreal", feature = "bigint")), derive(Copy))]
 pub enum Number {
     // TODO: Rename this to MachineInteger
     Integer(i64),
+    /// Arbitrary-precision integer outside the range of `i64`. Requires the `bigint`
+    /// crate feature.
+    #[cfg(feature = "bigint")]
+    BigInteger(BigInt),
+    /// Exact rational number that is not an integer. Requires the `rational` crate
+    /// feature.
+    #[cfg(feature = "rational")]
+    Rational(BigRational),
     // TODO: Make an explicit MachineReal type which hides the inner f64, so that other
     //       code can make use of WL machine reals with a guaranteed type. In
     //       particular, change wl_compile::mir::Constant to use that type.
     Real(F64),
+    /// Arbitrary-precision real number. Requires the `bigreal` crate feature.
+    #[cfg(feature = "bigreal")]
+    BigReal(BigReal),
 }
 
 /// 64-bit floating-point real number. Not NaN.
@@ -320,7 +744,7 @@ impl Normal {
     pub fn new<E: Into<Expr>>(head: E, contents: Vec<Expr>) -> Self {
         Self {
             head: head.into(),
-            contents,
+            contents: contents.into(),
         }
     }
 
@@ -337,11 +761,103 @@ impl Normal {
         &self.contents
     }
 
+    /// Mutable access to the elements of this normal expression.
+    ///
+    /// Combined with [`Expr::kind_mut()`], this can be used to modify the elements of
+    /// an expression in place. Both `kind_mut()` and this method copy the data they
+    /// return first if it is shared, so other references to the original expression
+    /// are never modified. Only the outer expression is copied in that case: each
+    /// element is itself a shared [`Expr`], and is copied only if it is modified in
+    /// turn.
+    ///
+    /// ```
+    /// use wolfram_expr::{expr, ExprKind};
+    ///
+    /// let original = expr!(f[1, 2]);
+    ///
+    /// let mut expr = original.clone();
+    /// if let ExprKind::Normal(normal) = expr.kind_mut() {
+    ///     normal.elements_mut()[0] = expr!(x);
+    ///     normal.push(expr!(3));
+    /// }
+    ///
+    /// assert_eq!(expr, expr!(f[x, 2, 3]));
+    /// assert_eq!(original, expr!(f[1, 2]));
+    /// ```
+    pub fn elements_mut(&mut self) -> &mut [Expr] {
+        #[cfg(not(feature = "smallvec"))]
+        return Arc::make_mut(&mut self.contents);
+
+        #[cfg(feature = "smallvec")]
+        return &mut self.contents;
+    }
+
+    /// Append `elem` to the elements of this normal expression.
+    ///
+    /// By default, the elements are stored in a fixed-size shared allocation, so this
+    /// copies the existing elements into a new allocation. To append many elements,
+    /// collect them into a [`Vec`] and construct the expression once instead.
+    pub fn push(&mut self, elem: Expr) {
+        #[cfg(not(feature = "smallvec"))]
+        {
+            let mut contents = Vec::with_capacity(self.contents.len() + 1);
+            contents.extend_from_slice(&self.contents);
+            contents.push(elem);
+
+            self.contents = contents.into();
+        }
+
+        #[cfg(feature = "smallvec")]
+        self.contents.push(elem);
+    }
+
     /// The elements of this normal expression.
     ///
     /// Use [`Normal::elements()`] to get a reference to this value.
     pub fn into_elements(self) -> Vec<Expr> {
-        self.contents
+        #[cfg(not(feature = "smallvec"))]
+        return self.contents.to_vec();
+
+        #[cfg(feature = "smallvec")]
+        return self.contents.into_vec();
+    }
+
+    /// Get the element at `index`, or `None` if `index` is out of bounds.
+    ///
+    /// `index` is 0-based. The 0th index is the first element, not the head.
+    pub fn get(&self, index: usize) -> Option<&Expr> {
+        self.contents.get(index)
+    }
+
+    /// Get a mutable reference to the element at `index`, or `None` if `index` is out
+    /// of bounds.
+    ///
+    /// `index` is 0-based. The 0th index is the first element, not the head.
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut Expr> {
+        self.elements_mut().get_mut(index)
+    }
+
+    /// Replace the element at `index` with `elem`, returning the previous element.
+    ///
+    /// Returns `None`, and does not modify this expression, if `index` is out of
+    /// bounds.
+    ///
+    /// `index` is 0-based. The 0th index is the first element, not the head.
+    ///
+    /// ```
+    /// use wolfram_expr::expr;
+    ///
+    /// let mut normal = expr!(f[1, 2]).try_as_normal().unwrap().clone();
+    ///
+    /// assert_eq!(normal.set(1, expr!(x)), Some(expr!(2)));
+    /// assert_eq!(normal.set(2, expr!(y)), None);
+    ///
+    /// assert_eq!(normal, *expr!(f[1, x]).try_as_normal().unwrap());
+    /// ```
+    pub fn set(&mut self, index: usize, elem: Expr) -> Option<Expr> {
+        let slot = self.get_mut(index)?;
+
+        Some(mem::replace(slot, elem))
     }
 
     /// Returns `true` if the head of this expression is `sym`.
@@ -350,22 +866,161 @@ impl Normal {
     }
 }
 
+/// Get the element at `index`.
+///
+/// `index` is 0-based, following Rust conventions: `normal[0]` is the first element,
+/// not the head. This differs from
+/// [`Part`](https://reference.wolfram.com/language/ref/Part.html)<sub>WL</sub>, where
+/// `expr[[1]]` is the first element and `expr[[0]]` is the head.
+///
+/// # Panics
+///
+/// Panics if `index` is out of bounds. Use [`Normal::get()`] to handle out of bounds
+/// indices without panicking.
+impl Index<usize> for Normal {
+    type Output = Expr;
+
+    fn index(&self, index: usize) -> &Expr {
+        &self.contents[index]
+    }
+}
+
+/// Get the element at `index`.
+///
+/// `index` is 0-based, like [`Normal`]'s [`Index`] implementation. Atoms have no
+/// elements.
+///
+/// # Panics
+///
+/// Panics if `self` is an atom, or `index` is out of bounds. Use
+/// [`Expr::normal_part()`] to handle these cases without panicking.
+///
+/// ```
+/// use wolfram_expr::expr;
+///
+/// let expr = expr!(f[1, g[x]]);
+///
+/// assert_eq!(expr[0], expr!(1));
+/// assert_eq!(expr[1][0], expr!(x));
+/// ```
+impl Index<usize> for Expr {
+    type Output = Expr;
+
+    fn index(&self, index: usize) -> &Expr {
+        &self.elements()[index]
+    }
+}
+
 impl Number {
-    /// # Panics
+    /// Construct a real number.
     ///
-    /// This function will panic if `r` is NaN.
+    /// # Panics
     ///
-    /// TODO: Change this function to take `NotNan` instead, so the caller doesn't have to
-    ///       worry about panics.
+    /// This function will panic if `r` is NaN. Use [`Number::try_real()`] to handle NaN
+    /// values without panicking.
     pub fn real(r: f64) -> Self {
-        let r = match ordered_float::NotNan::new(r) {
+        match Number::try_real(r) {
             Ok(r) => r,
-            Err(_) => panic!("Number::real: got NaN"),
-        };
-        Self::Real(r)
+            Err(NanError) => panic!("Number::real: got NaN"),
+        }
+    }
+
+    /// Construct a real number, or return an error if `r` is NaN.
+    pub fn try_real(r: f64) -> Result<Self, NanError> {
+        match ordered_float::NotNan::new(r) {
+            Ok(r) => Ok(Self::Real(r)),
+            Err(_) => Err(NanError),
+        }
+    }
+
+    /// Convert this number to an [`f64`].
+    ///
+    /// Machine reals are returned exactly. Every other number is rounded to the nearest
+    /// `f64`: integers with a magnitude greater than 2<sup>53</sup> may lose precision,
+    /// and arbitrary-precision integers outside the range of `f64` are converted to an
+    /// infinity.
+    ///
+    /// ```
+    /// use wolfram_expr::Number;
+    ///
+    /// assert_eq!(Number::Integer(-3).to_f64(), -3.0);
+    /// assert_eq!(Number::real(2.5).to_f64(), 2.5);
+    /// assert_eq!(Number::Integer(i64::MAX).to_f64(), 9223372036854775807.0);
+    /// ```
+    pub fn to_f64(&self) -> f64 {
+        match self {
+            Number::Integer(int) => *int as f64,
+            #[cfg(feature = "bigint")]
+            Number::BigInteger(int) => crate::big_integer::big_integer_to_f64(int),
+            #[cfg(feature = "rational")]
+            Number::Rational(rational) => crate::rational::rational_to_f64(rational),
+            Number::Real(real) => **real,
+            #[cfg(feature = "bigreal")]
+            Number::BigReal(real) => real.to_f64(),
+        }
+    }
+
+    /// Convert this number to an [`i64`], if it has an exact integral value in the range
+    /// of `i64`.
+    ///
+    /// No rounding or truncation is ever performed: a real with a fractional part, or
+    /// any number outside the range of `i64`, returns `None`. A real with an integral
+    /// value, like `2.0`, is converted exactly. `-0.0` is converted to `0`.
+    ///
+    /// ```
+    /// use wolfram_expr::Number;
+    ///
+    /// assert_eq!(Number::Integer(-3).to_i64(), Some(-3));
+    /// assert_eq!(Number::real(2.0).to_i64(), Some(2));
+    /// assert_eq!(Number::real(2.5).to_i64(), None);
+    /// assert_eq!(Number::real(1e19).to_i64(), None);
+    /// ```
+    pub fn to_i64(&self) -> Option<i64> {
+        match self {
+            Number::Integer(int) => Some(*int),
+            #[cfg(feature = "bigint")]
+            Number::BigInteger(int) => i64::try_from(int).ok(),
+            #[cfg(feature = "rational")]
+            Number::Rational(rational) if rational.is_integer() => {
+                i64::try_from(rational.numer()).ok()
+            },
+            #[cfg(feature = "rational")]
+            Number::Rational(_) => None,
+            Number::Real(real) => {
+                // `i64::MIN` and `-i64::MIN` are exactly representable as `f64`s, so the
+                // range check is exact.
+                let in_range = -(i64::MIN as f64) > **real && **real >= i64::MIN as f64;
+
+                if real.fract() == 0.0 && in_range {
+                    Some(**real as i64)
+                } else {
+                    None
+                }
+            },
+            #[cfg(feature = "bigreal")]
+            Number::BigReal(real) if real.value().repr().is_int() => {
+                i64::try_from(real.value().to_int().value()).ok()
+            },
+            #[cfg(feature = "bigreal")]
+            Number::BigReal(_) => None,
+        }
     }
 }
 
+/// Error returned by [`Expr::try_real()`] and [`Number::try_real()`] when given a NaN
+/// value, which cannot be represented by an expression.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NanError;
+
+impl fmt::Display for NanError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "real number is NaN")
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for NanError {}
+
 //=======================================
 // Display & Debug impl/s
 //=======================================
@@ -380,9 +1035,44 @@ impl fmt::Debug for Expr {
 /// reconstruct the `Expr` being displayed. This means symbols will always include their
 /// contexts, special characters in String's will always be properly escaped, and numeric
 /// literals needing precision and accuracy marks will have them.
+///
+/// Machine reals are formatted using the shortest decimal representation that parses
+/// back to exactly the same `f64` value, with a `*^` exponent if needed, e.g. `2.0`,
+/// `0.25`, or `1.*^-15`, so they never need a precision mark: for any
+/// finite real `x`, parsing the `Display` form of `Expr::real(x)` with
+/// `parse::parse_expr()` returns an equal expression.
+///
+/// # Formatting flags
+///
+/// * The alternate flag, `{:#}`, formats the expression across multiple lines, like
+///   [`Expr::to_string_pretty()`] with an indent of 4 spaces.
+/// * The precision, e.g. `{:.3}`, is the maximum number of elements shown for each
+///   normal expression. Any further elements are replaced by a `<N more>` marker, so the
+///   result can no longer be parsed.
+///
+/// ```
+/// use wolfram_expr::expr;
+///
+/// let list = expr!(System::List[1, 2, 3, 4, 5, f[a, b, c, d]]);
+///
+/// assert_eq!(format!("{:.3}", list), "System`List[1, 2, 3, <3 more>]");
+/// assert_eq!(format!("{:.0}", list), "System`List[<6 more>]");
+/// ```
+///
+/// The width and fill flags are ignored.
 impl fmt::Display for Expr {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "{}", self.inner)
+        if f.alternate() {
+            let pretty =
+                format::pretty_string(self, format::PRETTY_INDENT, f.precision());
+
+            return f.write_str(&pretty);
+        }
+
+        match f.precision() {
+            Some(max_elements) => format::fmt_truncated(f, self, max_elements),
+            None => write!(f, "{}", self.inner),
+        }
     }
 }
 
@@ -391,7 +1081,20 @@ impl fmt::Display for ExprKind {
         match *self {
             Self::Normal(ref normal) => fmt::Display::fmt(normal, f),
             Self::Integer(ref int) => fmt::Display::fmt(int, f),
-            Self::Real(ref real) => fmt::Display::fmt(real, f),
+            #[cfg(feature = "bigint")]
+            Self::BigInteger(ref int) => fmt::Display::fmt(int, f),
+            #[cfg(feature = "rational")]
+            Self::Rational(ref rational) => write!(
+                f,
+                "System`Rational[{}, {}]",
+                rational.numer(),
+                rational.denom()
+            ),
+            // Use the same form as `Number`, so that integral reals keep their decimal
+            // point, e.g. `2.0` instead of `2`.
+            Self::Real(ref real) => fmt::Display::fmt(&Number::Real(*real), f),
+            #[cfg(feature = "bigreal")]
+            Self::BigReal(ref real) => fmt::Display::fmt(real, f),
             Self::String(ref string) => {
                 // Escape any '"' which appear in the string.
                 // Using the Debug implementation will cause \n, \t, etc. to appear in
@@ -414,12 +1117,15 @@ impl fmt::Debug for ExprKind {
 impl fmt::Display for Normal {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(f, "{}[", self.head)?;
-        for (idx, elem) in self.contents.iter().enumerate() {
-            write!(f, "{}", elem)?;
-            if idx != self.contents.len() - 1 {
-                write!(f, ", ")?;
+
+        let mut elements = self.contents.iter();
+        if let Some(first) = elements.next() {
+            write!(f, "{}", first)?;
+            for elem in elements {
+                write!(f, ", {}", elem)?;
             }
         }
+
         write!(f, "]")
     }
 }
@@ -428,11 +1134,23 @@ impl fmt::Display for Number {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match *self {
             Self::Integer(ref int) => write!(f, "{}", int),
-            Self::Real(ref real) => {
-                // Make sure we're not printing NotNan (which surprisingly implements
-                // Display)
-                write!(f, "{:?}", **real)
-            },
+            #[cfg(feature = "bigint")]
+            Self::BigInteger(ref int) => write!(f, "{}", int),
+            #[cfg(feature = "rational")]
+            Self::Rational(ref rational) => write!(
+                f,
+                "System`Rational[{}, {}]",
+                rational.numer(),
+                rational.denom()
+            ),
+            // Make sure we're not printing NotNan (which surprisingly implements
+            // Display). Infinite reals have no Wolfram Language syntax.
+            Self::Real(ref real) if real.is_infinite() => write!(f, "{:?}", **real),
+            // The shortest form that parses back to the same value, so no precision
+            // mark is needed.
+            Self::Real(ref real) => format::fmt_finite_real(f, **real, false),
+            #[cfg(feature = "bigreal")]
+            Self::BigReal(ref real) => fmt::Display::fmt(real, f),
         }
     }
 }
@@ -441,6 +1159,20 @@ impl fmt::Display for Number {
 // Comparision trait impls
 //======================================
 
+impl PartialEq for Expr {
+    fn eq(&self, other: &Self) -> bool {
+        // Fast path: clones of the same expression share an allocation, and every
+        // expression is equal to itself (reals are never NaN).
+        Arc::ptr_eq(&self.inner, &other.inner) || self.inner == other.inner
+    }
+}
+
+impl Hash for Expr {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.inner.hash(state)
+    }
+}
+
 impl PartialEq<Symbol> for Expr {
     fn eq(&self, other: &Symbol) -> bool {
         match self.kind() {
@@ -449,3 +1181,133 @@ impl PartialEq<Symbol> for Expr {
         }
     }
 }
+
+/// Returns `true` if this is an [`ExprKind::Integer`] equal to `other`.
+///
+/// Arbitrary-precision integers are always outside the range of `i64`, so they are
+/// never equal to an `i64`.
+impl PartialEq<i64> for Expr {
+    fn eq(&self, other: &i64) -> bool {
+        match self.kind() {
+            ExprKind::Integer(int) => int == other,
+            _ => false,
+        }
+    }
+}
+
+/// Returns `true` if this is an [`ExprKind::Real`] equal to `other`.
+///
+/// Numbers of different types are never equal: `Expr::from(5) == 5.0` is `false`.
+impl PartialEq<f64> for Expr {
+    fn eq(&self, other: &f64) -> bool {
+        match self.kind() {
+            ExprKind::Real(real) => **real == *other,
+            _ => false,
+        }
+    }
+}
+
+/// Returns `true` if this is an [`ExprKind::String`] equal to `other`.
+///
+/// Symbols are never equal to a string: `` Expr::symbol(Symbol::new("Global`x")) ``
+/// is not equal to `` "Global`x" ``.
+impl PartialEq<str> for Expr {
+    fn eq(&self, other: &str) -> bool {
+        match self.kind() {
+            ExprKind::String(string) => string == other,
+            _ => false,
+        }
+    }
+}
+
+/// Returns `true` if this is an [`ExprKind::String`] equal to `other`.
+impl PartialEq<&str> for Expr {
+    fn eq(&self, other: &&str) -> bool {
+        *self == **other
+    }
+}
+
+//======================================
+// Drop impl
+//======================================
+
+/// Dropping a deeply nested expression does not overflow the call stack.
+///
+/// When the last reference to a normal expression is dropped, its subexpressions are
+/// moved onto an explicit stack and dropped one at a time, instead of recursively.
+/// Subexpressions that are still shared with another expression are only released.
+impl Drop for Expr {
+    fn drop(&mut self) {
+        let normal = match Arc::get_mut(&mut self.inner) {
+            Some(ExprKind::Normal(normal)) => normal,
+            _ => return,
+        };
+
+        // Fast path: dropping the head and elements will not recurse any further.
+        if !has_unique_normal_child(normal) {
+            return;
+        }
+
+        // Shared by every head that is moved onto the stack, so that each one costs a
+        // reference count increment instead of an allocation.
+        let placeholder = Expr::from(0);
+
+        let mut stack = Vec::new();
+        take_children(normal, &mut stack, &placeholder);
+
+        while let Some(mut expr) = stack.pop() {
+            if let Some(ExprKind::Normal(normal)) = Arc::get_mut(&mut expr.inner) {
+                take_children(normal, &mut stack, &placeholder);
+            }
+            // `expr` has no uniquely owned normal children left, so dropping it here
+            // takes the fast path above.
+        }
+    }
+}
+
+/// Returns `true` if `expr` is a normal expression that is not shared with any other
+/// expression.
+fn is_unique_normal(expr: &Expr) -> bool {
+    Arc::strong_count(&expr.inner) == 1 && matches!(*expr.inner, ExprKind::Normal(_))
+}
+
+fn has_unique_normal_child(normal: &Normal) -> bool {
+    is_unique_normal(&normal.head)
+        || (has_unique_elements(normal) && normal.contents.iter().any(is_unique_normal))
+}
+
+/// Returns `true` if the elements of `normal` are not shared with any other normal
+/// expression.
+#[cfg(not(feature = "smallvec"))]
+fn has_unique_elements(normal: &Normal) -> bool {
+    Arc::strong_count(&normal.contents) == 1
+}
+
+/// Elements stored in a `SmallVec` are never shared.
+#[cfg(feature = "smallvec")]
+fn has_unique_elements(_: &Normal) -> bool {
+    true
+}
+
+/// Move the head and elements of `normal` onto `stack`, leaving a clone of
+/// `placeholder` as its head.
+fn take_children(normal: &mut Normal, stack: &mut Vec<Expr>, placeholder: &Expr) {
+    // Elements can't be moved out of an `Arc<[Expr]>`. Instead, push a clone of each
+    // element and then release the originals: the clones on `stack` become the unique
+    // owners, and releasing the originals does not recurse because they are shared at
+    // that point.
+    #[cfg(not(feature = "smallvec"))]
+    if has_unique_elements(normal) {
+        stack.extend(normal.contents.iter().cloned());
+        normal.contents = Arc::from(Vec::new());
+    }
+
+    #[cfg(feature = "smallvec")]
+    stack.extend(normal.contents.drain(..));
+
+    // Only replace the head if dropping it could recurse. Symbol heads, the common
+    // case, are left in place.
+    if is_unique_normal(&normal.head) {
+        stack.push(mem::replace(&mut normal.head, placeholder.clone()));
+    }
+}
@@ -0,0 +1,313 @@
+//! Implementation details of the [`expr!`][crate::expr!] macro.
+
+use crate::{Expr, Symbol};
+
+// Re-exported so that `expr!` can be used in `#![no_std]` crates.
+pub use alloc::vec;
+
+/// Names of the `` System` `` symbols that the [`expr!`][crate::expr!] macro resolves
+/// without an explicit context.
+///
+/// Keep this list sorted, and in sync with the list in the documentation of `expr!`.
+///
+/// Any other identifier without a context is a symbol in the `` Global` `` context.
+pub const SYSTEM_SYMBOLS: &[&str] = &[
+    "And",
+    "Apply",
+    "Association",
+    "Blank",
+    "Complex",
+    "Cos",
+    "DirectedInfinity",
+    "Equal",
+    "Exp",
+    "Function",
+    "Greater",
+    "GreaterEqual",
+    "Hold",
+    "HoldComplete",
+    "HoldForm",
+    "If",
+    "Less",
+    "LessEqual",
+    "List",
+    "Log",
+    "Map",
+    "Missing",
+    "Not",
+    "Null",
+    "Or",
+    "Part",
+    "Pattern",
+    "Plus",
+    "Power",
+    "Rational",
+    "Rule",
+    "RuleDelayed",
+    "Sequence",
+    "Set",
+    "SetDelayed",
+    "Sin",
+    "Slot",
+    "Sqrt",
+    "Tan",
+    "Times",
+    "Unequal",
+];
+
+
+/// Construct an [`Expr`] using Wolfram Language-like syntax.
+///
+/// The following forms are supported:
+///
+/// Syntax               | Expression
+/// ---------------------|------------------------------------------------------------
+/// `5`, `-2.5`          | Integer or real number
+/// `"hello"`            | String
+/// `true`, `false`      | `` System`True ``, `` System`False ``
+/// `x`                  | Symbol in the `` Global` `` context: `` Global`x ``
+/// `Plus`               | Known symbol in the `` System` `` context: `` System`Plus ``
+/// `System::Plus`       | Symbol with an explicit context: `` System`Plus ``
+/// `f[a, b]`, `g[x][y]` | Normal expression
+/// `#var`               | The Rust variable `var`, converted with `Expr::from(var.clone())`
+/// `#(expr)`            | The Rust expression `expr`, converted with `Expr::from(expr)`
+///
+/// # Example
+///
+/// Construct the expression `` System`Plus[Global`a, System`Times[Global`b, 2]] ``:
+///
+/// ```
+/// use wolfram_expr::{expr, Expr, Symbol};
+///
+/// let b = Expr::symbol(Symbol::new("Global`b"));
+///
+/// let expr = expr!(Plus[a, Times[#b, 2]]);
+///
+/// assert_eq!(expr.to_string(), "System`Plus[Global`a, System`Times[Global`b, 2]]");
+/// ```
+///
+/// # Symbol contexts
+///
+/// An identifier without a context is a `` System` `` symbol if it is one of these
+/// common symbols:
+///
+/// `And`, `Apply`, `Association`, `Blank`, `Complex`, `Cos`, `DirectedInfinity`, `Equal`,
+/// `Exp`, `Function`, `Greater`, `GreaterEqual`, `Hold`, `HoldComplete`, `HoldForm`,
+/// `If`, `Less`, `LessEqual`, `List`, `Log`, `Map`, `Missing`, `Not`, `Null`, `Or`,
+/// `Part`, `Pattern`, `Plus`, `Power`, `Rational`, `Rule`, `RuleDelayed`, `Sequence`,
+/// `Set`, `SetDelayed`, `Sin`, `Slot`, `Sqrt`, `Tan`, `Times`, and `Unequal`.
+///
+/// Any other identifier without a context is a `` Global` `` symbol. Write
+/// `Global::Plus` to construct `` Global`Plus ``.
+///
+/// # Panics
+///
+/// Wolfram Language symbol names cannot contain underscores. Using a Rust identifier
+/// that contains an underscore as a symbol will panic.
+#[macro_export]
+macro_rules! expr {
+    (# $var:ident $($rest:tt)*) => {
+        $crate::expr!(@apply $crate::Expr::from($var.clone()); $($rest)*)
+    };
+    (# ($value:expr) $($rest:tt)*) => {
+        $crate::expr!(@apply $crate::Expr::from($value); $($rest)*)
+    };
+    ($lit:literal) => {
+        $crate::macros::ExprLiteral::into_expr($lit)
+    };
+
+    //
+    // Symbols: `name` or `ctx1::ctx2::name`
+    //
+
+    (@path [$($context:ident)*] $component:ident :: $($rest:tt)*) => {
+        $crate::expr!(@path [$($context)* $component] $($rest)*)
+    };
+    (@path [] $name:ident $($rest:tt)*) => {
+        $crate::expr!(
+            @apply $crate::macros::bare_symbol(stringify!($name));
+            $($rest)*
+        )
+    };
+    (@path [$($context:ident)+] $name:ident $($rest:tt)*) => {
+        $crate::expr!(
+            @apply $crate::Expr::symbol($crate::Symbol::new(
+                concat!($(stringify!($context), "`",)+ stringify!($name))
+            ));
+            $($rest)*
+        )
+    };
+
+    //
+    // Normal expressions: a head followed by any number of `[...]` argument lists
+    //
+
+    (@apply $head:expr; [$($args:tt)*] $($rest:tt)*) => {
+        $crate::expr!(
+            @apply $crate::Expr::normal($head, $crate::__expr_args!([] [] $($args)*));
+            $($rest)*
+        )
+    };
+    (@apply $head:expr;) => {
+        $head
+    };
+
+    ($($tokens:tt)+) => {
+        $crate::expr!(@path [] $($tokens)+)
+    };
+}
+
+/// Split a comma-separated argument list into a `Vec` of [`expr!`] invocations.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __expr_args {
+    // Fast paths for runs of literal arguments, to avoid hitting the macro recursion
+    // limit when constructing long lists of numbers or strings.
+    (
+        [$($args:expr),*] []
+        $a:literal, $b:literal, $c:literal, $d:literal $(, $($rest:tt)*)?
+    ) => {
+        $crate::__expr_args!(
+            [
+                $($args,)*
+                $crate::expr!($a), $crate::expr!($b), $crate::expr!($c), $crate::expr!($d)
+            ]
+            []
+            $($($rest)*)?
+        )
+    };
+    ([$($args:expr),*] [] $a:literal $(, $($rest:tt)*)?) => {
+        $crate::__expr_args!([$($args,)* $crate::expr!($a)] [] $($($rest)*)?)
+    };
+
+    ([$($args:expr),*] []) => {
+        $crate::macros::vec![$($args),*]
+    };
+    ([$($args:expr),*] [$($current:tt)+]) => {
+        $crate::macros::vec![$($args,)* $crate::expr!($($current)+)]
+    };
+    ([$($args:expr),*] [$($current:tt)*] , $($rest:tt)*) => {
+        $crate::__expr_args!([$($args,)* $crate::expr!($($current)*)] [] $($rest)*)
+    };
+    ([$($args:expr),*] [$($current:tt)*] $next:tt $($rest:tt)*) => {
+        $crate::__expr_args!([$($args),*] [$($current)* $next] $($rest)*)
+    };
+}
+
+/// Construct the symbol named by an identifier without a context in [`expr!`].
+#[doc(hidden)]
+pub fn bare_symbol(name: &str) -> Expr {
+    let context = match SYSTEM_SYMBOLS.binary_search(&name) {
+        Ok(_) => "System`",
+        Err(_) => "Global`",
+    };
+
+    Expr::symbol(Symbol::new(&[context, name].concat()))
+}
+
+/// Rust literal that can be converted to an [`Expr`] by the [`expr!`] macro.
+pub trait ExprLiteral {
+    #[allow(missing_docs)]
+    fn into_expr(self) -> Expr;
+}
+
+impl ExprLiteral for i64 {
+    fn into_expr(self) -> Expr {
+        Expr::from(self)
+    }
+}
+
+impl ExprLiteral for f64 {
+    fn into_expr(self) -> Expr {
+        Expr::real(self)
+    }
+}
+
+impl ExprLiteral for &str {
+    fn into_expr(self) -> Expr {
+        Expr::from(self)
+    }
+}
+
+impl ExprLiteral for bool {
+    fn into_expr(self) -> Expr {
+        Expr::from(self)
+    }
+}
+
+#[test]
+fn test_expr_macro() {
+    use crate::Symbol;
+
+    assert_eq!(expr!(5), Expr::from(5));
+    assert_eq!(expr!(-5), Expr::from(-5));
+    assert_eq!(expr!(2.5), Expr::real(2.5));
+    assert_eq!(expr!(-2.5), Expr::real(-2.5));
+    assert_eq!(expr!("hello"), Expr::from("hello"));
+    assert_eq!(expr!(true), Expr::from(true));
+    assert_eq!(expr!(x), Expr::symbol(Symbol::new("Global`x")));
+    assert_eq!(
+        expr!(MyPackage::Private::x),
+        Expr::symbol(Symbol::new("MyPackage`Private`x"))
+    );
+
+    assert_eq!(expr!(f[]).to_string(), "Global`f[]");
+    assert_eq!(expr!(f[1, "a",]).to_string(), "Global`f[1, \"a\"]");
+    assert_eq!(
+        expr!(g[x][System::List[1, -2], y]).to_string(),
+        "Global`g[Global`x][System`List[1, -2], Global`y]"
+    );
+
+    // Long argument lists don't exceed the macro recursion limit.
+    let list = expr!(System::List[
+        0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19,
+        20, 21, 22, 23, 24, 25, 26, 27, 28, 29, 30, 31, 32, 33, 34, 35, 36, 37, 38, 39,
+        40, 41, 42, 43, 44, 45, 46, 47, 48, 49, 50, 51, 52, 53, 54, 55, 56, 57, 58, 59,
+        60, 61, 62, 63, 64, 65, 66, 67, 68, 69, 70, 71, 72, 73, 74, 75, 76, 77, 78, 79,
+        80, 81, 82, 83, 84, 85, 86, 87, 88, 89, 90, 91, 92, 93, 94, 95, 96, 97, 98, 99,
+        x
+    ]);
+    let mut elements: Vec<Expr> = (0..100).map(Expr::from).collect();
+    elements.push(Expr::symbol(Symbol::new("Global`x")));
+    assert_eq!(list, Expr::list(elements));
+}
+
+#[test]
+fn test_expr_macro_system_symbols() {
+    use crate::Symbol;
+
+    assert!(SYSTEM_SYMBOLS.windows(2).all(|pair| pair[0] < pair[1]));
+
+    assert_eq!(expr!(List), Expr::symbol(Symbol::new("System`List")));
+    assert_eq!(
+        expr!(Plus[1, 2, x]),
+        Expr::normal(Symbol::new("System`Plus"), vec![
+            Expr::from(1),
+            Expr::from(2),
+            Expr::symbol(Symbol::new("Global`x"))
+        ])
+    );
+    assert_eq!(
+        expr!(Plus[1, Times[2, x]]),
+        expr!(System::Plus[1, System::Times[2, x]])
+    );
+
+    // Explicit contexts are unchanged.
+    assert_eq!(expr!(Global::Plus).to_string(), "Global`Plus");
+    assert_eq!(expr!(MyList[]).to_string(), "Global`MyList[]");
+}
+
+#[test]
+fn test_expr_macro_interpolation() {
+    let x = Expr::from(1);
+    let x_ref = &x;
+    let n = 7;
+
+    assert_eq!(expr!(#x), x);
+    assert_eq!(expr!(f[#x, #x_ref, #n]).to_string(), "Global`f[1, 1, 7]");
+    assert_eq!(expr!(f[#(n + 1)]).to_string(), "Global`f[8]");
+
+    // Interpolated values can be used as heads.
+    let head = expr!(g[1]);
+    assert_eq!(expr!(#head[2]).to_string(), "Global`g[1][2]");
+}
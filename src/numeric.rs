@@ -0,0 +1,478 @@
+//! Transformations and comparisons of the numbers in an expression.
+
+use alloc::vec::Vec;
+use core::convert::TryFrom;
+
+use crate::{
+    arith::{arithmetic, Op},
+    visit::Transformer,
+    Expr, ExprKind, Normal, Number, Transform,
+};
+
+
+impl Expr {
+    /// Replace each real number in this expression whose absolute value is less than
+    /// `tolerance` with the integer `0`.
+    ///
+    /// This is equivalent to
+    /// [`Chop`](https://reference.wolfram.com/language/ref/Chop.html)<sub>WL</sub>,
+    /// which uses a default tolerance of `1e-10`. Heads are not modified.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use wolfram_expr::expr;
+    ///
+    /// let expr = expr!(System::List[1.0, 1e-15, 2.0]);
+    ///
+    /// assert_eq!(expr.chop(1e-10), expr!(System::List[1.0, 0, 2.0]));
+    /// ```
+    pub fn chop(&self, tolerance: f64) -> Expr {
+        self.transform(|expr| match expr.kind() {
+            ExprKind::Real(real) if real.abs() < tolerance => {
+                Transform::Replace(Expr::from(0))
+            },
+            #[cfg(feature = "bigreal")]
+            ExprKind::BigReal(real) if real.to_f64().abs() < tolerance => {
+                Transform::Replace(Expr::from(0))
+            },
+            ExprKind::Normal(_) => Transform::Descend,
+            _ => Transform::Keep,
+        })
+    }
+
+    /// Convert each integer and rational number in this expression to the nearest real
+    /// number.
+    ///
+    /// This is a simplified form of
+    /// [`N`](https://reference.wolfram.com/language/ref/N.html)<sub>WL</sub>, for use
+    /// before passing an expression to numeric code. Only numbers are converted:
+    /// functions are **not** evaluated, so `Plus[1, 2]` becomes `Plus[1.0, 2.0]`, not
+    /// `3.0`. Symbols, strings, and heads are not modified. Arbitrary-precision
+    /// integers too large for an `f64` are converted to an infinity.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use wolfram_expr::expr;
+    ///
+    /// let expr = expr!(System::Plus[1, System::Times[2, x], 0.5]);
+    ///
+    /// assert_eq!(
+    ///     expr.to_real_approx(),
+    ///     expr!(System::Plus[1.0, System::Times[2.0, x], 0.5])
+    /// );
+    /// ```
+    pub fn to_real_approx(&self) -> Expr {
+        self.transform(|expr| match expr.kind() {
+            ExprKind::Integer(int) => Transform::Replace(Expr::real(*int as f64)),
+            #[cfg(feature = "bigint")]
+            ExprKind::BigInteger(int) => Transform::Replace(Expr::real(
+                crate::big_integer::big_integer_to_f64(int),
+            )),
+            #[cfg(feature = "rational")]
+            ExprKind::Rational(rational) => {
+                Transform::Replace(Expr::real(crate::rational::rational_to_f64(rational)))
+            },
+            ExprKind::Normal(_) => Transform::Descend,
+            _ => Transform::Keep,
+        })
+    }
+
+    /// Evaluate each `` System`Plus `` and `` System`Times `` subexpression whose
+    /// elements are all numbers, and each `` System`Power `` subexpression whose
+    /// elements are machine integers or reals.
+    ///
+    /// Subexpressions are folded bottom-up, so `Plus[1, Times[2, 3]]` becomes `7`.
+    /// `Plus` and `Times` are evaluated using the arithmetic operators of [`Number`],
+    /// which follow the Wolfram Language's rules for combining number types: the result
+    /// is exact if every element is exact, and a real if any element is a real.
+    /// `Plus[]` folds to `0` and `Times[]` to `1`.
+    ///
+    /// Subexpressions whose result can't be represented as a number are left
+    /// unchanged. These include:
+    ///
+    /// * integers raised to negative integer powers, like `Power[2, -1]`, whose result
+    ///   is a rational number, unless the base is `1` or `-1`;
+    /// * integer powers that overflow `i64`, like `Power[10, 19]`;
+    /// * indeterminate or infinite results, like `Power[0, 0]`, `Power[0, -1]`, and
+    ///   `Power[10., 400]`, including reals that overflow to infinity;
+    /// * negative numbers raised to non-integer powers, whose result is complex.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use wolfram_expr::expr;
+    ///
+    /// let expr = expr!(f[System::Plus[1, System::Times[2, 3]], System::Power[2, 0.5]]);
+    ///
+    /// assert_eq!(expr.fold_constants(), expr!(f[7, 1.4142135623730951]));
+    ///
+    /// assert_eq!(
+    ///     expr!(System::Plus[x, System::Times[2, 3]]).fold_constants(),
+    ///     expr!(System::Plus[x, 6])
+    /// );
+    /// ```
+    pub fn fold_constants(&self) -> Expr {
+        FoldConstants.transform(self)
+    }
+
+    /// Returns `true` if this expression is structurally equal to `other`, treating
+    /// integers and reals with the same mathematical value as equal.
+    ///
+    /// Unlike `==`, which considers `2` and `2.0` to be different expressions, this
+    /// compares an integer and a real by their *exact* values: `2` is equal to `2.0`,
+    /// but not to `2.0000001`, and `9007199254740993` is not equal to
+    /// `9007199254740992.0`, even though converting the integer to an `f64` would
+    /// round it to that value. No tolerance is applied to reals, so `0.1 + 0.2` is not
+    /// equal to `0.3`.
+    ///
+    /// Exact rational numbers are equal to reals with the same exact value, like `1/2`
+    /// and `0.5`. Arbitrary-precision reals are only equal to equal arbitrary-precision
+    /// reals.
+    ///
+    /// Normal expressions are equal if their heads and elements are `numeric_eq`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use wolfram_expr::expr;
+    ///
+    /// assert!(expr!(f[1, g[2.0]]).numeric_eq(&expr!(f[1.0, g[2]])));
+    ///
+    /// assert!(!expr!(2).numeric_eq(&expr!(2.0000001)));
+    /// assert!(!expr!("2").numeric_eq(&expr!(2)));
+    /// ```
+    pub fn numeric_eq(&self, other: &Expr) -> bool {
+        match (self.kind(), other.kind()) {
+            (ExprKind::Normal(a), ExprKind::Normal(b)) => {
+                a.elements().len() == b.elements().len()
+                    && a.head().numeric_eq(b.head())
+                    && a.elements()
+                        .iter()
+                        .zip(b.elements())
+                        .all(|(a, b)| a.numeric_eq(b))
+            },
+            _ => match (self.try_as_number(), other.try_as_number()) {
+                (Some(a), Some(b)) => numbers_eq(&a, &b),
+                _ => self == other,
+            },
+        }
+    }
+}
+
+/// Returns `true` if `a` and `b` have the same exact value.
+fn numbers_eq(a: &Number, b: &Number) -> bool {
+    match (a, b) {
+        (Number::Integer(int), real @ Number::Real(_))
+        | (real @ Number::Real(_), Number::Integer(int)) => real.to_i64() == Some(*int),
+        #[cfg(feature = "bigint")]
+        (Number::BigInteger(int), Number::Real(real))
+        | (Number::Real(real), Number::BigInteger(int)) => {
+            use num_traits::FromPrimitive;
+
+            real.fract() == 0.0
+                && num_bigint::BigInt::from_f64(**real).as_ref() == Some(int)
+        },
+        #[cfg(feature = "rational")]
+        (Number::Rational(rational), Number::Real(real))
+        | (Number::Real(real), Number::Rational(rational)) => {
+            num_rational::BigRational::from_float(**real).as_ref() == Some(rational)
+        },
+        _ => a == b,
+    }
+}
+
+//======================================
+// Constant folding
+//======================================
+
+struct FoldConstants;
+
+impl Transformer for FoldConstants {
+    fn transform_normal(&mut self, normal: &Normal) -> Option<Expr> {
+        let folded = self.transform_children(normal);
+
+        fold(folded.as_ref().unwrap_or(normal)).or_else(|| folded.map(Expr::from))
+    }
+}
+
+/// Machine number operand of a folded `Power` expression.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Machine {
+    Integer(i64),
+    Real(f64),
+}
+
+/// Evaluate `normal`, or return `None` if it can't be folded.
+fn fold(normal: &Normal) -> Option<Expr> {
+    let name = normal.head().try_as_symbol()?.as_str();
+
+    let args = normal
+        .elements()
+        .iter()
+        .map(Expr::try_as_number)
+        .collect::<Option<Vec<Number>>>()?;
+
+    let sum_or_product = |op, identity| {
+        args.iter()
+            .cloned()
+            .try_fold(Number::Integer(identity), |a, b| arithmetic(op, a, b))
+    };
+
+    let result = match name {
+        "System`Plus" => sum_or_product(Op::Add, 0)?,
+        "System`Times" => sum_or_product(Op::Mul, 1)?,
+        "System`Power" => match args.as_slice() {
+            [base, exp] => {
+                match Machine::from_number(base)?.pow(Machine::from_number(exp)?)? {
+                    Machine::Integer(int) => Number::Integer(int),
+                    Machine::Real(real) => Number::try_real(real).ok()?,
+                }
+            },
+            _ => return None,
+        },
+        _ => return None,
+    };
+
+    // Infinite reals have no Wolfram Language syntax, and are not the value of the
+    // expression: `Plus[1.*^308, 1.*^308]` is an overflow, not `Infinity`.
+    if let Number::Real(real) = result {
+        if !real.is_finite() {
+            return None;
+        }
+    }
+
+    Some(Expr::number(result))
+}
+
+impl Machine {
+    fn from_number(number: &Number) -> Option<Machine> {
+        match number {
+            Number::Integer(int) => Some(Machine::Integer(*int)),
+            Number::Real(real) => Some(Machine::Real(**real)),
+            #[cfg(feature = "bigint")]
+            Number::BigInteger(_) => None,
+            #[cfg(feature = "rational")]
+            Number::Rational(_) => None,
+            #[cfg(feature = "bigreal")]
+            Number::BigReal(_) => None,
+        }
+    }
+
+    fn to_f64(self) -> f64 {
+        match self {
+            Machine::Integer(int) => int as f64,
+            Machine::Real(real) => real,
+        }
+    }
+
+    /// Returns `None` if the result is not a real number, is indeterminate or
+    /// infinite, or is an integer that overflows `i64`.
+    fn pow(self, exp: Machine) -> Option<Machine> {
+        let (base_f64, exp_f64) = (self.to_f64(), exp.to_f64());
+
+        // `0^0` is indeterminate, and `0^-n` is complex infinity.
+        if base_f64 == 0.0 && exp_f64 <= 0.0 {
+            return None;
+        }
+
+        match (self, exp) {
+            // Exact for any exponent, however large.
+            (Machine::Integer(base @ (0 | 1)), Machine::Integer(_)) => {
+                Some(Machine::Integer(base))
+            },
+            (Machine::Integer(-1), Machine::Integer(exp)) => {
+                Some(Machine::Integer(if exp % 2 == 0 { 1 } else { -1 }))
+            },
+            (Machine::Integer(_), Machine::Integer(exp)) if exp < 0 => None,
+            (Machine::Integer(base), Machine::Integer(exp)) => {
+                let exp = u32::try_from(exp).ok()?;
+
+                base.checked_pow(exp).map(Machine::Integer)
+            },
+            _ => Some(Machine::Real(base_f64.powf(exp_f64))),
+        }
+    }
+}
+
+#[test]
+fn test_chop() {
+    use crate::expr;
+
+    assert_eq!(
+        expr!(System::List[1.0, 1e-15, 2.0]).chop(1e-10),
+        expr!(System::List[1.0, 0, 2.0])
+    );
+    assert_eq!(
+        expr!(f[-1e-12, g[1e-11, 0.5], 0, x]).chop(1e-10),
+        expr!(f[0, g[0, 0.5], 0, x])
+    );
+
+    // The tolerance is exclusive.
+    assert_eq!(expr!(f[0.5, -0.5]).chop(0.5), expr!(f[0.5, -0.5]));
+    assert_eq!(expr!(1e-20).chop(1e-10), expr!(0));
+}
+
+#[test]
+fn test_fold_constants() {
+    use crate::expr;
+
+    assert_eq!(
+        expr!(System::Plus[1, System::Times[2, 3]]).fold_constants(),
+        expr!(7)
+    );
+    assert_eq!(
+        expr!(System::Times[System::Plus[1, 2], System::Power[2, 3]]).fold_constants(),
+        expr!(24)
+    );
+    assert_eq!(expr!(System::Plus[]).fold_constants(), expr!(0));
+    assert_eq!(expr!(System::Times[]).fold_constants(), expr!(1));
+
+    // Reals are contagious.
+    assert_eq!(expr!(System::Plus[1, 0.5]).fold_constants(), expr!(1.5));
+    assert_eq!(expr!(System::Times[2, 1.0]).fold_constants(), expr!(2.0));
+    assert_eq!(expr!(System::Power[2.0, 2]).fold_constants(), expr!(4.0));
+    assert_eq!(expr!(System::Power[4, 0.5]).fold_constants(), expr!(2.0));
+    assert_eq!(expr!(System::Power[-2, 3]).fold_constants(), expr!(-8));
+
+    // Integer overflow follows the `Number` arithmetic operators.
+    #[cfg(not(feature = "bigint"))]
+    {
+        assert_eq!(
+            expr!(System::Plus[#(i64::MAX), 1]).fold_constants(),
+            Expr::real(i64::MAX as f64 + 1.0)
+        );
+        assert_eq!(
+            expr!(System::Times[4611686018427387904, 4]).fold_constants(),
+            Expr::real(18446744073709551616.0)
+        );
+    }
+    #[cfg(feature = "bigint")]
+    assert_eq!(
+        expr!(System::Plus[#(i64::MAX), 1]).fold_constants(),
+        Expr::big_integer(crate::BigInt::from(i64::MAX) + 1u8)
+    );
+
+    // Powers of 0, 1, and -1 are exact for any exponent.
+    assert_eq!(
+        expr!(System::Power[-1, 1000000000000000001]).fold_constants(),
+        expr!(-1)
+    );
+    assert_eq!(
+        expr!(System::Power[-1, 1000000000000000000]).fold_constants(),
+        expr!(1)
+    );
+    assert_eq!(
+        expr!(System::Power[1, 1000000000000000000]).fold_constants(),
+        expr!(1)
+    );
+    assert_eq!(
+        expr!(System::Power[0, 1000000000000000000]).fold_constants(),
+        expr!(0)
+    );
+    assert_eq!(expr!(System::Power[-1, -3]).fold_constants(), expr!(-1));
+    assert_eq!(expr!(System::Power[1, -3]).fold_constants(), expr!(1));
+
+    // Results that aren't machine numbers are not folded.
+    for expr in [
+        expr!(System::Power[2, -1]),
+        expr!(System::Power[0, 0]),
+        expr!(System::Power[0.0, -1]),
+        expr!(System::Power[-8, 0.5]),
+        expr!(System::Power[2, 3, 4]),
+        expr!(System::Power[10, 19]),
+        expr!(System::Power[2, 1000000000000000000]),
+        expr!(System::Power[10.0, 400]),
+        expr!(System::Plus[1e308, 1e308]),
+        expr!(System::Times[-1e308, 10]),
+    ] {
+        assert_eq!(expr.fold_constants(), expr);
+    }
+
+    // Non-numeric elements and other heads are not folded, but their elements are.
+    assert_eq!(
+        expr!(System::Plus[x, System::Plus[1, 2]]).fold_constants(),
+        expr!(System::Plus[x, 3])
+    );
+    assert_eq!(
+        expr!(f[System::Plus[1, 2], "a"]).fold_constants(),
+        expr!(f[3, "a"])
+    );
+    assert_eq!(
+        expr!(Global::Plus[1, 2]).fold_constants(),
+        expr!(Global::Plus[1, 2])
+    );
+    assert_eq!(
+        expr!(System::Plus[1, 2][System::Times[2, 2]]).fold_constants(),
+        Expr::normal(Expr::from(3), vec![Expr::from(4)])
+    );
+}
+
+#[test]
+fn test_numeric_eq() {
+    use crate::expr;
+
+    // Integers and reals.
+    assert!(expr!(2).numeric_eq(&expr!(2.0)));
+    assert!(expr!(-2.0).numeric_eq(&expr!(-2)));
+    assert!(expr!(0).numeric_eq(&expr!(-0.0)));
+    assert!(!expr!(2).numeric_eq(&expr!(2.0000001)));
+    assert!(!expr!(2).numeric_eq(&expr!(2.5)));
+    assert!(!expr!(9007199254740993).numeric_eq(&expr!(9007199254740992.0)));
+    assert!(Expr::from(i64::MIN).numeric_eq(&Expr::real(i64::MIN as f64)));
+    assert!(!Expr::from(i64::MAX).numeric_eq(&Expr::real(i64::MAX as f64)));
+    assert!(!expr!(1).numeric_eq(&Expr::real(f64::INFINITY)));
+
+    // Values of the same type are compared like `==`.
+    assert!(expr!(2).numeric_eq(&expr!(2)));
+    assert!(expr!(2.5).numeric_eq(&expr!(2.5)));
+    assert!(!expr!(2).numeric_eq(&expr!(3)));
+    assert!(expr!("a").numeric_eq(&expr!("a")));
+    assert!(!expr!("1").numeric_eq(&expr!(1)));
+    assert!(!expr!(x).numeric_eq(&expr!(1)));
+
+    // Normal expressions are compared recursively, including heads.
+    assert!(expr!(f[1, g[2.0]][3]).numeric_eq(&expr!(f[1.0, g[2]][3.0])));
+    assert!(!expr!(f[1, 2]).numeric_eq(&expr!(f[1.0])));
+    assert!(!expr!(f[1]).numeric_eq(&expr!(g[1.0])));
+    assert!(!expr!(f[1]).numeric_eq(&expr!(1)));
+
+    #[cfg(feature = "bigint")]
+    {
+        let big = Expr::big_integer(crate::BigInt::from(1u8) << 70u32);
+        assert!(big.numeric_eq(&Expr::real(2f64.powi(70))));
+        assert!(!big.numeric_eq(&Expr::real(2f64.powi(71))));
+    }
+
+    #[cfg(feature = "rational")]
+    {
+        assert!(Expr::rational(1, 2).numeric_eq(&expr!(0.5)));
+        assert!(expr!(-0.75).numeric_eq(&Expr::rational(-3, 4)));
+        assert!(!Expr::rational(1, 3).numeric_eq(&Expr::real(1.0 / 3.0)));
+    }
+}
+
+#[test]
+fn test_to_real_approx() {
+    use crate::expr;
+
+    assert_eq!(
+        expr!(System::Plus[1, 2]).to_real_approx(),
+        expr!(System::Plus[1.0, 2.0])
+    );
+    assert_eq!(
+        expr!(f[-3, "1", x, g[0, 2.5]]).to_real_approx(),
+        expr!(f[-3.0, "1", x, g[0.0, 2.5]])
+    );
+    assert_eq!(
+        Expr::from(i64::MAX).to_real_approx(),
+        Expr::real(9223372036854775807.0)
+    );
+
+    let expr = expr!(f[x, "a", g[1.0]]);
+    assert!(alloc::sync::Arc::ptr_eq(
+        &expr.inner,
+        &expr.to_real_approx().inner
+    ));
+}
@@ -0,0 +1,616 @@
+//! Canonical ordering of expressions.
+
+use core::cmp::Ordering;
+
+use crate::{Expr, ExprKind, Normal, Number};
+
+
+impl Expr {
+    /// Compare this expression to `other` using a canonical order that approximates
+    /// the order used by
+    /// [`Sort`](https://reference.wolfram.com/language/ref/Sort.html)<sub>WL</sub>.
+    ///
+    /// Expressions are ordered as follows:
+    ///
+    /// 1. Numbers come first, ordered by value. Numbers with the same value are ordered
+    ///    exact numbers first, then machine reals, then arbitrary-precision reals.
+    ///    Integers and rational numbers are compared exactly, but other numbers of
+    ///    different kinds are compared by their nearest `f64` values. This is the
+    ///    [`Ord`] implementation of [`Number`].
+    /// 2. Strings come next, ordered alphabetically, ignoring case. Strings that differ
+    ///    only in case are ordered lowercase first.
+    /// 3. Symbols come next, ordered like strings by their symbol name, and then by
+    ///    their context.
+    /// 4. Normal expressions come last, ordered by number of elements, then by head,
+    ///    and then by each element in turn.
+    ///
+    /// Unlike the derived [`PartialOrd`] implementation of [`Symbol`](crate::Symbol),
+    /// this order is meaningful to Wolfram Language users, but it is not guaranteed to
+    /// match `Sort` exactly. It is a total order, which returns [`Ordering::Equal`]
+    /// only for equal expressions.
+    ///
+    /// This is equivalent to [`wolfram_order()`](crate::wolfram_order).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::cmp::Ordering;
+    ///
+    /// use wolfram_expr::expr;
+    ///
+    /// assert_eq!(expr!(2).canonical_cmp(&expr!(10)), Ordering::Less);
+    /// assert_eq!(expr!(1.5).canonical_cmp(&expr!(1)), Ordering::Greater);
+    /// assert_eq!(expr!("b").canonical_cmp(&expr!(a)), Ordering::Less);
+    /// assert_eq!(expr!(f[z]).canonical_cmp(&expr!(f[a, b])), Ordering::Less);
+    /// ```
+    pub fn canonical_cmp(&self, other: &Expr) -> Ordering {
+        let (a, b) = (self.kind(), other.kind());
+
+        match (a, b) {
+            (ExprKind::String(a), ExprKind::String(b)) => cmp_names(a, b),
+            (ExprKind::Symbol(a), ExprKind::Symbol(b)) => {
+                cmp_names(a.symbol_name().as_str(), b.symbol_name().as_str())
+                    .then_with(|| cmp_names(a.context().as_str(), b.context().as_str()))
+            },
+            (ExprKind::Normal(a), ExprKind::Normal(b)) => a
+                .elements()
+                .len()
+                .cmp(&b.elements().len())
+                .then_with(|| a.head().canonical_cmp(b.head()))
+                .then_with(|| {
+                    a.elements()
+                        .iter()
+                        .zip(b.elements())
+                        .map(|(a, b)| a.canonical_cmp(b))
+                        .find(|ordering| *ordering != Ordering::Equal)
+                        .unwrap_or(Ordering::Equal)
+                }),
+            _ if rank(a) != rank(b) => rank(a).cmp(&rank(b)),
+            // Both expressions are numbers.
+            _ => {
+                let number =
+                    |expr: &Expr| expr.try_as_number().expect("expected a number");
+
+                number(self).cmp(&number(other))
+            },
+        }
+    }
+
+    /// Get the greatest element of this expression, according to
+    /// [`Expr::canonical_cmp()`].
+    ///
+    /// If the elements are all numbers, this is the element with the greatest value,
+    /// like [`Max`](https://reference.wolfram.com/language/ref/Max.html)<sub>WL</sub>.
+    /// Otherwise, non-numeric elements are compared using the canonical order, so any
+    /// string, symbol, or normal expression element is greater than every number.
+    ///
+    /// Returns `None` if `self` is not a [`Normal`](crate::Normal) expression, or has no
+    /// elements. If several elements are greatest, the last of them is returned.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use wolfram_expr::expr;
+    ///
+    /// assert_eq!(expr!(System::List[3, 10.5, -2]).max_element(), Some(&expr!(10.5)));
+    ///
+    /// // Symbols are greater than numbers.
+    /// assert_eq!(expr!(System::List[3, x, 100]).max_element(), Some(&expr!(x)));
+    /// ```
+    pub fn max_element(&self) -> Option<&Expr> {
+        self.try_as_normal()?
+            .elements()
+            .iter()
+            .max_by(|a, b| a.canonical_cmp(b))
+    }
+
+    /// Get the least element of this expression, according to
+    /// [`Expr::canonical_cmp()`].
+    ///
+    /// If the elements are all numbers, this is the element with the least value,
+    /// like [`Min`](https://reference.wolfram.com/language/ref/Min.html)<sub>WL</sub>.
+    /// Otherwise, non-numeric elements are compared using the canonical order, so any
+    /// number element is less than every string, symbol, or normal expression.
+    ///
+    /// Returns `None` if `self` is not a [`Normal`](crate::Normal) expression, or has no
+    /// elements. If several elements are least, the first of them is returned.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use wolfram_expr::expr;
+    ///
+    /// assert_eq!(expr!(System::List[3, 10.5, -2]).min_element(), Some(&expr!(-2)));
+    /// assert_eq!(expr!(System::List[b, "z", a]).min_element(), Some(&expr!("z")));
+    /// ```
+    pub fn min_element(&self) -> Option<&Expr> {
+        self.try_as_normal()?
+            .elements()
+            .iter()
+            .min_by(|a, b| a.canonical_cmp(b))
+    }
+
+    /// Sort `elements` in place using [`wolfram_order()`], like
+    /// [`Sort`](https://reference.wolfram.com/language/ref/Sort.html)<sub>WL</sub>.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use wolfram_expr::{expr, Expr};
+    ///
+    /// let mut elements = vec![expr!(x), expr!(f[a]), expr!("s"), expr!(2), expr!(-1.5)];
+    /// Expr::canonical_sort(&mut elements);
+    ///
+    /// assert_eq!(elements, [expr!(-1.5), expr!(2), expr!("s"), expr!(x), expr!(f[a])]);
+    /// ```
+    pub fn canonical_sort(elements: &mut [Expr]) {
+        elements.sort_by(wolfram_order)
+    }
+
+    /// If this is a [`Normal`] expression, return a copy with its elements sorted in
+    /// canonical order. Otherwise return a clone of `self`.
+    ///
+    /// See [`Normal::sorted()`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use wolfram_expr::expr;
+    ///
+    /// assert_eq!(
+    ///     expr!(System::Plus[x, 2, "s", 1.5]).sort_arguments(),
+    ///     expr!(System::Plus[1.5, 2, "s", x])
+    /// );
+    /// assert_eq!(expr!(x).sort_arguments(), expr!(x));
+    /// ```
+    pub fn sort_arguments(&self) -> Expr {
+        match self.try_as_normal() {
+            Some(normal) => Expr::from(normal.sorted()),
+            None => self.clone(),
+        }
+    }
+}
+
+impl Normal {
+    /// Return a copy of this expression with its elements sorted in canonical order,
+    /// according to [`wolfram_order()`]. The head is left unchanged.
+    ///
+    /// This is useful for canonicalizing the arguments of an orderless function, such
+    /// as [`Plus`](https://reference.wolfram.com/language/ref/Plus.html)<sub>WL</sub>,
+    /// before comparing expressions.
+    pub fn sorted(&self) -> Normal {
+        let mut elements = self.elements().to_vec();
+        Expr::canonical_sort(&mut elements);
+
+        Normal::new(self.head().clone(), elements)
+    }
+}
+
+/// Compare `a` and `b` using an approximation of the Wolfram Language canonical order,
+/// as used by [`Sort`](https://reference.wolfram.com/language/ref/Sort.html)<sub>WL</sub>
+/// and [`Order`](https://reference.wolfram.com/language/ref/Order.html)<sub>WL</sub>.
+///
+/// This is the comparator used by [`Expr::canonical_cmp()`] and
+/// [`Expr::canonical_sort()`]. It implements the following rules, in order:
+///
+/// 1. Numbers come before all other expressions, and are ordered by value. Numbers
+///    with the same value are ordered integers and rationals first, then machine
+///    reals, then arbitrary-precision reals.
+/// 2. Strings come next, ordered alphabetically, ignoring case. Strings that differ
+///    only in case are ordered lowercase first.
+/// 3. Symbols come next, ordered like strings by their symbol name, and then by their
+///    context.
+/// 4. Normal expressions come last. They are ordered by number of elements, then by
+///    head, and then by each element in turn.
+///
+/// Strings come before symbols, as in Wolfram Language, where `Sort[{x, "a"}]` is
+/// `{"a", x}`. Special cases of `Order`, such as ordering `x^2` next to `x`, are not
+/// implemented.
+///
+/// This order is unrelated to the derived [`Ord`] implementation of [`Expr`], which is
+/// only suitable for use in sorted collections such as
+/// [`BTreeSet`](alloc::collections::BTreeSet). It is a total order, which returns
+/// [`Ordering::Equal`] only for equal expressions.
+///
+/// # Example
+///
+/// ```
+/// use std::cmp::Ordering;
+///
+/// use wolfram_expr::{expr, wolfram_order};
+///
+/// assert_eq!(wolfram_order(&expr!(10), &expr!("a")), Ordering::Less);
+/// assert_eq!(wolfram_order(&expr!("a"), &expr!(a)), Ordering::Less);
+/// assert_eq!(wolfram_order(&expr!(b), &expr!(f[a])), Ordering::Less);
+/// ```
+pub fn wolfram_order(a: &Expr, b: &Expr) -> Ordering {
+    a.canonical_cmp(b)
+}
+
+/// The position of the kind of expression `kind` in the canonical order.
+fn rank(kind: &ExprKind) -> u8 {
+    match kind {
+        ExprKind::Integer(_) | ExprKind::Real(_) => 0,
+        #[cfg(feature = "bigint")]
+        ExprKind::BigInteger(_) => 0,
+        #[cfg(feature = "rational")]
+        ExprKind::Rational(_) => 0,
+        #[cfg(feature = "bigreal")]
+        ExprKind::BigReal(_) => 0,
+        ExprKind::String(_) => 1,
+        ExprKind::Symbol(_) => 2,
+        ExprKind::Normal(_) => 3,
+    }
+}
+
+/// Numbers are ordered by value, and then by kind: exact numbers first, then machine
+/// reals, then arbitrary-precision reals.
+///
+/// Integers and rational numbers are compared exactly, but other numbers of different
+/// kinds are compared by their nearest `f64` values, so that e.g. `Integer(1)` is less
+/// than `Real(1.0)`, which is less than `Integer(2)`. This is a total order, which
+/// returns [`Ordering::Equal`] only for equal numbers. It is the order used for numbers
+/// by [`Expr::canonical_cmp()`].
+///
+/// ```
+/// use wolfram_expr::Number;
+///
+/// use Number::Integer;
+///
+/// let mut numbers = vec![Number::real(1.5), Integer(2), Number::real(1.0), Integer(1)];
+/// numbers.sort();
+///
+/// assert_eq!(numbers, [
+///     Integer(1),
+///     Number::real(1.0),
+///     Number::real(1.5),
+///     Integer(2)
+/// ]);
+/// ```
+impl Ord for Number {
+    fn cmp(&self, other: &Number) -> Ordering {
+        match (self, other) {
+            // Compare values of the same kind exactly.
+            (Number::Integer(a), Number::Integer(b)) => a.cmp(b),
+            #[cfg(feature = "bigint")]
+            (Number::BigInteger(a), Number::BigInteger(b)) => a.cmp(b),
+            // An integer in the range of `i64` is normally never a `BigInteger`, but if
+            // it is, order it after the equal `Integer`.
+            #[cfg(feature = "bigint")]
+            (Number::Integer(a), Number::BigInteger(b)) => {
+                crate::BigInt::from(*a).cmp(b).then(Ordering::Less)
+            },
+            #[cfg(feature = "bigint")]
+            (Number::BigInteger(a), Number::Integer(b)) => {
+                a.cmp(&crate::BigInt::from(*b)).then(Ordering::Greater)
+            },
+            // Compare rational numbers exactly with each other and with integers.
+            #[cfg(feature = "rational")]
+            (Number::Rational(_), _) | (_, Number::Rational(_))
+                if exact_rational(self).is_some() && exact_rational(other).is_some() =>
+            {
+                exact_rational(self).cmp(&exact_rational(other))
+            },
+            (Number::Real(a), Number::Real(b)) => a.cmp(b),
+            #[cfg(feature = "bigreal")]
+            (Number::BigReal(a), Number::BigReal(b)) => a
+                .value()
+                .cmp(b.value())
+                .then_with(|| a.precision().total_cmp(&b.precision())),
+            // Neither value is NaN.
+            _ => self
+                .to_f64()
+                .partial_cmp(&other.to_f64())
+                .unwrap_or(Ordering::Equal)
+                .then_with(|| number_rank(self).cmp(&number_rank(other))),
+        }
+    }
+}
+
+impl PartialOrd for Number {
+    fn partial_cmp(&self, other: &Number) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// The position of the kind of `number` among numbers with the same value.
+fn number_rank(number: &Number) -> u8 {
+    match number {
+        Number::Integer(_) => 0,
+        #[cfg(feature = "bigint")]
+        Number::BigInteger(_) => 0,
+        // A rational number is never equal to an integer.
+        #[cfg(feature = "rational")]
+        Number::Rational(_) => 0,
+        Number::Real(_) => 1,
+        #[cfg(feature = "bigreal")]
+        Number::BigReal(_) => 2,
+    }
+}
+
+/// If `number` is an integer or rational number, return its exact value.
+#[cfg(feature = "rational")]
+fn exact_rational(number: &Number) -> Option<crate::BigRational> {
+    let int = match number {
+        Number::Integer(int) => crate::BigInt::from(*int),
+        Number::BigInteger(int) => int.clone(),
+        Number::Rational(rational) => return Some(rational.clone()),
+        _ => return None,
+    };
+
+    Some(crate::BigRational::from_integer(int))
+}
+
+/// Compare strings alphabetically, ignoring case, and then lowercase first.
+fn cmp_names(a: &str, b: &str) -> Ordering {
+    let a_folded = a.chars().flat_map(char::to_lowercase);
+    let b_folded = b.chars().flat_map(char::to_lowercase);
+
+    a_folded.cmp(b_folded).then_with(|| b.cmp(a))
+}
+
+#[test]
+fn test_canonical_cmp() {
+    use crate::expr;
+
+    let mut exprs = vec![
+        expr!(f[a, b]),
+        expr!(x),
+        expr!("B"),
+        expr!(System::x),
+        expr!(g[a]),
+        expr!(2.5),
+        expr!("b"),
+        expr!(f[a]),
+        expr!(3),
+        expr!("a"),
+        expr!(f[]),
+        expr!(1),
+        expr!(A),
+        expr!(1.0),
+        expr!(-7),
+        expr!(a),
+    ];
+    exprs.sort_by(Expr::canonical_cmp);
+
+    assert_eq!(exprs, [
+        expr!(-7),
+        expr!(1),
+        expr!(1.0),
+        expr!(2.5),
+        expr!(3),
+        expr!("a"),
+        expr!("b"),
+        expr!("B"),
+        expr!(a),
+        expr!(A),
+        expr!(x),
+        expr!(System::x),
+        expr!(f[]),
+        expr!(f[a]),
+        expr!(g[a]),
+        expr!(f[a, b]),
+    ]);
+
+    for a in &exprs {
+        for b in &exprs {
+            assert_eq!(a.canonical_cmp(b) == Ordering::Equal, a == b);
+            assert_eq!(a.canonical_cmp(b), b.canonical_cmp(a).reverse());
+        }
+    }
+}
+
+#[test]
+fn test_min_max_element() {
+    use crate::expr;
+
+    let numbers = expr!(System::List[3, -1.5, 10, 2.0]);
+    assert_eq!(numbers.max_element(), Some(&expr!(10)));
+    assert_eq!(numbers.min_element(), Some(&expr!(-1.5)));
+
+    let mixed = expr!(System::List[x, 3, "a", f[1]]);
+    assert_eq!(mixed.max_element(), Some(&expr!(f[1])));
+    assert_eq!(mixed.min_element(), Some(&expr!(3)));
+
+    assert_eq!(expr!(f[]).max_element(), None);
+    assert_eq!(expr!(f[]).min_element(), None);
+    assert_eq!(expr!(x).max_element(), None);
+    assert_eq!(expr!(5).min_element(), None);
+}
+
+#[test]
+fn test_canonical_sort() {
+    use crate::expr;
+
+    fn sort(mut elements: Vec<Expr>) -> Vec<Expr> {
+        Expr::canonical_sort(&mut elements);
+        elements
+    }
+
+    // Sort[{x, "a", 1, f[a]}] == {1, "a", x, f[a]}
+    assert_eq!(sort(vec![expr!(x), expr!("a"), expr!(1), expr!(f[a])]), [
+        expr!(1),
+        expr!("a"),
+        expr!(x),
+        expr!(f[a])
+    ]);
+
+    // Sort[{3, -2.5, 1, 2.}] == {-2.5, 1, 2., 3}
+    assert_eq!(sort(vec![expr!(3), expr!(-2.5), expr!(1), expr!(2.0)]), [
+        expr!(-2.5),
+        expr!(1),
+        expr!(2.0),
+        expr!(3)
+    ]);
+
+    // Sort[{b, B, a, A}] == {a, A, b, B}
+    assert_eq!(sort(vec![expr!(b), expr!(B), expr!(a), expr!(A)]), [
+        expr!(a),
+        expr!(A),
+        expr!(b),
+        expr!(B)
+    ]);
+
+    // Sort[{"b", "B", "a"}] == {"a", "b", "B"}
+    assert_eq!(sort(vec![expr!("b"), expr!("B"), expr!("a")]), [
+        expr!("a"),
+        expr!("b"),
+        expr!("B")
+    ]);
+
+    // Sort[{f[a, b], g[b], f[b], f[a]}] == {f[a], f[b], g[b], f[a, b]}
+    assert_eq!(
+        sort(vec![expr!(f[a, b]), expr!(g[b]), expr!(f[b]), expr!(f[a])]),
+        [expr!(f[a]), expr!(f[b]), expr!(g[b]), expr!(f[a, b])]
+    );
+
+    assert!(sort(vec![]).is_empty());
+}
+
+#[test]
+fn test_sort_arguments() {
+    use crate::expr;
+
+    let plus = expr!(System::Plus[x, 3, "b", 2.5, a, -1, "a", 1.0]);
+    let sorted = expr!(System::Plus[-1, 1.0, 2.5, 3, "a", "b", a, x]);
+
+    assert_eq!(plus.sort_arguments(), sorted);
+    assert_eq!(
+        plus.try_as_normal().unwrap().sorted(),
+        *sorted.try_as_normal().unwrap()
+    );
+    assert_eq!(sorted.sort_arguments(), sorted);
+
+    // Only the elements are sorted, not the head or nested arguments.
+    assert_eq!(
+        expr!(g[z, a][f[y, x], 1]).sort_arguments(),
+        expr!(g[z, a][1, f[y, x]])
+    );
+
+    assert_eq!(expr!(f[]).sort_arguments(), expr!(f[]));
+    assert_eq!(expr!("a").sort_arguments(), expr!("a"));
+}
+
+#[test]
+fn test_wolfram_order() {
+    use crate::expr;
+
+    assert_eq!(wolfram_order(&expr!(-1), &expr!(0.5)), Ordering::Less);
+    assert_eq!(wolfram_order(&expr!("z"), &expr!(a)), Ordering::Less);
+    assert_eq!(wolfram_order(&expr!(z), &expr!(f[])), Ordering::Less);
+    assert_eq!(wolfram_order(&expr!(f[x]), &expr!(f[x])), Ordering::Equal);
+    assert_eq!(wolfram_order(&expr!(f[y]), &expr!(f[x])), Ordering::Greater);
+}
+
+#[cfg(feature = "bigint")]
+#[test]
+fn test_canonical_cmp_big_integer() {
+    use crate::BigInt;
+
+    let big = Expr::big_integer(BigInt::from(i64::MAX) + 1u8);
+    let negative_big = Expr::big_integer(BigInt::from(i64::MIN) - 1u8);
+
+    let mut exprs = vec![
+        big.clone(),
+        Expr::from(i64::MAX),
+        Expr::real(1e30),
+        negative_big.clone(),
+        Expr::from(0),
+    ];
+    exprs.sort_by(Expr::canonical_cmp);
+
+    assert_eq!(exprs, [
+        negative_big,
+        Expr::from(0),
+        Expr::from(i64::MAX),
+        big,
+        Expr::real(1e30),
+    ]);
+}
+
+#[cfg(feature = "rational")]
+#[test]
+fn test_canonical_cmp_rational() {
+    let mut exprs = vec![
+        Expr::from(1),
+        Expr::rational(1, 3),
+        Expr::real(0.5),
+        Expr::rational(-7, 2),
+        Expr::from(-3),
+        Expr::rational(2, 3),
+    ];
+    exprs.sort_by(Expr::canonical_cmp);
+
+    assert_eq!(exprs, [
+        Expr::rational(-7, 2),
+        Expr::from(-3),
+        Expr::rational(1, 3),
+        Expr::real(0.5),
+        Expr::rational(2, 3),
+        Expr::from(1),
+    ]);
+
+    // Close rational numbers are compared exactly.
+    let a = Expr::rational(1, 3);
+    let b = Expr::rational(1_000_000_000_000_000_001i64, 3_000_000_000_000_000_000i64);
+    assert_eq!(a.canonical_cmp(&b), Ordering::Less);
+}
+
+#[test]
+fn test_number_ord() {
+    use Number::Integer;
+
+    let real = Number::real;
+
+    let mut numbers = vec![
+        real(2.5),
+        Integer(3),
+        real(-1.0),
+        Integer(-1),
+        real(f64::INFINITY),
+        Integer(2),
+        real(2.0),
+        real(f64::NEG_INFINITY),
+        Integer(i64::MIN),
+        real(0.0),
+    ];
+    numbers.sort();
+
+    assert_eq!(numbers, [
+        real(f64::NEG_INFINITY),
+        Integer(i64::MIN),
+        Integer(-1),
+        real(-1.0),
+        real(0.0),
+        Integer(2),
+        real(2.0),
+        real(2.5),
+        Integer(3),
+        real(f64::INFINITY),
+    ]);
+
+    // The order is consistent with `Eq`.
+    for a in &numbers {
+        for b in &numbers {
+            assert_eq!(a.cmp(b) == Ordering::Equal, a == b, "{:?} {:?}", a, b);
+            assert_eq!(a.cmp(b), b.cmp(a).reverse(), "{:?} {:?}", a, b);
+            assert_eq!(a.partial_cmp(b), Some(a.cmp(b)));
+        }
+    }
+
+    assert!(Integer(1) < real(1.5));
+    assert!(real(1.5) < Integer(2));
+
+    #[cfg(feature = "rational")]
+    {
+        let mut numbers = vec![Number::rational(1, 2), Integer(0), real(0.5), Integer(1)];
+        numbers.sort();
+
+        assert_eq!(numbers, [
+            Integer(0),
+            Number::rational(1, 2),
+            real(0.5),
+            Integer(1)
+        ]);
+    }
+}
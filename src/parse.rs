@@ -0,0 +1,735 @@
+//! Parsing of Wolfram Language expressions.
+//!
+//! This module requires the `unstable_parse` crate feature. The syntax it supports may
+//! change in future versions of this crate.
+//!
+//! # Syntax
+//!
+//! [`parse_expr()`] parses expressions written in
+//! [`FullForm`](https://reference.wolfram.com/language/ref/FullForm.html)<sub>WL</sub>,
+//! such as those produced by the [`Display`](core::fmt::Display) implementation of
+//! [`Expr`]:
+//!
+//! Syntax                             | Expression
+//! -----------------------------------|--------------------------------------------------
+//! `5`, `-12`                         | Integer
+//! `1.5`, `-2.`, `1.5*^-10`          | Real
+//! `"text"`                           | String, with `\"`, `\\`, `\n`, `\t`, `\r`, `\0`, and `\u{...}` escapes
+//! `` System`Plus ``, `x`, `Plus`     | Symbol; see below for symbols without a context
+//! `f[x, y]`, `f[x][y]`               | Normal expression
+//! `{x, y}`                           | `` System`List[x, y] ``
+//!
+//! A symbol without a context, such as `Plus` or `x`, is resolved like an identifier in
+//! the [`expr!`](crate::expr!) macro: it is in the `` System` `` context if it is one of
+//! the [`SYSTEM_SYMBOLS`], and in the `` Global` `` context otherwise. A symbol with a
+//! relative context, such as `` `Plus ``, is in the `` Global` `` context.
+//!
+//! With the `bigreal` crate feature, a real number followed by a precision mark, such as
+//! `` 1.5`30 ``, is parsed as a `BigReal`. A real number followed by
+//! an empty precision mark, such as `` 1.5` ``, is a machine real.
+//!
+//! With the `bigint` crate feature, an integer outside the range of `i64` is parsed as
+//! an `ExprKind::BigInteger`. Without it, such an integer is an error.
+//!
+//! With the `rational` crate feature, `` System`Rational[n, d] `` with integer arguments
+//! and a non-zero denominator is parsed as an `ExprKind::Rational`, reduced to lowest
+//! terms.
+//!
+//! Whitespace is allowed between tokens. Operators such as `+` are not supported.
+//!
+//! Parsing the [`Display`](core::fmt::Display) form of an expression returns an equal
+//! expression, except for expressions that contain infinite machine reals, which have
+//! no FullForm syntax.
+
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::{fmt, str::FromStr};
+
+use crate::{macros::SYSTEM_SYMBOLS, Expr, ExprKind, Normal, Symbol};
+
+/// Maximum nesting depth of brackets and braces accepted by [`parse_expr()`].
+const MAX_DEPTH: usize = 256;
+
+/// Error returned by [`parse_expr()`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    offset: usize,
+    kind: ParseErrorKind,
+}
+
+/// The kind of a [`ParseError`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ParseErrorKind {
+    /// The input ended before the expression was complete.
+    UnexpectedEnd,
+    /// The input contains a character that is not valid at its position.
+    UnexpectedChar(char),
+    /// The input contains a number that is malformed or out of range.
+    InvalidNumber(String),
+    /// The input contains a malformed symbol.
+    InvalidSymbol(String),
+    /// The input contains a string with a malformed escape sequence.
+    InvalidEscape,
+    /// The input nests brackets and braces more than 256 levels deep.
+    TooDeep,
+}
+
+/// Parse `input` as a Wolfram Language expression.
+///
+/// See the [module documentation](self#syntax) for the supported syntax.
+///
+/// # Example
+///
+/// ```
+/// use wolfram_expr::{expr, parse::parse_expr};
+///
+/// assert_eq!(
+///     parse_expr(r#"f[1, {2.5, "a"}, System`x]"#),
+///     Ok(expr!(f[1, System::List[2.5, "a"], System::x]))
+/// );
+/// assert!(parse_expr("f[1,").is_err());
+/// ```
+pub fn parse_expr(input: &str) -> Result<Expr, ParseError> {
+    let mut parser = Parser {
+        input,
+        offset: 0,
+        depth: 0,
+    };
+
+    let expr = parser.expr()?;
+
+    parser.skip_whitespace();
+    match parser.peek() {
+        None => Ok(expr),
+        Some(char) => Err(parser.error(ParseErrorKind::UnexpectedChar(char))),
+    }
+}
+
+/// Parses `input` using [`parse_expr()`].
+///
+/// This implementation requires the `unstable_parse` crate feature.
+///
+/// # Example
+///
+/// ```
+/// use wolfram_expr::{expr, Expr};
+///
+/// assert_eq!("f[1, 2]".parse::<Expr>(), Ok(expr!(f[1, 2])));
+/// ```
+impl FromStr for Expr {
+    type Err = ParseError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        parse_expr(input)
+    }
+}
+
+//======================================
+// Parser
+//======================================
+
+struct Parser<'i> {
+    input: &'i str,
+    /// Byte offset of the next character to parse.
+    offset: usize,
+    /// Number of enclosing brackets and braces.
+    depth: usize,
+}
+
+impl<'i> Parser<'i> {
+    fn expr(&mut self) -> Result<Expr, ParseError> {
+        self.skip_whitespace();
+
+        let mut expr = match self.peek() {
+            None => return Err(self.error(ParseErrorKind::UnexpectedEnd)),
+            Some('"') => self.string()?,
+            Some('{') => {
+                self.next();
+                let elements = self.sequence('}')?;
+
+                Expr::list(elements)
+            },
+            Some(char) if char == '-' || char.is_ascii_digit() => self.number()?,
+            Some(char) if char == '`' || char == '$' || char.is_alphabetic() => {
+                self.symbol()?
+            },
+            Some(char) => return Err(self.error(ParseErrorKind::UnexpectedChar(char))),
+        };
+
+        loop {
+            self.skip_whitespace();
+
+            if self.peek() != Some('[') {
+                return Ok(expr);
+            }
+
+            self.next();
+            let elements = self.sequence(']')?;
+
+            let normal = Normal::new(expr, elements);
+
+            #[cfg(feature = "rational")]
+            if let Some(rational) = crate::rational::rational_from_normal(&normal) {
+                expr = rational;
+                continue;
+            }
+
+            expr = Expr::from(normal);
+        }
+    }
+
+    /// Parse a comma-separated sequence of expressions, followed by `close`.
+    fn sequence(&mut self, close: char) -> Result<Vec<Expr>, ParseError> {
+        if self.depth == MAX_DEPTH {
+            return Err(self.error(ParseErrorKind::TooDeep));
+        }
+
+        self.depth += 1;
+        let elements = self.elements(close);
+        self.depth -= 1;
+
+        elements
+    }
+
+    fn elements(&mut self, close: char) -> Result<Vec<Expr>, ParseError> {
+        let mut elements = Vec::new();
+
+        self.skip_whitespace();
+        if self.peek() == Some(close) {
+            self.next();
+            return Ok(elements);
+        }
+
+        loop {
+            elements.push(self.expr()?);
+
+            self.skip_whitespace();
+            match self.peek() {
+                Some(',') => (),
+                Some(char) if char == close => {
+                    self.next();
+                    return Ok(elements);
+                },
+                Some(char) => {
+                    return Err(self.error(ParseErrorKind::UnexpectedChar(char)))
+                },
+                None => return Err(self.error(ParseErrorKind::UnexpectedEnd)),
+            }
+            self.next();
+        }
+    }
+
+    fn number(&mut self) -> Result<Expr, ParseError> {
+        let start = self.offset;
+
+        self.eat('-');
+        let has_digits = self.digits();
+        let has_point = self.eat('.');
+        self.digits();
+
+        if !has_digits {
+            return Err(self.number_error(start));
+        }
+
+        let has_exponent = has_point && self.eat_str("*^");
+
+        if has_exponent {
+            if !self.eat('-') {
+                self.eat('+');
+            }
+
+            if !self.digits() {
+                return Err(self.number_error(start));
+            }
+        }
+
+        let number = &self.input[start..self.offset];
+
+        if self.eat('`') {
+            return self.precision(start);
+        }
+
+        if !has_point && !has_exponent {
+            return match i64::from_str(number) {
+                Ok(int) => Ok(Expr::from(int)),
+                #[cfg(feature = "bigint")]
+                Err(_) => crate::big_integer::parse_big_integer(number)
+                    .map(Expr::big_integer)
+                    .ok_or_else(|| self.number_error(start)),
+                #[cfg(not(feature = "bigint"))]
+                Err(_) => Err(self.number_error(start)),
+            };
+        }
+
+        real(number).ok_or_else(|| self.number_error(start))
+    }
+
+    /// Parse the precision following the precision mark of the number beginning at
+    /// `start`.
+    fn precision(&mut self, start: usize) -> Result<Expr, ParseError> {
+        let mark = self.offset - 1;
+
+        self.digits();
+        self.eat('.');
+        self.digits();
+
+        // A number with an empty precision mark is a machine real.
+        if self.offset == mark + 1 {
+            return real(&self.input[start..mark])
+                .ok_or_else(|| self.number_error(start));
+        }
+
+        if self.eat_str("*^") {
+            self.eat('-');
+            self.digits();
+        }
+
+        #[cfg(feature = "bigreal")]
+        if let Some(real) = crate::BigReal::parse(&self.input[start..self.offset]) {
+            return Ok(Expr::big_real(real));
+        }
+
+        Err(self.number_error(start))
+    }
+
+    fn string(&mut self) -> Result<Expr, ParseError> {
+        // Skip the opening '"'.
+        self.next();
+
+        let mut string = String::new();
+
+        loop {
+            let char = match self.next() {
+                Some('"') => return Ok(Expr::string(string)),
+                Some('\\') => {
+                    let escape = self.offset - 1;
+
+                    self.escape().ok_or_else(|| {
+                        ParseError::new(escape, ParseErrorKind::InvalidEscape)
+                    })?
+                },
+                Some(char) => char,
+                None => return Err(self.error(ParseErrorKind::UnexpectedEnd)),
+            };
+
+            string.push(char);
+        }
+    }
+
+    /// Parse the remainder of an escape sequence, after the `\`.
+    fn escape(&mut self) -> Option<char> {
+        let char = match self.next()? {
+            '"' => '"',
+            '\\' => '\\',
+            '\'' => '\'',
+            'n' => '\n',
+            't' => '\t',
+            'r' => '\r',
+            '0' => '\0',
+            'u' => {
+                if !self.eat('{') {
+                    return None;
+                }
+
+                let start = self.offset;
+                while self.peek()?.is_ascii_hexdigit() {
+                    self.next();
+                }
+                let hex = &self.input[start..self.offset];
+
+                if !self.eat('}') {
+                    return None;
+                }
+
+                char::from_u32(u32::from_str_radix(hex, 16).ok()?)?
+            },
+            _ => return None,
+        };
+
+        Some(char)
+    }
+
+    fn symbol(&mut self) -> Result<Expr, ParseError> {
+        let start = self.offset;
+
+        while let Some(char) = self.peek() {
+            if char == '`' || char == '$' || char.is_alphanumeric() {
+                self.next();
+            } else {
+                break;
+            }
+        }
+
+        let name = &self.input[start..self.offset];
+
+        if let Some(symbol) = Symbol::try_new(name) {
+            return Ok(Expr::symbol(symbol));
+        }
+
+        // Resolve symbols without a context the same way as the `expr!` macro, and
+        // symbols with a relative context in `Global``.
+        let (context, relative) = match name.strip_prefix('`') {
+            Some(relative) => ("Global`", relative),
+            None => match SYSTEM_SYMBOLS.binary_search(&name) {
+                Ok(_) => ("System`", name),
+                Err(_) => ("Global`", name),
+            },
+        };
+
+        match Symbol::try_new(&[context, relative].concat()) {
+            Some(symbol) => Ok(Expr::symbol(symbol)),
+            None => Err(ParseError::new(
+                start,
+                ParseErrorKind::InvalidSymbol(name.to_string()),
+            )),
+        }
+    }
+
+    //==================================
+    // Characters
+    //==================================
+
+    fn peek(&self) -> Option<char> {
+        self.input[self.offset..].chars().next()
+    }
+
+    fn next(&mut self) -> Option<char> {
+        let char = self.peek()?;
+        self.offset += char.len_utf8();
+        Some(char)
+    }
+
+    /// Consume the next character if it is `expected`.
+    fn eat(&mut self, expected: char) -> bool {
+        self.eat_str(expected.encode_utf8(&mut [0; 4]))
+    }
+
+    /// Consume `expected` if the remaining input starts with it.
+    fn eat_str(&mut self, expected: &str) -> bool {
+        let matches = self.input[self.offset..].starts_with(expected);
+        if matches {
+            self.offset += expected.len();
+        }
+        matches
+    }
+
+    /// Consume a sequence of ASCII digits, returning `true` if it is not empty.
+    fn digits(&mut self) -> bool {
+        let start = self.offset;
+        while matches!(self.peek(), Some(char) if char.is_ascii_digit()) {
+            self.next();
+        }
+        self.offset != start
+    }
+
+    fn skip_whitespace(&mut self) {
+        while matches!(self.peek(), Some(char) if char.is_whitespace()) {
+            self.next();
+        }
+    }
+
+    fn error(&self, kind: ParseErrorKind) -> ParseError {
+        ParseError::new(self.offset, kind)
+    }
+
+    fn number_error(&self, start: usize) -> ParseError {
+        let number = self.input[start..self.offset].to_string();
+
+        ParseError::new(start, ParseErrorKind::InvalidNumber(number))
+    }
+}
+
+/// Parse a machine real number, which may use a `*^` exponent.
+fn real(number: &str) -> Option<Expr> {
+    let real = f64::from_str(&number.replace("*^", "e")).ok()?;
+
+    ordered_float::NotNan::new(real)
+        .ok()
+        .map(|real| Expr::new(ExprKind::Real(real)))
+}
+
+//======================================
+// Error impls
+//======================================
+
+impl ParseError {
+    fn new(offset: usize, kind: ParseErrorKind) -> Self {
+        ParseError { offset, kind }
+    }
+
+    /// The byte offset in the input at which the error occurred.
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// The kind of this error.
+    pub fn kind(&self) -> &ParseErrorKind {
+        &self.kind
+    }
+}
+
+/// Formats as e.g. `unexpected character ']' at offset 4`.
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match &self.kind {
+            ParseErrorKind::UnexpectedEnd => write!(f, "unexpected end of input")?,
+            ParseErrorKind::UnexpectedChar(char) => {
+                write!(f, "unexpected character {:?}", char)?
+            },
+            ParseErrorKind::InvalidNumber(number) => {
+                write!(f, "invalid number {:?}", number)?
+            },
+            ParseErrorKind::InvalidSymbol(name) => {
+                write!(f, "invalid symbol {:?}", name)?
+            },
+            ParseErrorKind::InvalidEscape => write!(f, "invalid escape sequence")?,
+            ParseErrorKind::TooDeep => write!(f, "expression nested too deeply")?,
+        }
+
+        write!(f, " at offset {}", self.offset)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ParseError {}
+
+#[test]
+fn test_parse_expr() {
+    use crate::expr;
+
+    assert_eq!(parse_expr("5"), Ok(expr!(5)));
+    assert_eq!(parse_expr(" -12 "), Ok(expr!(-12)));
+    assert_eq!(parse_expr("1.5"), Ok(expr!(1.5)));
+    assert_eq!(parse_expr("-2."), Ok(expr!(-2.0)));
+    assert_eq!(parse_expr("1.5*^-3"), Ok(expr!(0.0015)));
+    assert_eq!(parse_expr("1.*^-15"), Ok(expr!(1e-15)));
+    assert_eq!(parse_expr("1.5`"), Ok(expr!(1.5)));
+    assert_eq!(parse_expr(r#""a\"\n\u{e9}""#), Ok(expr!("a\"\né")));
+    assert_eq!(parse_expr("System`Plus"), Ok(expr!(System::Plus)));
+    assert_eq!(parse_expr("x"), Ok(expr!(x)));
+    assert_eq!(parse_expr("`x"), Ok(expr!(x)));
+    assert_eq!(parse_expr("Plus"), Ok(expr!(System::Plus)));
+    assert_eq!(parse_expr("`Plus"), Ok(expr!(Global::Plus)));
+    assert_eq!(
+        parse_expr("Plus[1, Times[2, x]]"),
+        Ok(expr!(Plus[1, Times[2, x]]))
+    );
+    assert_eq!(
+        parse_expr("$x1"),
+        Ok(Expr::symbol(Symbol::new("Global`$x1")))
+    );
+    assert_eq!(parse_expr("f[]"), Ok(expr!(f[])));
+    assert_eq!(parse_expr("f [ 1 , g[x] ] [y]"), Ok(expr!(f[1, g[x]][y])));
+    assert_eq!(
+        parse_expr("{1, {}, {x}}"),
+        Ok(expr!(System::List[1, System::List[], System::List[x]]))
+    );
+}
+
+#[test]
+fn test_parse_expr_round_trip() {
+    use crate::expr;
+
+    let expr =
+        expr!(System::Plus[1, -2.5, "\"a\"\t\u{1}", System::Times[x, Global::y]][]);
+
+    assert_eq!(parse_expr(&expr.to_string()), Ok(expr));
+
+    for expr in [
+        Expr::real(2.0),
+        Expr::real(-0.0),
+        Expr::real(1e300),
+        Expr::real(-1e-300),
+        Expr::real(0.1 + 0.2),
+        Expr::from(i64::MIN),
+        Expr::string("\u{7f}\u{2028}\r\0"),
+        Expr::symbol(Symbol::new("A`B`$c1")),
+        Expr::normal(Expr::from(1), vec![Expr::real(2.0), Expr::string("h")]),
+    ] {
+        assert_eq!(parse_expr(&expr.to_string()), Ok(expr));
+    }
+}
+
+#[test]
+fn test_parse_expr_real_round_trip() {
+    // Random bit patterns, to cover the full range of `f64` values.
+    let mut rng = crate::tests::Rng(0x9E37_79B9_7F4A_7C15);
+
+    for _ in 0..100_000 {
+        let real = f64::from_bits(rng.next_u64());
+        if !real.is_finite() {
+            continue;
+        }
+
+        let string = Expr::real(real).to_string();
+        let parsed = match parse_expr(&string).unwrap().kind() {
+            ExprKind::Real(parsed) => parsed.to_bits(),
+            kind => panic!("{} parsed as {:?}", string, kind),
+        };
+
+        assert_eq!(parsed, real.to_bits(), "{}", string);
+    }
+}
+
+#[test]
+fn test_parse_expr_errors() {
+    let error = |input: &str| parse_expr(input).unwrap_err().to_string();
+
+    assert_eq!(error(""), "unexpected end of input at offset 0");
+    assert_eq!(error("f[1,"), "unexpected end of input at offset 4");
+    assert_eq!(error("f[1 2]"), "unexpected character '2' at offset 4");
+    assert_eq!(error("f[1]]"), "unexpected character ']' at offset 4");
+    assert_eq!(error("{1, ]"), "unexpected character ']' at offset 4");
+    assert_eq!(error("1 + 2"), "unexpected character '+' at offset 2");
+    assert_eq!(error("-x"), "invalid number \"-\" at offset 0");
+    assert_eq!(error("1e-15"), "unexpected character 'e' at offset 1");
+    #[cfg(not(feature = "bigint"))]
+    assert_eq!(
+        error("99999999999999999999"),
+        "invalid number \"99999999999999999999\" at offset 0"
+    );
+    assert_eq!(error("x``y"), "invalid symbol \"x``y\" at offset 0");
+    assert_eq!(error(r#""a\q""#), "invalid escape sequence at offset 2");
+    assert_eq!(error(r#""abc"#), "unexpected end of input at offset 4");
+
+    #[cfg(not(feature = "bigreal"))]
+    assert_eq!(error("1.5`30"), "invalid number \"1.5`30\" at offset 0");
+}
+
+#[test]
+fn test_parse_expr_deep_nesting() {
+    let nested = |depth: usize| "{".repeat(depth) + &"}".repeat(depth);
+
+    assert!(parse_expr(&nested(MAX_DEPTH)).is_ok());
+    assert_eq!(
+        parse_expr(&nested(MAX_DEPTH + 1)).unwrap_err(),
+        ParseError::new(MAX_DEPTH + 1, ParseErrorKind::TooDeep)
+    );
+    assert_eq!(
+        parse_expr(&"{".repeat(1_000_000)).unwrap_err().kind(),
+        &ParseErrorKind::TooDeep
+    );
+}
+
+#[cfg(feature = "bigreal")]
+#[test]
+fn test_parse_expr_big_real() {
+    let expr = Expr::big_real(crate::BigReal::parse("-1.25`40*^-100").unwrap());
+
+    assert_eq!(parse_expr("-1.25`40*^-100"), Ok(expr.clone()));
+    assert_eq!(parse_expr(&expr.to_string()), Ok(expr));
+}
+
+#[cfg(feature = "bigint")]
+#[test]
+fn test_parse_expr_big_integer() {
+    use crate::BigInt;
+
+    let expr = Expr::big_integer(BigInt::from(10u8).pow(30));
+    assert_eq!(
+        parse_expr("1000000000000000000000000000000"),
+        Ok(expr.clone())
+    );
+    assert_eq!(parse_expr(&expr.to_string()), Ok(expr));
+
+    let expr = Expr::big_integer(BigInt::from(i64::MIN) - 1u8);
+    assert_eq!(
+        parse_expr("f[-9223372036854775809]")
+            .unwrap()
+            .normal_part(0),
+        Some(&expr)
+    );
+
+    assert_eq!(parse_expr("-9223372036854775808"), Ok(Expr::from(i64::MIN)));
+}
+
+#[cfg(feature = "rational")]
+#[test]
+fn test_parse_expr_rational() {
+    let expr = Expr::rational(-2, 3);
+    assert_eq!(parse_expr("System`Rational[-2, 3]"), Ok(expr.clone()));
+    assert_eq!(parse_expr(&expr.to_string()), Ok(expr));
+
+    assert_eq!(parse_expr("System`Rational[4, 2]"), Ok(Expr::from(2)));
+
+    // Not a valid rational number.
+    assert_eq!(
+        parse_expr("System`Rational[1, 0]").unwrap().to_string(),
+        "System`Rational[1, 0]"
+    );
+    assert_eq!(
+        parse_expr("System`Rational[1, 2][x]").unwrap().to_string(),
+        "System`Rational[1, 2][Global`x]"
+    );
+}
+
+/// Check that `parse_expr(&expr.to_string()) == expr` for many pseudo-random
+/// expressions.
+#[test]
+fn test_parse_expr_round_trip_random() {
+    use crate::tests::Rng;
+
+    fn name(rng: &mut Rng) -> String {
+        const FIRST: &[char] = &['a', 'x', 'Z', '$', 'é', 'λ'];
+        const REST: &[char] = &['b', 'Y', '0', '9', '$', 'ß'];
+
+        let mut name = rng.pick(FIRST).to_string();
+        for _ in 0..rng.below(4) {
+            name.push(rng.pick(REST));
+        }
+        name
+    }
+
+    fn random_expr(rng: &mut Rng, depth: usize) -> Expr {
+        const CHARS: &[char] = &[
+            'a', ' ', '"', '\\', '\n', '\t', '\r', '\0', '\u{1}', '\u{7f}', 'é',
+            '\u{2028}', '😀', '[', ',', '`',
+        ];
+
+        match rng.below(if depth == 0 { 5 } else { 7 }) {
+            0 => Expr::from(rng.next_u64() as i64 >> rng.below(64)),
+            1 => {
+                let real = f64::from_bits(rng.next_u64());
+                if real.is_finite() {
+                    Expr::real(real)
+                } else {
+                    Expr::real(rng.below(1000) as f64)
+                }
+            },
+            2 => Expr::string(
+                (0..rng.below(6))
+                    .map(|_| rng.pick(CHARS))
+                    .collect::<String>(),
+            ),
+            3 | 4 => {
+                let mut symbol = String::new();
+                for _ in 0..=rng.below(3) {
+                    symbol.push_str(&name(rng));
+                    symbol.push('`');
+                }
+                symbol.push_str(&name(rng));
+                Expr::symbol(Symbol::new(&symbol))
+            },
+            _ => {
+                let head = random_expr(rng, depth - 1);
+                let elements = (0..rng.below(4))
+                    .map(|_| random_expr(rng, depth - 1))
+                    .collect();
+                Expr::normal(head, elements)
+            },
+        }
+    }
+
+    let mut rng = Rng(0x2545_F491_4F6C_DD1D);
+
+    for _ in 0..2000 {
+        let expr = random_expr(&mut rng, 4);
+        let string = expr.to_string();
+
+        assert_eq!(parse_expr(&string), Ok(expr), "{}", string);
+        assert_eq!(string.parse::<Expr>().unwrap().to_string(), string);
+    }
+}
@@ -0,0 +1,184 @@
+//! Structural pattern matching.
+//!
+//! This module requires the `std` crate feature.
+//!
+//! [`match_expr()`] matches an expression against a pattern containing the following
+//! pattern objects, without evaluating either expression:
+//!
+//! Pattern                    | Short form | Matches
+//! ---------------------------|------------|------------------------------------------
+//! `` System`Blank[] ``       | `_`        | Any expression
+//! `` System`Blank[h] ``      | `_h`       | Any expression with head `h`
+//! `` System`Pattern[x, p] `` | `x:p`      | Any expression matching `p`, binding it to `x`
+//!
+//! The head of an atomic expression is the symbol naming its type, e.g.
+//! `` System`Integer `` or `` System`String ``, as returned by
+//! [`Head`](https://reference.wolfram.com/language/ref/Head.html)<sub>WL</sub>.
+//!
+//! Any other pattern matches only a structurally equal expression: an atomic pattern
+//! matches an equal atom, and a normal pattern matches a normal expression with the same
+//! number of elements, whose head and elements match the pattern's head and elements.
+//!
+//! A pattern that binds the same name more than once, such as `f[x_, x_]`, matches only
+//! if each occurrence matches an equal expression.
+
+use std::collections::{hash_map::Entry, HashMap};
+
+use crate::{Expr, ExprKind, Symbol};
+
+
+/// Match `subject` against `pattern`.
+///
+/// Returns the expressions bound to each named pattern, or `None` if `subject` does not
+/// match `pattern`. See the [module documentation](self) for the supported patterns.
+///
+/// # Example
+///
+/// Match `f[1, g[2]]` against the pattern `f[x_, g[y_]]`:
+///
+/// ```
+/// use wolfram_expr::{expr, pattern::match_expr, Symbol};
+///
+/// let pattern = expr!(f[Pattern[x, Blank[]], g[Pattern[y, Blank[]]]]);
+///
+/// let bindings = match_expr(&pattern, &expr!(f[1, g[2]])).unwrap();
+///
+/// assert_eq!(bindings[&Symbol::new("Global`x")], expr!(1));
+/// assert_eq!(bindings[&Symbol::new("Global`y")], expr!(2));
+///
+/// assert_eq!(match_expr(&pattern, &expr!(f[1, h[2]])), None);
+/// ```
+pub fn match_expr(pattern: &Expr, subject: &Expr) -> Option<HashMap<Symbol, Expr>> {
+    let mut bindings = HashMap::new();
+
+    if matches(pattern, subject, &mut bindings) {
+        Some(bindings)
+    } else {
+        None
+    }
+}
+
+/// Returns `true` if `subject` matches `pattern`, adding any named patterns to
+/// `bindings`.
+fn matches(pattern: &Expr, subject: &Expr, bindings: &mut HashMap<Symbol, Expr>) -> bool {
+    let ExprKind::Normal(normal) = pattern.kind() else {
+        return pattern == subject;
+    };
+
+    let system = |name: &str| pattern.has_head_str(name);
+
+    match normal.elements() {
+        [] if system("System`Blank") => return true,
+        [head] if system("System`Blank") => return head == &head_of(subject),
+        [name, pattern] if system("System`Pattern") => {
+            if let Some(name) = name.try_as_symbol() {
+                if !matches(pattern, subject, bindings) {
+                    return false;
+                }
+
+                return match bindings.entry(name.clone()) {
+                    Entry::Occupied(entry) => entry.get() == subject,
+                    Entry::Vacant(entry) => {
+                        entry.insert(subject.clone());
+                        true
+                    },
+                };
+            }
+        },
+        _ => (),
+    }
+
+    let Some(subject) = subject.try_as_normal() else {
+        return false;
+    };
+
+    normal.elements().len() == subject.elements().len()
+        && matches(normal.head(), subject.head(), bindings)
+        && normal
+            .elements()
+            .iter()
+            .zip(subject.elements())
+            .all(|(pattern, subject)| matches(pattern, subject, bindings))
+}
+
+/// The head of `expr`, like `Head[expr]`.
+fn head_of(expr: &Expr) -> Expr {
+    let name = match expr.kind() {
+        ExprKind::Normal(normal) => return normal.head().clone(),
+        ExprKind::Integer(_) => "System`Integer",
+        #[cfg(feature = "bigint")]
+        ExprKind::BigInteger(_) => "System`Integer",
+        #[cfg(feature = "rational")]
+        ExprKind::Rational(_) => "System`Rational",
+        ExprKind::Real(_) => "System`Real",
+        #[cfg(feature = "bigreal")]
+        ExprKind::BigReal(_) => "System`Real",
+        ExprKind::String(_) => "System`String",
+        ExprKind::Symbol(_) => "System`Symbol",
+    };
+
+    Expr::symbol(Symbol::new(name))
+}
+
+#[test]
+fn test_match_expr() {
+    use crate::expr;
+
+    let x = || Symbol::new("Global`x");
+
+    // Blank[] matches anything.
+    for subject in [expr!(1), expr!("a"), expr!(x), expr!(f[g[1]][2])] {
+        assert_eq!(match_expr(&expr!(Blank[]), &subject), Some(HashMap::new()));
+    }
+
+    // Literal patterns match structurally.
+    assert!(match_expr(&expr!(f[1, x]), &expr!(f[1, x])).is_some());
+    assert!(match_expr(&expr!(f[1, x]), &expr!(f[1, y])).is_none());
+    assert!(match_expr(&expr!(f[1, x]), &expr!(f[1])).is_none());
+    assert!(match_expr(&expr!(f[1, x]), &expr!(g[1, x])).is_none());
+    assert!(match_expr(&expr!(f[1]), &expr!(1)).is_none());
+
+    // Named patterns bind the matched subexpression.
+    let bindings = match_expr(&expr!(f[Pattern[x, Blank[]], 2]), &expr!(f[g[1], 2]));
+    assert_eq!(bindings, Some(HashMap::from([(x(), expr!(g[1]))])));
+
+    // Patterns can appear in heads.
+    let bindings = match_expr(&expr!(Pattern[x, Blank[]][1]), &expr!(f[a][1]));
+    assert_eq!(bindings, Some(HashMap::from([(x(), expr!(f[a]))])));
+
+    // Named patterns can contain other patterns.
+    let pattern = expr!(Pattern[x, f[Pattern[y, Blank[]]]]);
+    let bindings = match_expr(&pattern, &expr!(f[1])).unwrap();
+    assert_eq!(bindings[&x()], expr!(f[1]));
+    assert_eq!(bindings[&Symbol::new("Global`y")], expr!(1));
+    assert_eq!(match_expr(&pattern, &expr!(g[1])), None);
+}
+
+#[test]
+fn test_match_expr_repeated_names() {
+    use crate::expr;
+
+    let pattern = expr!(f[Pattern[x, Blank[]], g[Pattern[x, Blank[]]]]);
+
+    assert_eq!(
+        match_expr(&pattern, &expr!(f[h[1], g[h[1]]])),
+        Some(HashMap::from([(Symbol::new("Global`x"), expr!(h[1]))]))
+    );
+    assert_eq!(match_expr(&pattern, &expr!(f[h[1], g[h[2]]])), None);
+    assert_eq!(match_expr(&pattern, &expr!(f[1, g[1.0]])), None);
+}
+
+#[test]
+fn test_match_expr_blank_head() {
+    use crate::expr;
+
+    assert!(match_expr(&expr!(Blank[f]), &expr!(f[1, 2])).is_some());
+    assert!(match_expr(&expr!(Blank[f]), &expr!(g[1, 2])).is_none());
+    assert!(match_expr(&expr!(Blank[f]), &expr!(f)).is_none());
+
+    assert!(match_expr(&expr!(Blank[System::Integer]), &expr!(5)).is_some());
+    assert!(match_expr(&expr!(Blank[System::Integer]), &expr!(5.0)).is_none());
+    assert!(match_expr(&expr!(Blank[System::Real]), &expr!(5.0)).is_some());
+    assert!(match_expr(&expr!(Blank[System::String]), &expr!("a")).is_some());
+    assert!(match_expr(&expr!(Blank[System::Symbol]), &expr!(a)).is_some());
+}
@@ -1,7 +1,5 @@
-use std::{
-    hash::{Hash, Hasher},
-    sync::Arc,
-};
+use alloc::sync::Arc;
+use core::hash::{Hash, Hasher};
 
 use crate::Expr;
 
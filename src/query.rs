@@ -0,0 +1,321 @@
+//! Queries about the subexpressions that occur in an expression.
+
+use alloc::{collections::BTreeSet, vec, vec::Vec};
+
+use crate::{Expr, ExprKind, Symbol};
+
+
+impl Expr {
+    /// Returns `true` if `sym` does not occur anywhere in this expression, including
+    /// in heads.
+    ///
+    /// This is equivalent to
+    /// [`FreeQ`](https://reference.wolfram.com/language/ref/FreeQ.html)<sub>WL</sub>
+    /// with a symbol as the pattern. The traversal stops at the first occurrence of
+    /// `sym`, and will not overflow the call stack on deeply nested expressions.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use wolfram_expr::{expr, Symbol};
+    ///
+    /// let expr = expr!(f[1, g[x]]);
+    ///
+    /// assert!(!expr.is_free_of_symbol(&Symbol::new("Global`x")));
+    /// assert!(!expr.is_free_of_symbol(&Symbol::new("Global`g")));
+    /// assert!(expr.is_free_of_symbol(&Symbol::new("Global`y")));
+    /// ```
+    pub fn is_free_of_symbol(&self, sym: &Symbol) -> bool {
+        !self.iter_subexpressions().any(|expr| is_symbol(expr, sym))
+    }
+
+    /// Count the number of times `sym` occurs in this expression, including in heads.
+    ///
+    /// Like [`Expr::count()`], this will not overflow the call stack on deeply nested
+    /// expressions.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use wolfram_expr::{expr, Symbol};
+    ///
+    /// let expr = expr!(f[x, g[x], y]);
+    ///
+    /// assert_eq!(expr.count_symbol(&Symbol::new("Global`x")), 2);
+    /// assert_eq!(expr.count_symbol(&Symbol::new("Global`f")), 1);
+    /// ```
+    pub fn count_symbol(&self, sym: &Symbol) -> usize {
+        self.count(|expr| is_symbol(expr, sym))
+    }
+
+    /// Get the depth of this expression: the number of nodes on the longest path from
+    /// the root of the expression to an atom.
+    ///
+    /// Atoms have depth 1, and a normal expression has a depth one greater than the
+    /// greatest depth of its head and elements. This is equivalent to
+    /// [`Depth`](https://reference.wolfram.com/language/ref/Depth.html)<sub>WL</sub>
+    /// with the option `Heads -> True`.
+    ///
+    /// The expression is traversed using an explicit stack, so this will not overflow
+    /// the call stack on deeply nested expressions.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use wolfram_expr::expr;
+    ///
+    /// assert_eq!(expr!(x).depth(), 1);
+    /// assert_eq!(expr!(f[1, g[2]]).depth(), 3);
+    /// assert_eq!(expr!(f[1][2]).depth(), 3);
+    /// ```
+    pub fn depth(&self) -> usize {
+        let mut max_depth = 0;
+        let mut stack: Vec<(&Expr, usize)> = vec![(self, 1)];
+
+        while let Some((expr, depth)) = stack.pop() {
+            max_depth = max_depth.max(depth);
+
+            if let ExprKind::Normal(normal) = expr.kind() {
+                stack.push((normal.head(), depth + 1));
+                stack.extend(normal.elements().iter().map(|elem| (elem, depth + 1)));
+            }
+        }
+
+        max_depth
+    }
+
+    /// Count the number of atoms in this expression, including atoms that occur as
+    /// heads.
+    ///
+    /// This is equivalent to
+    /// [`LeafCount`](https://reference.wolfram.com/language/ref/LeafCount.html)<sub>WL</sub>,
+    /// and is a simple measure of the complexity of an expression.
+    ///
+    /// The expression is traversed using an explicit stack, so this will not overflow
+    /// the call stack on deeply nested expressions.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use wolfram_expr::expr;
+    ///
+    /// assert_eq!(expr!(x).leaf_count(), 1);
+    /// assert_eq!(expr!(f[1, g[x, "a"]]).leaf_count(), 5);
+    /// ```
+    pub fn leaf_count(&self) -> usize {
+        let mut count = 0;
+        let mut stack: Vec<&Expr> = vec![self];
+
+        while let Some(expr) = stack.pop() {
+            match expr.kind() {
+                ExprKind::Normal(normal) => {
+                    stack.push(normal.head());
+                    stack.extend(normal.elements());
+                },
+                _ => count += 1,
+            }
+        }
+
+        count
+    }
+
+    /// Collect the distinct symbols that occur anywhere in this expression, including
+    /// in heads.
+    ///
+    /// Every symbol is collected, including symbols that are localized by scoping
+    /// constructs like `Function` or `Module`. The symbols are ordered by their
+    /// fully qualified names.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use wolfram_expr::{expr, Symbol};
+    ///
+    /// let symbols = expr!(g[x][y, f[x]]).free_symbols();
+    ///
+    /// assert_eq!(symbols.into_iter().collect::<Vec<_>>(), [
+    ///     Symbol::new("Global`f"),
+    ///     Symbol::new("Global`g"),
+    ///     Symbol::new("Global`x"),
+    ///     Symbol::new("Global`y"),
+    /// ]);
+    /// ```
+    pub fn free_symbols(&self) -> BTreeSet<Symbol> {
+        self.iter_subexpressions()
+            .filter_map(Expr::try_as_symbol)
+            .cloned()
+            .collect()
+    }
+
+    /// Count the subexpressions of this expression, including `self` and heads, that
+    /// satisfy `pred`.
+    ///
+    /// Subexpressions are visited using [`Expr::iter_subexpressions()`], so this will
+    /// not overflow the call stack on deeply nested expressions.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use wolfram_expr::expr;
+    ///
+    /// let expr = expr!(f[1, g[x, 2], x]);
+    ///
+    /// assert_eq!(expr.count(|e| e.try_as_number().is_some()), 2);
+    /// assert_eq!(expr.count(|e| e.try_as_normal().is_some()), 2);
+    /// ```
+    pub fn count<F: Fn(&Expr) -> bool>(&self, pred: F) -> usize {
+        self.iter_subexpressions().filter(|expr| pred(expr)).count()
+    }
+
+    /// Returns `true` if `needle` is equal to this expression or to any of its
+    /// subexpressions, including heads.
+    ///
+    /// This is equivalent to
+    /// [`MemberQ`](https://reference.wolfram.com/language/ref/MemberQ.html)<sub>WL</sub>
+    /// at levels `{0, Infinity}` with `Heads -> True` and a literal `needle`. The
+    /// traversal stops at the first occurrence of `needle`, and will not overflow the
+    /// call stack on deeply nested expressions.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use wolfram_expr::expr;
+    ///
+    /// let expr = expr!(f[1, g[x, "a"]]);
+    ///
+    /// assert!(expr.contains(&expr!(g[x, "a"])));
+    /// assert!(expr.contains(&expr!("a")));
+    /// assert!(!expr.contains(&expr!(g[x])));
+    /// ```
+    pub fn contains(&self, needle: &Expr) -> bool {
+        self.iter_subexpressions().any(|expr| expr == needle)
+    }
+}
+
+/// Returns `true` if `expr` is the symbol `sym`.
+fn is_symbol(expr: &Expr, sym: &Symbol) -> bool {
+    matches!(expr.kind(), ExprKind::Symbol(symbol) if symbol == sym)
+}
+
+#[test]
+fn test_is_free_of_symbol() {
+    use crate::expr;
+
+    let x = Symbol::new("Global`x");
+
+    assert!(expr!(f[1, "x", y]).is_free_of_symbol(&x));
+    assert!(expr!(1).is_free_of_symbol(&x));
+    assert!(!expr!(x).is_free_of_symbol(&x));
+    assert!(!expr!(f[g[h[x]]]).is_free_of_symbol(&x));
+    assert!(!expr!(x[1]).is_free_of_symbol(&x));
+    assert!(!expr!(f[1][g[x]]).is_free_of_symbol(&x));
+
+    // Symbols in other contexts are different symbols.
+    assert!(expr!(System::x).is_free_of_symbol(&x));
+}
+
+#[test]
+fn test_count_symbol() {
+    use crate::expr;
+
+    let x = Symbol::new("Global`x");
+
+    assert_eq!(expr!(f[x, g[x], y]).count_symbol(&x), 2);
+    assert_eq!(expr!(x).count_symbol(&x), 1);
+    assert_eq!(expr!(x[x][x]).count_symbol(&x), 3);
+    assert_eq!(expr!(f[1, "x", System::x]).count_symbol(&x), 0);
+}
+
+#[test]
+fn test_depth() {
+    use crate::expr;
+
+    assert_eq!(expr!(1).depth(), 1);
+    assert_eq!(expr!("a").depth(), 1);
+    assert_eq!(expr!(f[]).depth(), 2);
+    assert_eq!(expr!(f[1, g[2, h[x]], 3]).depth(), 4);
+
+    // Heads are included.
+    assert_eq!(expr!(f[1][g[2]][x]).depth(), 4);
+
+    let expr = crate::tests::nested(100_000);
+    assert_eq!(expr.depth(), 100_001);
+}
+
+#[test]
+fn test_leaf_count() {
+    use crate::expr;
+
+    assert_eq!(expr!(1).leaf_count(), 1);
+    assert_eq!(expr!(x).leaf_count(), 1);
+    assert_eq!(expr!(f[]).leaf_count(), 1);
+    assert_eq!(expr!(f[1, g[2, h[x]], "a"]).leaf_count(), 7);
+
+    // Heads are counted, including compound heads.
+    assert_eq!(expr!(f[1][g[2]][x]).leaf_count(), 5);
+
+    let expr = crate::tests::nested(100_000);
+    assert_eq!(expr.leaf_count(), 100_001);
+}
+
+#[test]
+fn test_free_symbols() {
+    use crate::expr;
+
+    let symbols =
+        |expr: Expr| -> Vec<Symbol> { expr.free_symbols().into_iter().collect() };
+
+    assert_eq!(symbols(expr!(g[x][y])), [
+        Symbol::new("Global`g"),
+        Symbol::new("Global`x"),
+        Symbol::new("Global`y"),
+    ]);
+    assert_eq!(symbols(expr!(System::Plus[x, System::Times[2, x], "y"])), [
+        Symbol::new("Global`x"),
+        Symbol::new("System`Plus"),
+        Symbol::new("System`Times"),
+    ]);
+    assert_eq!(symbols(expr!(x)), [Symbol::new("Global`x")]);
+    assert_eq!(symbols(expr!(1)), []);
+}
+
+#[test]
+fn test_count() {
+    use crate::expr;
+
+    let x = Expr::symbol(Symbol::new("Global`x"));
+    let is_x = |expr: &Expr| *expr == x;
+
+    assert_eq!(expr!(f[x, g[x], y]).count(is_x), 2);
+    assert_eq!(expr!(x[x][x]).count(is_x), 3);
+    assert_eq!(expr!(x).count(is_x), 1);
+    assert_eq!(expr!(f[1, "x", System::x]).count(is_x), 0);
+
+    // `self` and heads are included.
+    assert_eq!(expr!(f[g[1]][2]).count(|_| true), 7);
+
+    let expr = crate::tests::nested(100_000);
+    assert_eq!(expr.count(|e| e.try_as_symbol().is_some()), 100_000);
+}
+
+#[test]
+fn test_contains() {
+    use crate::expr;
+
+    let expr = expr!(f[1, g[h[2.5, "s"]], x][y]);
+
+    assert!(expr.contains(&expr));
+    assert!(expr.contains(&expr!(h[2.5, "s"])));
+    assert!(expr.contains(&expr!(2.5)));
+    assert!(expr.contains(&expr!("s")));
+    assert!(expr.contains(&expr!(f[1, g[h[2.5, "s"]], x])));
+    assert!(expr.contains(&expr!(f)));
+
+    assert!(!expr.contains(&expr!(2)));
+    assert!(!expr.contains(&expr!(h[2.5])));
+    assert!(!expr.contains(&expr!(System::x)));
+
+    let expr = crate::tests::nested(100_000);
+    assert!(expr.contains(&Expr::from(0)));
+    assert!(!expr.contains(&Expr::from(1)));
+}
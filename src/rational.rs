@@ -0,0 +1,173 @@
+//! Exact rational numbers.
+
+use num_bigint::BigInt;
+use num_rational::BigRational;
+use num_traits::ToPrimitive;
+
+use crate::{Expr, Number};
+#[cfg(any(feature = "std", feature = "unstable_parse"))]
+use crate::{ExprKind, Normal};
+
+
+impl Expr {
+    /// Construct an exact rational number expression `numerator / denominator`.
+    ///
+    /// The fraction is reduced to lowest terms, with a positive denominator. If the
+    /// reduced denominator is `1`, this returns an integer expression, like
+    /// [`Rational`](https://reference.wolfram.com/language/ref/Rational.html)<sub>WL</sub>.
+    ///
+    /// This function requires the `rational` crate feature.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if `denominator` is zero.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use wolfram_expr::Expr;
+    ///
+    /// assert_eq!(Expr::rational(4, -6).to_string(), "System`Rational[-2, 3]");
+    /// assert_eq!(Expr::rational(4, -6).input_form().to_string(), "-2/3");
+    ///
+    /// assert_eq!(Expr::rational(4, 2), Expr::from(2));
+    /// ```
+    pub fn rational<N: Into<BigInt>, D: Into<BigInt>>(
+        numerator: N,
+        denominator: D,
+    ) -> Expr {
+        Expr::number(Number::rational(numerator, denominator))
+    }
+}
+
+impl Number {
+    /// Construct an exact rational number `numerator / denominator`.
+    ///
+    /// See [`Expr::rational()`].
+    ///
+    /// This function requires the `rational` crate feature.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if `denominator` is zero.
+    pub fn rational<N: Into<BigInt>, D: Into<BigInt>>(
+        numerator: N,
+        denominator: D,
+    ) -> Number {
+        let denominator = denominator.into();
+
+        if denominator == BigInt::from(0u8) {
+            panic!("Number::rational: got zero denominator");
+        }
+
+        Number::from(BigRational::new(numerator.into(), denominator))
+    }
+}
+
+impl From<BigRational> for Expr {
+    fn from(rational: BigRational) -> Expr {
+        Expr::number(Number::from(rational))
+    }
+}
+
+/// Returns an integer if the denominator of `rational` is `1`.
+impl From<BigRational> for Number {
+    fn from(rational: BigRational) -> Number {
+        // `BigRational` arithmetic keeps values reduced, with a positive denominator.
+        if rational.is_integer() {
+            Number::big_integer(rational.to_integer())
+        } else {
+            Number::Rational(rational)
+        }
+    }
+}
+
+/// Convert `rational` to the nearest `f64`.
+pub(crate) fn rational_to_f64(rational: &BigRational) -> f64 {
+    rational
+        .to_f64()
+        .expect("rational number has a non-zero denominator")
+}
+
+/// If `normal` is `` System`Rational[numerator, denominator] `` with integer arguments
+/// and a non-zero denominator, return the rational number it represents.
+#[cfg(any(feature = "std", feature = "unstable_parse"))]
+pub(crate) fn rational_from_normal(normal: &Normal) -> Option<Expr> {
+    if !normal.has_head(&crate::Symbol::new("System`Rational")) {
+        return None;
+    }
+
+    let integer = |expr: &Expr| match expr.kind() {
+        ExprKind::Integer(int) => Some(BigInt::from(*int)),
+        ExprKind::BigInteger(int) => Some(int.clone()),
+        _ => None,
+    };
+
+    match normal.elements() {
+        [numerator, denominator] => {
+            let (numerator, denominator) = (integer(numerator)?, integer(denominator)?);
+
+            if denominator == BigInt::from(0u8) {
+                return None;
+            }
+
+            Some(Expr::rational(numerator, denominator))
+        },
+        _ => None,
+    }
+}
+
+/// Parse a rational number written as `numerator/denominator`, e.g. `-2/3`.
+pub(crate) fn parse_rational(input: &str) -> Option<BigRational> {
+    let (numerator, denominator) = input.split_once('/')?;
+
+    let numerator = crate::big_integer::parse_big_integer(numerator)?;
+    let denominator = crate::big_integer::parse_big_integer(denominator)?;
+
+    if denominator == BigInt::from(0u8) {
+        return None;
+    }
+
+    Some(BigRational::new(numerator, denominator))
+}
+
+#[test]
+fn test_rational() {
+    assert_eq!(
+        Expr::rational(2, 3).kind(),
+        &crate::ExprKind::Rational(BigRational::new(2.into(), 3.into()))
+    );
+    assert_eq!(Expr::rational(4, 6), Expr::rational(2, 3));
+    assert_eq!(Expr::rational(2, -3), Expr::rational(-2, 3));
+    assert_eq!(Expr::rational(-4, -6), Expr::rational(2, 3));
+
+    // Integral rationals are integers.
+    assert_eq!(Expr::rational(4, 2), Expr::from(2));
+    assert_eq!(Expr::rational(0, 5), Expr::from(0));
+    assert_eq!(
+        Expr::rational(BigInt::from(i64::MAX) * 6u8, 3),
+        Expr::big_integer(BigInt::from(i64::MAX) * 2u8)
+    );
+
+    assert_eq!(Expr::rational(-2, 3).to_string(), "System`Rational[-2, 3]");
+    assert_eq!(rational_to_f64(&BigRational::new(1.into(), 4.into())), 0.25);
+}
+
+#[test]
+#[should_panic(expected = "zero denominator")]
+fn test_rational_zero_denominator() {
+    Expr::rational(1, 0);
+}
+
+#[test]
+fn test_parse_rational() {
+    assert_eq!(
+        parse_rational("-4/6"),
+        Some(BigRational::new((-2).into(), 3.into()))
+    );
+
+    assert_eq!(parse_rational("1/0"), None);
+    assert_eq!(parse_rational("1"), None);
+    assert_eq!(parse_rational("1/+2"), None);
+    assert_eq!(parse_rational("1/2/3"), None);
+}
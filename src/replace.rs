@@ -0,0 +1,252 @@
+//! Rule-based replacement of subexpressions.
+
+use alloc::vec::Vec;
+
+#[cfg(feature = "std")]
+use crate::Symbol;
+use crate::{Expr, ExprKind, Normal};
+
+
+impl Expr {
+    /// Replace each subexpression equal to the left-hand side of one of `rules` with
+    /// the corresponding right-hand side.
+    ///
+    /// This is equivalent to
+    /// [`ReplaceAll`](https://reference.wolfram.com/language/ref/ReplaceAll.html)<sub>WL</sub>
+    /// (`expr /. rules`) with rules whose left-hand sides are literal expressions
+    /// instead of patterns:
+    ///
+    /// * The expression is traversed top-down, including heads.
+    /// * Rules are tried in order, and the first matching rule is used.
+    /// * Once a subexpression has been replaced, its replacement is not traversed.
+    ///
+    /// Subexpressions that contain no replacements are shared with `self` instead of
+    /// being rebuilt.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use wolfram_expr::{expr, Expr};
+    ///
+    /// let expr = expr!(f[x, g[x], y]);
+    ///
+    /// let replaced = expr.replace_all(&[(expr!(x), expr!(1)), (expr!(y), expr!(x))]);
+    ///
+    /// assert_eq!(replaced, expr!(f[1, g[1], x]));
+    /// ```
+    pub fn replace_all(&self, rules: &[(Expr, Expr)]) -> Expr {
+        replace_all(self, rules).unwrap_or_else(|| self.clone())
+    }
+
+    /// Replace each occurrence of a symbol in `map`, as an element or as a head, with
+    /// the corresponding expression.
+    ///
+    /// Like [`Expr::replace_all()`], replacements are not traversed, and subexpressions
+    /// that contain no mapped symbols are shared with `self` instead of being rebuilt.
+    ///
+    /// This function requires the `std` crate feature.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::collections::HashMap;
+    ///
+    /// use wolfram_expr::{expr, Symbol};
+    ///
+    /// let map = HashMap::from([
+    ///     (Symbol::new("Global`f"), expr!(g)),
+    ///     (Symbol::new("Global`x"), expr!(h[y])),
+    /// ]);
+    ///
+    /// assert_eq!(expr!(f[x, f[x]]).replace_symbols(&map), expr!(g[h[y], g[h[y]]]));
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn replace_symbols(&self, map: &std::collections::HashMap<Symbol, Expr>) -> Expr {
+        let lookup = |expr: &Expr| map.get(expr.try_as_symbol()?);
+
+        replace_with(self, &lookup).unwrap_or_else(|| self.clone())
+    }
+
+    /// Apply [`Expr::replace_all()`] repeatedly until the result stops changing.
+    ///
+    /// This is equivalent to
+    /// [`ReplaceRepeated`](https://reference.wolfram.com/language/ref/ReplaceRepeated.html)<sub>WL</sub>
+    /// (`expr //. rules`). The rules are applied at most `max_iterations` times, which
+    /// guards against rule sets that never reach a fixed point (e.g. `x -> f[x]`).
+    ///
+    /// The last result is returned even if `max_iterations` was reached before the
+    /// result stopped changing.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use wolfram_expr::{expr, Expr};
+    ///
+    /// let rules = [(expr!(a), expr!(b)), (expr!(b), expr!(c))];
+    ///
+    /// assert_eq!(expr!(f[a]).replace_repeated(&rules, 10), expr!(f[c]));
+    /// ```
+    pub fn replace_repeated(
+        &self,
+        rules: &[(Expr, Expr)],
+        max_iterations: usize,
+    ) -> Expr {
+        let mut expr = self.clone();
+
+        for _ in 0..max_iterations {
+            match replace_all(&expr, rules) {
+                Some(next) if next != expr => expr = next,
+                _ => break,
+            }
+        }
+
+        expr
+    }
+}
+
+/// Returns `None` if `expr` contains no subexpressions matching `rules`.
+fn replace_all(expr: &Expr, rules: &[(Expr, Expr)]) -> Option<Expr> {
+    let lookup = |expr: &Expr| {
+        rules
+            .iter()
+            .find(|(lhs, _)| lhs == expr)
+            .map(|(_, rhs)| rhs)
+    };
+
+    replace_with(expr, &lookup)
+}
+
+/// Replace each outermost subexpression for which `lookup` returns a replacement.
+///
+/// Returns `None` if `lookup` returns `None` for every subexpression of `expr`.
+fn replace_with<'r, F>(expr: &Expr, lookup: &F) -> Option<Expr>
+where
+    F: Fn(&Expr) -> Option<&'r Expr>,
+{
+    if let Some(replacement) = lookup(expr) {
+        return Some(replacement.clone());
+    }
+
+    let ExprKind::Normal(normal) = expr.kind() else {
+        return None;
+    };
+
+    let head = replace_with(normal.head(), lookup);
+    let elements: Vec<Option<Expr>> = normal
+        .elements()
+        .iter()
+        .map(|elem| replace_with(elem, lookup))
+        .collect();
+
+    if head.is_none() && elements.iter().all(Option::is_none) {
+        return None;
+    }
+
+    let head = head.unwrap_or_else(|| normal.head().clone());
+    let elements = elements
+        .into_iter()
+        .zip(normal.elements())
+        .map(|(new, old)| new.unwrap_or_else(|| old.clone()))
+        .collect();
+
+    Some(Expr::from(Normal::new(head, elements)))
+}
+
+#[test]
+fn test_replace_all() {
+    use crate::expr;
+
+    let expr = expr!(f[x, g[x][y]]);
+
+    // Heads are replaced.
+    assert_eq!(
+        expr.replace_all(&[(expr!(g), expr!(h))]),
+        expr!(f[x, h[x][y]])
+    );
+    assert_eq!(
+        expr.replace_all(&[(expr!(g[x]), expr!(1))]),
+        expr!(f[x, #(1)[y]])
+    );
+
+    // The first matching rule is used, and replacements are not traversed.
+    assert_eq!(
+        expr.replace_all(&[
+            (expr!(x), expr!(y)),
+            (expr!(y), expr!(z)),
+            (expr!(x), expr!(1))
+        ]),
+        expr!(f[y, g[y][z]])
+    );
+
+    // The whole expression can be replaced.
+    assert_eq!(expr.replace_all(&[(expr.clone(), expr!(1))]), expr!(1));
+
+    // Unchanged subexpressions are shared.
+    let expr = expr!(f[g[1, 2], x]);
+    let replaced = expr.replace_all(&[(expr!(x), expr!(y))]);
+    assert_eq!(replaced, expr!(f[g[1, 2], y]));
+    assert!(alloc::sync::Arc::ptr_eq(
+        &replaced.normal_part(0).unwrap().inner,
+        &expr.normal_part(0).unwrap().inner
+    ));
+}
+
+#[test]
+fn test_replace_repeated() {
+    use crate::expr;
+
+    let rules = [(expr!(a), expr!(b)), (expr!(b), expr!(c))];
+
+    assert_eq!(expr!(f[a, b]).replace_all(&rules), expr!(f[b, c]));
+    assert_eq!(expr!(f[a, b]).replace_repeated(&rules, 10), expr!(f[c, c]));
+
+    // Non-terminating rules stop after `max_iterations`.
+    let rules = [(expr!(x), expr!(f[x]))];
+    assert_eq!(expr!(x).replace_repeated(&rules, 3), expr!(f[f[f[x]]]));
+    assert_eq!(expr!(x).replace_repeated(&rules, 0), expr!(x));
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_replace_symbols() {
+    use std::collections::HashMap;
+
+    use crate::expr;
+
+    let map = HashMap::from([
+        (Symbol::new("Global`f"), expr!(h)),
+        (Symbol::new("Global`x"), expr!(g[x])),
+    ]);
+
+    // Head symbols are replaced, and replacements are not traversed.
+    assert_eq!(expr!(f[x, y]).replace_symbols(&map), expr!(h[g[x], y]));
+    assert_eq!(expr!(f).replace_symbols(&map), expr!(h));
+
+    // Deeply nested leaves are replaced.
+    let mut expr = expr!(x);
+    let mut expected = expr!(g[x]);
+    for _ in 0..100 {
+        expr = expr!(k[1, #expr]);
+        expected = expr!(k[1, #expected]);
+    }
+    assert_eq!(expr.replace_symbols(&map), expected);
+
+    // Only symbols are replaced, not strings with the same name.
+    assert_eq!(
+        expr!(k["Global`x", System::x]).replace_symbols(&map),
+        expr!(k["Global`x", System::x])
+    );
+
+    // Unchanged subexpressions are shared.
+    let expr = expr!(k[k[1, 2], x]);
+    let replaced = expr.replace_symbols(&map);
+    assert!(alloc::sync::Arc::ptr_eq(
+        &replaced.normal_part(0).unwrap().inner,
+        &expr.normal_part(0).unwrap().inner
+    ));
+    let unchanged = expr!(k[1, y]);
+    assert!(alloc::sync::Arc::ptr_eq(
+        &unchanged.replace_symbols(&map).inner,
+        &unchanged.inner
+    ));
+}
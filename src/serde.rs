@@ -0,0 +1,596 @@
+//! [`serde`](https://serde.rs) encodings of [`Expr`].
+//!
+//! This module requires the `serde` crate feature.
+//!
+//! [`Expr`], [`ExprKind`], [`Normal`](crate::Normal), [`Number`](crate::Number), and
+//! [`Symbol`](crate::Symbol) implement [`Serialize`] and [`Deserialize`] using the
+//! [structured encoding](#structured-encoding).
+//!
+//! An expression can also be serialized in one of two encodings, selected by wrapping it
+//! in one of the newtypes in this module:
+//!
+//! * [`StructuredExpr`] — a structured encoding that mirrors [`ExprKind`], suitable for
+//!   consumption by other programs.
+//! * [`StringExpr`] — the expression's [`Display`](core::fmt::Display) form, as a
+//!   single string, suitable for logs.
+//!
+//! The [`structured`] and [`string`] modules provide the same encodings for use with
+//! the `#[serde(with = "...")]` and `#[serde(serialize_with = "...")]` field attributes.
+//!
+//! # Structured encoding
+//!
+//! Each expression is encoded as a single-entry map from the name of its [`ExprKind`]
+//! variant to its contents. Symbols are encoded as their fully qualified name, and
+//! normal expressions as a map with `head` and `elements` fields. For example, the
+//! expression `` System`List[1, 2.5, "a", Global`x] `` is encoded in JSON as:
+//!
+//! ```json
+//! {
+//!     "Normal": {
+//!         "head": { "Symbol": "System`List" },
+//!         "elements": [
+//!             { "Integer": 1 },
+//!             { "Real": 2.5 },
+//!             { "String": "a" },
+//!             { "Symbol": "Global`x" }
+//!         ]
+//!     }
+//! }
+//! ```
+//!
+//! Arbitrary-precision real numbers are encoded as a string containing their
+//! [`Display`](core::fmt::Display) form, e.g. `` {"BigReal": "1.5`30"} ``, and
+//! arbitrary-precision integers as a string containing their decimal digits, e.g.
+//! `{"BigInteger": "-92233720368547758070"}`. Rational numbers are encoded as a string
+//! of the form `numerator/denominator`, e.g. `{"Rational": "-2/3"}`.
+//!
+//! A [`Number`](crate::Number) is encoded like a number expression, e.g.
+//! `{"Integer": 5}`, and a [`Symbol`](crate::Symbol) or
+//! [`Normal`](crate::Normal) like the contents of the corresponding variant.
+//!
+//! Deserializing a real number that is NaN, or a symbol that is not a valid fully
+//! qualified symbol, fails with an error.
+//!
+//! # String encoding
+//!
+//! Deserializing the string encoding parses the string using `parse::parse_expr()`,
+//! and so requires the `unstable_parse` crate feature. Without that feature,
+//! [`StringExpr`] only implements [`Serialize`].
+//!
+//! # Example
+//!
+//! ```
+//! use serde::Serialize;
+//! use wolfram_expr::{expr, Expr};
+//!
+//! #[derive(Serialize)]
+//! struct Event {
+//!     #[serde(with = "wolfram_expr::serde::structured")]
+//!     data: Expr,
+//!     #[serde(serialize_with = "wolfram_expr::serde::string::serialize")]
+//!     source: Expr,
+//! }
+//!
+//! let event = Event {
+//!     data: expr!(f[1]),
+//!     source: expr!(g[x]),
+//! };
+//!
+//! assert_eq!(
+//!     serde_json::to_string(&event).unwrap(),
+//!     r#"{"data":{"Normal":{"head":{"Symbol":"Global`f"},"elements":[{"Integer":1}]}},"source":"Global`g[Global`x]"}"#
+//! );
+//! ```
+
+#[cfg(any(feature = "bigreal", feature = "bigint"))]
+use alloc::string::ToString;
+use alloc::{string::String, vec::Vec};
+
+#[cfg(any(feature = "bigreal", feature = "bigint"))]
+use ::serde::de::Unexpected;
+use ::serde::{de::Error as _, Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::{Expr, ExprKind, Normal, Number, Symbol, F64};
+
+/// [`Expr`] wrapper that uses the [structured encoding](self#structured-encoding).
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct StructuredExpr(pub Expr);
+
+/// [`Expr`] wrapper that uses the [string encoding](self#string-encoding).
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct StringExpr(pub Expr);
+
+//======================================
+// Structured encoding
+//======================================
+
+/// Use the [structured encoding](self#structured-encoding) with
+/// `#[serde(with = "wolfram_expr::serde::structured")]`.
+///
+/// This is the encoding used by the [`Serialize`] and [`Deserialize`] implementations
+/// of [`Expr`], so this attribute is only needed to make the choice of encoding
+/// explicit.
+pub mod structured {
+    use super::*;
+
+    /// Serialize `expr` using the structured encoding.
+    pub fn serialize<S: Serializer>(
+        expr: &Expr,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        Repr::from(expr.kind()).serialize(serializer)
+    }
+
+    /// Deserialize an expression from the structured encoding.
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Expr, D::Error> {
+        let kind = match OwnedRepr::deserialize(deserializer)? {
+            OwnedRepr::Integer(int) => ExprKind::Integer(int),
+            #[cfg(feature = "bigint")]
+            OwnedRepr::BigInteger(string) => ExprKind::from(big_integer(&string)?),
+            #[cfg(feature = "rational")]
+            OwnedRepr::Rational(string) => ExprKind::from(rational(&string)?),
+            OwnedRepr::Real(real) => ExprKind::Real(not_nan(real)?),
+            #[cfg(feature = "bigreal")]
+            OwnedRepr::BigReal(string) => ExprKind::BigReal(big_real(&string)?),
+            OwnedRepr::String(string) => ExprKind::String(string),
+            OwnedRepr::Symbol(symbol) => ExprKind::Symbol(symbol),
+            OwnedRepr::Normal(normal) => ExprKind::Normal(normal),
+        };
+
+        Ok(Expr::new(kind))
+    }
+}
+
+impl Serialize for StructuredExpr {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        structured::serialize(&self.0, serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for StructuredExpr {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        structured::deserialize(deserializer).map(StructuredExpr)
+    }
+}
+
+/// Borrowed form of the structured encoding.
+#[derive(Serialize)]
+#[serde(rename = "Expr")]
+enum Repr<'e> {
+    Integer(i64),
+    #[cfg(feature = "bigint")]
+    BigInteger(String),
+    #[cfg(feature = "rational")]
+    Rational(String),
+    Real(f64),
+    #[cfg(feature = "bigreal")]
+    BigReal(String),
+    String(&'e str),
+    Symbol(&'e Symbol),
+    Normal(&'e Normal),
+}
+
+/// Owned form of the structured encoding.
+#[derive(Deserialize)]
+#[serde(rename = "Expr")]
+enum OwnedRepr {
+    Integer(i64),
+    #[cfg(feature = "bigint")]
+    BigInteger(String),
+    #[cfg(feature = "rational")]
+    Rational(String),
+    Real(f64),
+    #[cfg(feature = "bigreal")]
+    BigReal(String),
+    String(String),
+    Symbol(Symbol),
+    Normal(Normal),
+}
+
+impl<'e> From<&'e ExprKind> for Repr<'e> {
+    fn from(kind: &'e ExprKind) -> Self {
+        match kind {
+            ExprKind::Integer(int) => Repr::Integer(*int),
+            #[cfg(feature = "bigint")]
+            ExprKind::BigInteger(int) => Repr::BigInteger(int.to_string()),
+            #[cfg(feature = "rational")]
+            ExprKind::Rational(rational) => Repr::Rational(rational.to_string()),
+            ExprKind::Real(real) => Repr::Real(**real),
+            #[cfg(feature = "bigreal")]
+            ExprKind::BigReal(real) => Repr::BigReal(real.to_string()),
+            ExprKind::String(string) => Repr::String(string),
+            ExprKind::Symbol(symbol) => Repr::Symbol(symbol),
+            ExprKind::Normal(normal) => Repr::Normal(normal),
+        }
+    }
+}
+
+fn not_nan<E: ::serde::de::Error>(real: f64) -> Result<F64, E> {
+    F64::new(real).map_err(|_| E::custom("real number is NaN"))
+}
+
+#[cfg(feature = "bigint")]
+fn big_integer<E: ::serde::de::Error>(string: &str) -> Result<Number, E> {
+    match crate::big_integer::parse_big_integer(string) {
+        Some(int) => Ok(Number::big_integer(int)),
+        None => Err(E::invalid_value(
+            Unexpected::Str(string),
+            &"an arbitrary-precision integer",
+        )),
+    }
+}
+
+#[cfg(feature = "rational")]
+fn rational<E: ::serde::de::Error>(string: &str) -> Result<Number, E> {
+    match crate::rational::parse_rational(string) {
+        Some(rational) => Ok(Number::from(rational)),
+        None => Err(E::invalid_value(
+            Unexpected::Str(string),
+            &"a rational number",
+        )),
+    }
+}
+
+#[cfg(feature = "bigreal")]
+fn big_real<E: ::serde::de::Error>(string: &str) -> Result<crate::BigReal, E> {
+    crate::BigReal::parse(string).ok_or_else(|| {
+        E::invalid_value(
+            Unexpected::Str(string),
+            &"an arbitrary-precision real number",
+        )
+    })
+}
+
+//======================================
+// Expression types
+//======================================
+
+/// Uses the [structured encoding](self#structured-encoding).
+impl Serialize for Expr {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        structured::serialize(self, serializer)
+    }
+}
+
+/// Uses the [structured encoding](self#structured-encoding).
+impl<'de> Deserialize<'de> for Expr {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        structured::deserialize(deserializer)
+    }
+}
+
+/// Uses the [structured encoding](self#structured-encoding) of an expression with this
+/// kind.
+impl Serialize for ExprKind {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        Repr::from(self).serialize(serializer)
+    }
+}
+
+/// Uses the [structured encoding](self#structured-encoding) of an expression with this
+/// kind.
+impl<'de> Deserialize<'de> for ExprKind {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        structured::deserialize(deserializer).map(Expr::to_kind)
+    }
+}
+
+/// Encoded as a map with `head` and `elements` fields, as in the
+/// [structured encoding](self#structured-encoding).
+impl Serialize for Normal {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        #[derive(Serialize)]
+        #[serde(rename = "Normal")]
+        struct NormalRepr<'e> {
+            head: &'e Expr,
+            elements: &'e [Expr],
+        }
+
+        NormalRepr {
+            head: self.head(),
+            elements: self.elements(),
+        }
+        .serialize(serializer)
+    }
+}
+
+/// Encoded as a map with `head` and `elements` fields, as in the
+/// [structured encoding](self#structured-encoding).
+impl<'de> Deserialize<'de> for Normal {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(rename = "Normal")]
+        struct OwnedNormalRepr {
+            head: Expr,
+            elements: Vec<Expr>,
+        }
+
+        let OwnedNormalRepr { head, elements } =
+            OwnedNormalRepr::deserialize(deserializer)?;
+
+        Ok(Normal::new(head, elements))
+    }
+}
+
+/// Uses the [structured encoding](self#structured-encoding) of a number expression,
+/// e.g. `{"Integer": 5}`.
+impl Serialize for Number {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let repr = match self {
+            Number::Integer(int) => Repr::Integer(*int),
+            #[cfg(feature = "bigint")]
+            Number::BigInteger(int) => Repr::BigInteger(int.to_string()),
+            #[cfg(feature = "rational")]
+            Number::Rational(rational) => Repr::Rational(rational.to_string()),
+            Number::Real(real) => Repr::Real(**real),
+            #[cfg(feature = "bigreal")]
+            Number::BigReal(real) => Repr::BigReal(real.to_string()),
+        };
+
+        repr.serialize(serializer)
+    }
+}
+
+/// Uses the [structured encoding](self#structured-encoding) of a number expression,
+/// e.g. `{"Integer": 5}`.
+impl<'de> Deserialize<'de> for Number {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let number = match OwnedRepr::deserialize(deserializer)? {
+            OwnedRepr::Integer(int) => Number::Integer(int),
+            #[cfg(feature = "bigint")]
+            OwnedRepr::BigInteger(string) => big_integer(&string)?,
+            #[cfg(feature = "rational")]
+            OwnedRepr::Rational(string) => rational(&string)?,
+            OwnedRepr::Real(real) => Number::Real(not_nan(real)?),
+            #[cfg(feature = "bigreal")]
+            OwnedRepr::BigReal(string) => Number::BigReal(big_real(&string)?),
+            OwnedRepr::String(_) | OwnedRepr::Symbol(_) | OwnedRepr::Normal(_) => {
+                return Err(D::Error::custom("expected a number expression"))
+            },
+        };
+
+        Ok(number)
+    }
+}
+
+/// Encoded as the fully qualified name of the symbol, e.g. `` "System`Plus" ``.
+impl Serialize for Symbol {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+/// Encoded as the fully qualified name of the symbol, e.g. `` "System`Plus" ``.
+impl<'de> Deserialize<'de> for Symbol {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let name = String::deserialize(deserializer)?;
+
+        name.parse().map_err(D::Error::custom)
+    }
+}
+
+//======================================
+// String encoding
+//======================================
+
+/// Use the [string encoding](self#string-encoding) with
+/// `#[serde(serialize_with = "wolfram_expr::serde::string::serialize")]`, or, with the
+/// `unstable_parse` crate feature, `#[serde(with = "wolfram_expr::serde::string")]`.
+pub mod string {
+    use super::*;
+
+    /// Serialize `expr` as its [`Display`](core::fmt::Display) form.
+    pub fn serialize<S: Serializer>(
+        expr: &Expr,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(expr)
+    }
+
+    /// Deserialize an expression by parsing a string.
+    ///
+    /// This function requires the `unstable_parse` crate feature.
+    #[cfg(feature = "unstable_parse")]
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Expr, D::Error> {
+        let string = String::deserialize(deserializer)?;
+
+        crate::parse::parse_expr(&string).map_err(D::Error::custom)
+    }
+}
+
+impl Serialize for StringExpr {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        string::serialize(&self.0, serializer)
+    }
+}
+
+#[cfg(feature = "unstable_parse")]
+impl<'de> Deserialize<'de> for StringExpr {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        string::deserialize(deserializer).map(StringExpr)
+    }
+}
+
+#[test]
+fn test_structured_roundtrip() {
+    use crate::expr;
+
+    let expr = expr!(f[1, -2.5, "a\"b", System::List[x, g[]][y]]);
+
+    let json = serde_json::to_string(&StructuredExpr(expr.clone())).unwrap();
+    let StructuredExpr(roundtrip) = serde_json::from_str(&json).unwrap();
+
+    assert_eq!(roundtrip, expr);
+
+    assert_eq!(
+        serde_json::to_value(StructuredExpr(expr!(System::List[1, 2.5, "a", x])))
+            .unwrap(),
+        serde_json::json!({
+            "Normal": {
+                "head": { "Symbol": "System`List" },
+                "elements": [
+                    { "Integer": 1 },
+                    { "Real": 2.5 },
+                    { "String": "a" },
+                    { "Symbol": "Global`x" }
+                ]
+            }
+        })
+    );
+}
+
+#[cfg(feature = "unstable_parse")]
+#[test]
+fn test_string_roundtrip() {
+    use crate::expr;
+
+    let expr = expr!(f[1, -2.5, "a\"b", System::List[x, g[]][y]]);
+
+    let json = serde_json::to_string(&StringExpr(expr.clone())).unwrap();
+    let StringExpr(roundtrip) = serde_json::from_str(&json).unwrap();
+
+    assert_eq!(roundtrip, expr);
+
+    assert!(serde_json::from_str::<StringExpr>(r#""f[1""#).is_err());
+}
+
+#[test]
+fn test_structured_invalid() {
+    // Symbols must be fully qualified.
+    assert!(serde_json::from_str::<StructuredExpr>(r#"{"Symbol": "x"}"#).is_err());
+    assert!(serde_json::from_str::<StructuredExpr>(r#"{"Symbol": "Global`"}"#).is_err());
+
+    assert!(serde_json::from_str::<StructuredExpr>(r#"{"Other": 1}"#).is_err());
+    assert!(
+        serde_json::from_str::<StructuredExpr>(r#"{"Normal": {"head": 1}}"#).is_err()
+    );
+}
+
+#[test]
+fn test_string_serialize() {
+    use crate::expr;
+
+    assert_eq!(
+        serde_json::to_string(&StringExpr(expr!(f[x, "a\"b", 1.5]))).unwrap(),
+        r#""Global`f[Global`x, \"a\\\"b\", 1.5]""#
+    );
+}
+
+#[test]
+fn test_expr_types_roundtrip() {
+    use crate::{expr, Number};
+
+    let expr = expr!(f[1, -2.5, "a", System::List[x, g[]][y]]);
+    let json = serde_json::to_string(&expr).unwrap();
+    assert_eq!(
+        json,
+        serde_json::to_string(&StructuredExpr(expr.clone())).unwrap()
+    );
+    assert_eq!(serde_json::from_str::<Expr>(&json).unwrap(), expr);
+
+    let kind: ExprKind = serde_json::from_str(&json).unwrap();
+    assert_eq!(kind, expr.kind().clone());
+    assert_eq!(serde_json::to_string(&kind).unwrap(), json);
+
+    let normal = expr.try_as_normal().unwrap();
+    let normal_json = serde_json::to_value(normal).unwrap();
+    assert_eq!(normal_json, serde_json::to_value(&expr).unwrap()["Normal"]);
+    assert_eq!(
+        &serde_json::from_value::<Normal>(normal_json).unwrap(),
+        normal
+    );
+
+    for number in [Number::Integer(-3), Number::real(0.5)] {
+        let json = serde_json::to_string(&number).unwrap();
+        let roundtrip: Number = serde_json::from_str(&json).unwrap();
+        assert_eq!(roundtrip, number);
+        assert_eq!(
+            serde_json::to_string(&Expr::number(roundtrip)).unwrap(),
+            json
+        );
+    }
+    assert!(serde_json::from_str::<Number>(r#"{"String": "1"}"#).is_err());
+
+    let symbol = Symbol::new("System`Plus");
+    assert_eq!(serde_json::to_string(&symbol).unwrap(), r#""System`Plus""#);
+    assert_eq!(
+        serde_json::from_str::<Symbol>(r#""System`Plus""#).unwrap(),
+        symbol
+    );
+    assert!(serde_json::from_str::<Symbol>(r#""Plus""#).is_err());
+    assert!(serde_json::from_str::<Symbol>(r#""System`1x""#).is_err());
+}
+
+#[test]
+fn test_nan_rejected() {
+    use ::serde::de::value::{self, MapAccessDeserializer, MapDeserializer};
+
+    use crate::Number;
+
+    fn real(
+        value: f64,
+    ) -> MapAccessDeserializer<
+        MapDeserializer<'static, core::iter::Once<(&'static str, f64)>, value::Error>,
+    > {
+        MapAccessDeserializer::new(MapDeserializer::new(core::iter::once((
+            "Real", value,
+        ))))
+    }
+
+    assert_eq!(Expr::deserialize(real(1.5)).unwrap(), Expr::real(1.5));
+    assert!(Expr::deserialize(real(f64::NAN)).is_err());
+    assert!(ExprKind::deserialize(real(f64::NAN)).is_err());
+    assert!(Number::deserialize(real(f64::NAN)).is_err());
+}
+
+#[cfg(feature = "bigint")]
+#[test]
+fn test_big_integer_roundtrip() {
+    use crate::{BigInt, Number};
+
+    let expr = Expr::big_integer(-(BigInt::from(i64::MAX) * 10u8));
+    let json = serde_json::to_string(&expr).unwrap();
+    assert_eq!(json, r#"{"BigInteger":"-92233720368547758070"}"#);
+    assert_eq!(serde_json::from_str::<Expr>(&json).unwrap(), expr);
+    assert_eq!(
+        serde_json::from_str::<Number>(&json).unwrap(),
+        expr.try_as_number().unwrap()
+    );
+
+    assert_eq!(
+        serde_json::from_str::<Expr>(r#"{"BigInteger":"12"}"#).unwrap(),
+        Expr::from(12)
+    );
+    assert!(serde_json::from_str::<Expr>(r#"{"BigInteger":"1e5"}"#).is_err());
+}
+
+#[cfg(feature = "rational")]
+#[test]
+fn test_rational_roundtrip() {
+    use crate::Number;
+
+    let expr = Expr::rational(4, -6);
+    let json = serde_json::to_string(&expr).unwrap();
+    assert_eq!(json, r#"{"Rational":"-2/3"}"#);
+    assert_eq!(serde_json::from_str::<Expr>(&json).unwrap(), expr);
+    assert_eq!(
+        serde_json::from_str::<Number>(&json).unwrap(),
+        expr.try_as_number().unwrap()
+    );
+
+    // Rationals are reduced, and integral rationals are integers.
+    assert_eq!(
+        serde_json::from_str::<Expr>(r#"{"Rational":"4/6"}"#).unwrap(),
+        Expr::rational(2, 3)
+    );
+    assert_eq!(
+        serde_json::from_str::<Expr>(r#"{"Rational":"4/2"}"#).unwrap(),
+        Expr::from(2)
+    );
+    assert!(serde_json::from_str::<Expr>(r#"{"Rational":"1/0"}"#).is_err());
+    assert!(serde_json::from_str::<Expr>(r#"{"Rational":"0.5"}"#).is_err());
+}
@@ -0,0 +1,1035 @@
+//! Operations that restructure an expression's heads and elements.
+
+use alloc::vec::Vec;
+use core::convert::TryFrom;
+
+use crate::{Expr, ExprKind, Normal, Symbol};
+
+
+impl Expr {
+    /// Apply `f` to the head of this expression at nesting depth `n`.
+    ///
+    /// This is equivalent to
+    /// [`Operate`](https://reference.wolfram.com/language/ref/Operate.html)<sub>WL</sub>
+    /// with an explicit level argument:
+    ///
+    /// * At `n = 0`, `f` is applied to the whole expression.
+    /// * At `n = 1`, `f` is applied to the head of the expression.
+    /// * At `n = 2`, `f` is applied to the head of the head, and so on.
+    ///
+    /// If the expression has fewer than `n` levels of nested heads, `f` is applied to
+    /// the innermost head, which is always an atom. For an atomic `self` and `n > 0`,
+    /// `f` is applied to `self`.
+    ///
+    /// # Example
+    ///
+    /// Construct the curried expression `p[f][x][y]` from `f[x][y]`:
+    ///
+    /// ```
+    /// use wolfram_expr::{Expr, Symbol};
+    ///
+    /// let f_x_y = Expr::normal(
+    ///     Expr::normal(Symbol::new("Global`f"), vec![Expr::from(1)]),
+    ///     vec![Expr::from(2)],
+    /// );
+    ///
+    /// let result = f_x_y.operate(2, |head| Expr::normal(Symbol::new("Global`p"), vec![head]));
+    ///
+    /// assert_eq!(result.to_string(), "Global`p[Global`f][1][2]");
+    /// ```
+    pub fn operate<F: FnOnce(Expr) -> Expr>(&self, n: usize, f: F) -> Expr {
+        if n == 0 {
+            return f(self.clone());
+        }
+
+        match self.kind() {
+            ExprKind::Normal(normal) => {
+                let head = normal.head().operate(n - 1, f);
+
+                Expr::from(Normal::new(head, normal.elements().to_vec()))
+            },
+            ExprKind::Integer(_)
+            | ExprKind::Real(_)
+            | ExprKind::String(_)
+            | ExprKind::Symbol(_) => f(self.clone()),
+            #[cfg(feature = "bigint")]
+            ExprKind::BigInteger(_) => f(self.clone()),
+            #[cfg(feature = "rational")]
+            ExprKind::Rational(_) => f(self.clone()),
+            #[cfg(feature = "bigreal")]
+            ExprKind::BigReal(_) => f(self.clone()),
+        }
+    }
+
+    /// Sort the elements of this expression by the key extracted by `f`.
+    ///
+    /// This is equivalent to
+    /// [`SortBy`](https://reference.wolfram.com/language/ref/SortBy.html)<sub>WL</sub>.
+    /// The sort is stable: elements with equal keys keep their original relative order.
+    ///
+    /// If `self` is not a [`Normal`] expression, a clone of `self` is returned.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use wolfram_expr::Expr;
+    ///
+    /// let list = Expr::list(vec![Expr::from("ccc"), Expr::from("a"), Expr::from("bb")]);
+    ///
+    /// let sorted = list.sort_by_key(|e| e.try_as_str().map(str::len));
+    ///
+    /// assert_eq!(sorted.to_string(), r#"System`List["a", "bb", "ccc"]"#);
+    /// ```
+    pub fn sort_by_key<K: Ord, F: FnMut(&Expr) -> K>(&self, f: F) -> Expr {
+        let ExprKind::Normal(normal) = self.kind() else {
+            return self.clone();
+        };
+
+        let mut elements = normal.elements().to_vec();
+        elements.sort_by_key(f);
+
+        Expr::from(Normal::new(normal.head().clone(), elements))
+    }
+
+    /// Remove one layer of `Hold`, `HoldForm`, or `HoldComplete` from this expression.
+    ///
+    /// This is similar to
+    /// [`ReleaseHold`](https://reference.wolfram.com/language/ref/ReleaseHold.html)<sub>WL</sub>,
+    /// but only inspects the outermost head:
+    ///
+    /// * `Hold[x]` becomes `x`.
+    /// * `Hold[x, y]` becomes `Sequence[x, y]`.
+    /// * Any other expression is returned unchanged.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use wolfram_expr::Expr;
+    ///
+    /// let held = Expr::hold_form(Expr::from(5));
+    ///
+    /// assert_eq!(held.release_hold(), Expr::from(5));
+    /// ```
+    pub fn release_hold(&self) -> Expr {
+        let ExprKind::Normal(normal) = self.kind() else {
+            return self.clone();
+        };
+
+        let is_held = matches!(
+            normal.head().try_as_symbol().map(Symbol::as_str),
+            Some("System`Hold" | "System`HoldForm" | "System`HoldComplete")
+        );
+
+        if !is_held {
+            return self.clone();
+        }
+
+        match normal.elements() {
+            [single] => single.clone(),
+            elements => Expr::normal(Symbol::new("System`Sequence"), elements.to_vec()),
+        }
+    }
+
+    /// Get the first element of this expression.
+    ///
+    /// This is equivalent to
+    /// [`First`](https://reference.wolfram.com/language/ref/First.html)<sub>WL</sub>.
+    /// Returns `None` if `self` is not a [`Normal`] expression, or has no elements.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use wolfram_expr::expr;
+    ///
+    /// assert_eq!(expr!(f[1, 2, 3]).first(), Some(&expr!(1)));
+    /// assert_eq!(expr!(f[]).first(), None);
+    /// ```
+    pub fn first(&self) -> Option<&Expr> {
+        self.try_as_normal()?.elements().first()
+    }
+
+    /// Get the last element of this expression.
+    ///
+    /// This is equivalent to
+    /// [`Last`](https://reference.wolfram.com/language/ref/Last.html)<sub>WL</sub>.
+    /// Returns `None` if `self` is not a [`Normal`] expression, or has no elements.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use wolfram_expr::expr;
+    ///
+    /// assert_eq!(expr!(f[1, 2, 3]).last(), Some(&expr!(3)));
+    /// assert_eq!(expr!(x).last(), None);
+    /// ```
+    pub fn last(&self) -> Option<&Expr> {
+        self.try_as_normal()?.elements().last()
+    }
+
+    /// Construct a new expression with the same head as this expression and all of its
+    /// elements except the first.
+    ///
+    /// This is equivalent to
+    /// [`Rest`](https://reference.wolfram.com/language/ref/Rest.html)<sub>WL</sub>.
+    /// Returns `None` if `self` is not a [`Normal`] expression, or has no elements.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use wolfram_expr::expr;
+    ///
+    /// assert_eq!(expr!(f[1, 2, 3]).rest(), Some(expr!(f[2, 3])));
+    /// assert_eq!(expr!(f[]).rest(), None);
+    /// ```
+    pub fn rest(&self) -> Option<Expr> {
+        let normal = self.try_as_normal()?;
+        let (_, rest) = normal.elements().split_first()?;
+
+        Some(Expr::from(Normal::new(
+            normal.head().clone(),
+            rest.to_vec(),
+        )))
+    }
+
+    /// Construct a new expression with the same head as this expression and all of its
+    /// elements except the last.
+    ///
+    /// This is equivalent to
+    /// [`Most`](https://reference.wolfram.com/language/ref/Most.html)<sub>WL</sub>.
+    /// Returns `None` if `self` is not a [`Normal`] expression, or has no elements.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use wolfram_expr::expr;
+    ///
+    /// assert_eq!(expr!(f[1, 2, 3]).most(), Some(expr!(f[1, 2])));
+    /// assert_eq!(expr!(f[]).most(), None);
+    /// ```
+    pub fn most(&self) -> Option<Expr> {
+        let normal = self.try_as_normal()?;
+        let (_, most) = normal.elements().split_last()?;
+
+        Some(Expr::from(Normal::new(
+            normal.head().clone(),
+            most.to_vec(),
+        )))
+    }
+
+    /// Construct a new expression with the same head as this expression and its first
+    /// `n` elements, or its last `-n` elements if `n` is negative.
+    ///
+    /// This is equivalent to
+    /// [`Take`](https://reference.wolfram.com/language/ref/Take.html)<sub>WL</sub>
+    /// with a single count. Returns `None` if `self` is not a [`Normal`] expression, or
+    /// has fewer than `n.abs()` elements.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use wolfram_expr::expr;
+    ///
+    /// assert_eq!(expr!(f[1, 2, 3]).take(2), Some(expr!(f[1, 2])));
+    /// assert_eq!(expr!(f[1, 2, 3]).take(-2), Some(expr!(f[2, 3])));
+    /// assert_eq!(expr!(f[1, 2, 3]).take(4), None);
+    /// ```
+    pub fn take(&self, n: i64) -> Option<Expr> {
+        let normal = self.try_as_normal()?;
+        let (front, back) = split_elements(normal.elements(), n)?;
+
+        let taken = if n >= 0 { front } else { back };
+
+        Some(Expr::from(Normal::new(
+            normal.head().clone(),
+            taken.to_vec(),
+        )))
+    }
+
+    /// Construct a new expression with the same head as this expression and all of its
+    /// elements except the first `n`, or except the last `-n` if `n` is negative.
+    ///
+    /// This is equivalent to
+    /// [`Drop`](https://reference.wolfram.com/language/ref/Drop.html)<sub>WL</sub>
+    /// with a single count. Returns `None` if `self` is not a [`Normal`] expression, or
+    /// has fewer than `n.abs()` elements.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use wolfram_expr::expr;
+    ///
+    /// assert_eq!(expr!(f[1, 2, 3]).drop(2), Some(expr!(f[3])));
+    /// assert_eq!(expr!(f[1, 2, 3]).drop(-2), Some(expr!(f[1])));
+    /// assert_eq!(expr!(f[1, 2, 3]).drop(-4), None);
+    /// ```
+    pub fn drop(&self, n: i64) -> Option<Expr> {
+        let normal = self.try_as_normal()?;
+        let (front, back) = split_elements(normal.elements(), n)?;
+
+        let kept = if n >= 0 { back } else { front };
+
+        Some(Expr::from(Normal::new(
+            normal.head().clone(),
+            kept.to_vec(),
+        )))
+    }
+
+    /// Reverse the order of the elements of this expression.
+    ///
+    /// This is equivalent to
+    /// [`Reverse`](https://reference.wolfram.com/language/ref/Reverse.html)<sub>WL</sub>:
+    /// only the elements of `self` are reversed, not the elements of its
+    /// subexpressions.
+    ///
+    /// If `self` is not a [`Normal`] expression, a clone of `self` is returned.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use wolfram_expr::expr;
+    ///
+    /// assert_eq!(expr!(f[1, g[2, 3], 4]).reverse(), expr!(f[4, g[2, 3], 1]));
+    /// ```
+    pub fn reverse(&self) -> Expr {
+        let ExprKind::Normal(normal) = self.kind() else {
+            return self.clone();
+        };
+
+        let elements = normal.elements().iter().rev().cloned().collect();
+
+        Expr::from(Normal::new(normal.head().clone(), elements))
+    }
+
+    /// Concatenate the elements of normal expressions that have the same head.
+    ///
+    /// This is equivalent to
+    /// [`Join`](https://reference.wolfram.com/language/ref/Join.html)<sub>WL</sub>.
+    /// Returns `None` if `exprs` is empty, contains an expression that is not a
+    /// [`Normal`] expression, or contains expressions with different heads.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use wolfram_expr::{expr, Expr};
+    ///
+    /// assert_eq!(
+    ///     Expr::join(&[expr!(f[1, 2]), expr!(f[]), expr!(f[3])]),
+    ///     Some(expr!(f[1, 2, 3]))
+    /// );
+    /// assert_eq!(Expr::join(&[expr!(f[1]), expr!(g[2])]), None);
+    /// ```
+    pub fn join(exprs: &[Expr]) -> Option<Expr> {
+        let (first, rest) = exprs.split_first()?;
+        let first = first.try_as_normal()?;
+
+        let head = first.head();
+        let mut elements = first.elements().to_vec();
+
+        for expr in rest {
+            let normal = expr.try_as_normal()?;
+
+            if normal.head() != head {
+                return None;
+            }
+
+            elements.extend_from_slice(normal.elements());
+        }
+
+        Some(Expr::from(Normal::new(head.clone(), elements)))
+    }
+
+    /// Splice the elements of any elements of this expression whose head is `head` into
+    /// this expression, if this expression has head `head`.
+    ///
+    /// This is equivalent to
+    /// [`Flatten`](https://reference.wolfram.com/language/ref/Flatten.html)<sub>WL</sub>
+    /// at level 1 (`Flatten[expr, 1, head]`): only the immediate elements of `self` are
+    /// spliced. Use [`Expr::flatten_all()`] to also splice nested elements with head
+    /// `head`.
+    ///
+    /// Elements with other heads are left unchanged. If `self` is not a [`Normal`]
+    /// expression with head `head`, a clone of `self` is returned.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use wolfram_expr::{expr, Symbol};
+    ///
+    /// let plus = Symbol::new("System`Plus");
+    ///
+    /// assert_eq!(
+    ///     expr!(System::Plus[System::Plus[a, b], c]).flatten(&plus),
+    ///     expr!(System::Plus[a, b, c])
+    /// );
+    /// assert_eq!(
+    ///     expr!(System::Plus[System::Plus[a, System::Plus[b]], f[c]]).flatten(&plus),
+    ///     expr!(System::Plus[a, System::Plus[b], f[c]])
+    /// );
+    /// ```
+    pub fn flatten(&self, head: &Symbol) -> Expr {
+        let ExprKind::Normal(normal) = self.kind() else {
+            return self.clone();
+        };
+
+        if !normal.has_head(head) {
+            return self.clone();
+        }
+
+        let mut elements = Vec::with_capacity(normal.elements().len());
+
+        for elem in normal.elements() {
+            match elem.try_as_normal() {
+                Some(inner) if inner.has_head(head) => {
+                    elements.extend_from_slice(inner.elements())
+                },
+                _ => elements.push(elem.clone()),
+            }
+        }
+
+        Expr::from(Normal::new(normal.head().clone(), elements))
+    }
+
+    /// Recursively splice the elements of any elements of this expression whose head is
+    /// `head` into this expression, if this expression has head `head`.
+    ///
+    /// This is equivalent to
+    /// [`Flatten`](https://reference.wolfram.com/language/ref/Flatten.html)<sub>WL</sub>
+    /// at all levels (`Flatten[expr, Infinity, head]`), and to the automatic
+    /// flattening of functions with the
+    /// [`Flat`](https://reference.wolfram.com/language/ref/Flat.html)<sub>WL</sub>
+    /// attribute. Nested elements with head `head` are spliced only if every
+    /// expression enclosing them also has head `head`.
+    ///
+    /// If `self` is not a [`Normal`] expression with head `head`, a clone of `self` is
+    /// returned.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use wolfram_expr::{expr, Symbol};
+    ///
+    /// let plus = Symbol::new("System`Plus");
+    ///
+    /// assert_eq!(
+    ///     expr!(System::Plus[System::Plus[a, System::Plus[b]], f[System::Plus[c]]])
+    ///         .flatten_all(&plus),
+    ///     expr!(System::Plus[a, b, f[System::Plus[c]]])
+    /// );
+    /// ```
+    pub fn flatten_all(&self, head: &Symbol) -> Expr {
+        let ExprKind::Normal(normal) = self.kind() else {
+            return self.clone();
+        };
+
+        if !normal.has_head(head) {
+            return self.clone();
+        }
+
+        let mut elements = Vec::with_capacity(normal.elements().len());
+        flatten_all(normal.elements(), head, &mut elements);
+
+        Expr::from(Normal::new(normal.head().clone(), elements))
+    }
+
+    /// Construct the outer product of `lists`, applying `head` to each combination of
+    /// one element from each list.
+    ///
+    /// This is equivalent to
+    /// [`Outer`](https://reference.wolfram.com/language/ref/Outer.html)<sub>WL</sub>
+    /// at level 1: the result is a list nested as deeply as there are `lists`, and the
+    /// element at position `{i, j, ...}` is `head[lists[0][i], lists[1][j], ...]`.
+    /// Unlike `Outer`, elements of `lists` that are themselves lists are not
+    /// combined element-wise.
+    ///
+    /// Returns `None` if `lists` is empty, or contains an expression that is not a
+    /// `` System`List ``.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use wolfram_expr::{expr, Expr, Symbol};
+    ///
+    /// let product = Expr::outer(Symbol::new("Global`f"), &[
+    ///     expr!(System::List[a, b]),
+    ///     expr!(System::List[1, 2, 3]),
+    /// ]);
+    ///
+    /// assert_eq!(
+    ///     product,
+    ///     Some(expr!(System::List[
+    ///         System::List[f[a, 1], f[a, 2], f[a, 3]],
+    ///         System::List[f[b, 1], f[b, 2], f[b, 3]]
+    ///     ]))
+    /// );
+    /// ```
+    pub fn outer<H: Into<Expr>>(head: H, lists: &[Expr]) -> Option<Expr> {
+        if lists.is_empty() {
+            return None;
+        }
+
+        let list = Symbol::new("System`List");
+
+        let lists = lists
+            .iter()
+            .map(|expr| match expr.try_as_normal() {
+                Some(normal) if normal.has_head(&list) => Some(normal.elements()),
+                _ => None,
+            })
+            .collect::<Option<Vec<&[Expr]>>>()?;
+
+        Some(outer(&head.into(), &lists, &mut Vec::new()))
+    }
+
+    /// Thread the head of this expression over its `` System`List `` elements.
+    ///
+    /// This is equivalent to
+    /// [`Thread`](https://reference.wolfram.com/language/ref/Thread.html)<sub>WL</sub>:
+    /// `f[{a, b}, {c, d}]` becomes `{f[a, c], f[b, d]}`. Elements that are not lists
+    /// are repeated in every application of the head, so `f[{a, b}, x]` becomes
+    /// `{f[a, x], f[b, x]}`. If no elements are lists, a clone of `self` is returned.
+    ///
+    /// Returns `None` if `self` is not a [`Normal`] expression, or if its list
+    /// elements have different lengths.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use wolfram_expr::expr;
+    ///
+    /// assert_eq!(
+    ///     expr!(f[System::List[1, 2], System::List[3, 4]]).thread(),
+    ///     Some(expr!(System::List[f[1, 3], f[2, 4]]))
+    /// );
+    /// assert_eq!(expr!(f[System::List[1, 2], System::List[3]]).thread(), None);
+    /// ```
+    pub fn thread(&self) -> Option<Expr> {
+        let normal = self.try_as_normal()?;
+        let list = Symbol::new("System`List");
+
+        let mut len = None;
+
+        for elem in normal.elements() {
+            match elem.try_as_normal() {
+                Some(inner) if inner.has_head(&list) => match len {
+                    Some(len) if len != inner.elements().len() => return None,
+                    _ => len = Some(inner.elements().len()),
+                },
+                _ => (),
+            }
+        }
+
+        let Some(len) = len else {
+            return Some(self.clone());
+        };
+
+        let threaded = (0..len)
+            .map(|index| {
+                let elements = normal
+                    .elements()
+                    .iter()
+                    .map(|elem| match elem.try_as_normal() {
+                        Some(inner) if inner.has_head(&list) => inner[index].clone(),
+                        _ => elem.clone(),
+                    })
+                    .collect();
+
+                Expr::from(Normal::new(normal.head().clone(), elements))
+            })
+            .collect();
+
+        Some(Expr::list(threaded))
+    }
+
+    /// Count the occurrences of each distinct element of this expression.
+    ///
+    /// This is equivalent to
+    /// [`Tally`](https://reference.wolfram.com/language/ref/Tally.html)<sub>WL</sub>:
+    /// the result is a list of `{element, count}` pairs, in the order in which each
+    /// element first appears. Elements are compared using [`Expr`]'s [`Eq`]
+    /// implementation.
+    ///
+    /// Returns `None` if `self` is not a [`Normal`] expression.
+    ///
+    /// This function requires the `std` crate feature.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use wolfram_expr::expr;
+    ///
+    /// assert_eq!(
+    ///     expr!(System::List[a, b, a, c, b, a]).tally(),
+    ///     Some(expr!(System::List[
+    ///         System::List[a, 3],
+    ///         System::List[b, 2],
+    ///         System::List[c, 1]
+    ///     ]))
+    /// );
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn tally(&self) -> Option<Expr> {
+        use std::collections::{hash_map::Entry, HashMap};
+
+        let elements = self.try_as_normal()?.elements();
+
+        // Index into `counts` of each distinct element.
+        let mut indices: HashMap<&Expr, usize> = HashMap::new();
+        let mut counts: Vec<(&Expr, i64)> = Vec::new();
+
+        for elem in elements {
+            match indices.entry(elem) {
+                Entry::Occupied(entry) => counts[*entry.get()].1 += 1,
+                Entry::Vacant(entry) => {
+                    entry.insert(counts.len());
+                    counts.push((elem, 1));
+                },
+            }
+        }
+
+        let pairs = counts
+            .into_iter()
+            .map(|(elem, count)| Expr::list(vec![elem.clone(), Expr::from(count)]))
+            .collect();
+
+        Some(Expr::list(pairs))
+    }
+
+    /// Construct a new expression with the elements of this expression and `head` as
+    /// its head.
+    ///
+    /// This is equivalent to
+    /// [`Apply`](https://reference.wolfram.com/language/ref/Apply.html)<sub>WL</sub>,
+    /// written `head @@ expr` in the Wolfram Language.
+    ///
+    /// Returns `None` if `self` is not a [`Normal`] expression. See
+    /// [`Normal::with_head()`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use wolfram_expr::expr;
+    ///
+    /// assert_eq!(expr!(f[1, 2]).with_head(expr!(g)), Some(expr!(g[1, 2])));
+    /// assert_eq!(expr!(x).with_head(expr!(g)), None);
+    ///
+    /// // `Plus @@ {1, 2, 3}`
+    /// assert_eq!(
+    ///     expr!(System::List[1, 2, 3]).with_head(expr!(System::Plus)),
+    ///     Some(expr!(System::Plus[1, 2, 3]))
+    /// );
+    /// ```
+    pub fn with_head(&self, head: Expr) -> Option<Expr> {
+        Some(Expr::from(self.try_as_normal()?.with_head(head)))
+    }
+}
+
+impl Normal {
+    /// Construct a new normal expression with the elements of this expression and
+    /// `head` as its head.
+    ///
+    /// This is equivalent to
+    /// [`Apply`](https://reference.wolfram.com/language/ref/Apply.html)<sub>WL</sub>
+    /// (`head @@ expr`). The elements are shared with `self`, not deep-copied.
+    pub fn with_head(&self, head: Expr) -> Normal {
+        Normal::new(head, self.elements().to_vec())
+    }
+
+    /// Construct a new normal expression with the elements of this expression, and the
+    /// result of applying `f` to the head of this expression as its head.
+    ///
+    /// # Example
+    ///
+    /// Curry the head `f` to `p[f]`:
+    ///
+    /// ```
+    /// use wolfram_expr::{expr, Expr};
+    ///
+    /// let normal = expr!(f[1, 2]).try_as_normal().unwrap().map_head(|head| expr!(p[#head]));
+    ///
+    /// assert_eq!(Expr::from(normal), expr!(p[f][1, 2]));
+    /// ```
+    pub fn map_head<F: FnOnce(&Expr) -> Expr>(&self, f: F) -> Normal {
+        self.with_head(f(self.head()))
+    }
+}
+
+/// Split `elements` after the first `n` elements, or before the last `-n` elements if
+/// `n` is negative.
+///
+/// Returns `None` if `elements` has fewer than `n.abs()` elements.
+fn split_elements(elements: &[Expr], n: i64) -> Option<(&[Expr], &[Expr])> {
+    let count = usize::try_from(n.unsigned_abs()).ok()?;
+
+    let index = if n >= 0 {
+        count
+    } else {
+        elements.len().checked_sub(count)?
+    };
+
+    if index > elements.len() {
+        return None;
+    }
+
+    Some(elements.split_at(index))
+}
+
+/// Append `elements` to `out`, replacing each element with head `head` by its
+/// flattened elements.
+fn flatten_all(elements: &[Expr], head: &Symbol, out: &mut Vec<Expr>) {
+    for elem in elements {
+        match elem.try_as_normal() {
+            Some(inner) if inner.has_head(head) => {
+                flatten_all(inner.elements(), head, out)
+            },
+            _ => out.push(elem.clone()),
+        }
+    }
+}
+
+/// Construct the outer product of `lists`, with `args` as the leading arguments of
+/// each application of `head`.
+fn outer(head: &Expr, lists: &[&[Expr]], args: &mut Vec<Expr>) -> Expr {
+    let Some((first, rest)) = lists.split_first() else {
+        return Expr::normal(head.clone(), args.clone());
+    };
+
+    let elements = first
+        .iter()
+        .map(|elem| {
+            args.push(elem.clone());
+            let product = outer(head, rest, args);
+            args.pop();
+            product
+        })
+        .collect();
+
+    Expr::list(elements)
+}
+
+#[test]
+fn test_operate() {
+    let p = |e: Expr| Expr::normal(Symbol::new("Global`p"), vec![e]);
+
+    // f[x][y]
+    let expr = Expr::normal(
+        Expr::normal(Symbol::new("Global`f"), vec![Expr::from(1)]),
+        vec![Expr::from(2)],
+    );
+
+    assert_eq!(expr.operate(0, p).to_string(), "Global`p[Global`f[1][2]]");
+    assert_eq!(expr.operate(1, p).to_string(), "Global`p[Global`f[1]][2]");
+    assert_eq!(expr.operate(2, p).to_string(), "Global`p[Global`f][1][2]");
+
+    // Nesting shallower than `n` applies to the innermost head.
+    assert_eq!(expr.operate(5, p).to_string(), "Global`p[Global`f][1][2]");
+
+    // Atoms have no head to descend into.
+    assert_eq!(Expr::from(5).operate(1, p).to_string(), "Global`p[5]");
+}
+
+#[test]
+fn test_sort_by_key() {
+    let pair = |key: i64, tag: &str| Expr::list(vec![Expr::from(key), Expr::from(tag)]);
+
+    let list = Expr::list(vec![pair(2, "a"), pair(1, "b"), pair(2, "c"), pair(1, "d")]);
+
+    let sorted = list.sort_by_key(|e| match e.normal_part(0).map(Expr::kind) {
+        Some(&ExprKind::Integer(key)) => key,
+        _ => panic!("expected integer key"),
+    });
+
+    // Elements with equal keys keep their original order.
+    assert_eq!(
+        sorted,
+        Expr::list(vec![pair(1, "b"), pair(1, "d"), pair(2, "a"), pair(2, "c")])
+    );
+
+    // Atoms are returned unchanged.
+    let atom = Expr::symbol(Symbol::new("Global`x"));
+    assert_eq!(atom.sort_by_key(|_| 0), atom);
+}
+
+#[test]
+fn test_release_hold() {
+    let x = Expr::symbol(Symbol::new("Global`x"));
+
+    assert_eq!(Expr::hold(x.clone()).release_hold(), x);
+    assert_eq!(Expr::hold_form(x.clone()).release_hold(), x);
+    assert_eq!(Expr::hold_complete(x.clone()).release_hold(), x);
+
+    // Only one layer is removed.
+    let nested = Expr::hold(Expr::hold(x.clone()));
+    assert_eq!(nested.release_hold(), Expr::hold(x.clone()));
+
+    assert_eq!(
+        Expr::normal(Symbol::new("System`Hold"), vec![x.clone(), Expr::from(1)])
+            .release_hold(),
+        Expr::normal(Symbol::new("System`Sequence"), vec![
+            x.clone(),
+            Expr::from(1)
+        ])
+    );
+
+    // Expressions without a hold head are unchanged.
+    let list = Expr::list(vec![Expr::hold(x.clone())]);
+    assert_eq!(list.release_hold(), list);
+    assert_eq!(x.release_hold(), x);
+}
+
+#[test]
+fn test_first_last_rest_most() {
+    use crate::expr;
+
+    let expr = expr!(f[1, g[2], 3][4, 5]);
+    assert_eq!(expr.first(), Some(&expr!(4)));
+    assert_eq!(expr.last(), Some(&expr!(5)));
+    assert_eq!(expr.rest(), Some(expr!(f[1, g[2], 3][5])));
+    assert_eq!(expr.most(), Some(expr!(f[1, g[2], 3][4])));
+
+    let single = expr!(f[x]);
+    assert_eq!(single.first(), Some(&expr!(x)));
+    assert_eq!(single.last(), Some(&expr!(x)));
+    assert_eq!(single.rest(), Some(expr!(f[])));
+    assert_eq!(single.most(), Some(expr!(f[])));
+
+    for expr in [expr!(f[]), expr!(x), expr!("a"), expr!(1)] {
+        assert_eq!(expr.first(), None);
+        assert_eq!(expr.last(), None);
+        assert_eq!(expr.rest(), None);
+        assert_eq!(expr.most(), None);
+    }
+}
+
+#[test]
+fn test_take_drop() {
+    use crate::expr;
+
+    let expr = expr!(f[1, 2, 3, 4]);
+
+    assert_eq!(expr.take(0), Some(expr!(f[])));
+    assert_eq!(expr.take(1), Some(expr!(f[1])));
+    assert_eq!(expr.take(4), Some(expr!(f[1, 2, 3, 4])));
+    assert_eq!(expr.drop(0), Some(expr!(f[1, 2, 3, 4])));
+    assert_eq!(expr.drop(1), Some(expr!(f[2, 3, 4])));
+    assert_eq!(expr.drop(4), Some(expr!(f[])));
+
+    // Negative counts are taken from the end.
+    assert_eq!(expr.take(-1), Some(expr!(f[4])));
+    assert_eq!(expr.take(-3), Some(expr!(f[2, 3, 4])));
+    assert_eq!(expr.take(-4), Some(expr!(f[1, 2, 3, 4])));
+    assert_eq!(expr.drop(-1), Some(expr!(f[1, 2, 3])));
+    assert_eq!(expr.drop(-3), Some(expr!(f[1])));
+    assert_eq!(expr.drop(-4), Some(expr!(f[])));
+
+    // Out of range.
+    for n in [5, -5, i64::MAX, i64::MIN] {
+        assert_eq!(expr.take(n), None);
+        assert_eq!(expr.drop(n), None);
+    }
+
+    // Non-symbol heads are preserved.
+    assert_eq!(expr!(f[x][1, 2]).take(-1), Some(expr!(f[x][2])));
+
+    // Atoms.
+    assert_eq!(expr!(x).take(0), None);
+    assert_eq!(expr!(x).drop(0), None);
+}
+
+#[test]
+fn test_reverse() {
+    use crate::expr;
+
+    assert_eq!(expr!(f[1, 2, 3]).reverse(), expr!(f[3, 2, 1]));
+    assert_eq!(expr!(f[1, 2][3, 4]).reverse(), expr!(f[1, 2][4, 3]));
+    assert_eq!(expr!(f[x]).reverse(), expr!(f[x]));
+    assert_eq!(expr!(f[]).reverse(), expr!(f[]));
+
+    let expr = expr!(f[1, g[2, 3], "a"]);
+    assert_eq!(expr.reverse().reverse(), expr);
+
+    // Atoms are returned unchanged.
+    assert_eq!(expr!(x).reverse(), expr!(x));
+}
+
+#[test]
+fn test_join() {
+    use crate::expr;
+
+    assert_eq!(
+        Expr::join(&[expr!(f[1]), expr!(f[2, g[3]]), expr!(f[4])]),
+        Some(expr!(f[1, 2, g[3], 4]))
+    );
+    assert_eq!(Expr::join(&[expr!(f[1, 2])]), Some(expr!(f[1, 2])));
+    assert_eq!(Expr::join(&[expr!(f[]), expr!(f[])]), Some(expr!(f[])));
+    assert_eq!(
+        Expr::join(&[expr!(f[1][2]), expr!(f[1][3])]),
+        Some(expr!(f[1][2, 3]))
+    );
+
+    assert_eq!(Expr::join(&[]), None);
+    assert_eq!(Expr::join(&[expr!(f[1]), expr!(g[1])]), None);
+    assert_eq!(Expr::join(&[expr!(f[1]), expr!(f[1][2])]), None);
+    assert_eq!(Expr::join(&[expr!(f[1]), expr!(f)]), None);
+    assert_eq!(Expr::join(&[expr!(x)]), None);
+}
+
+#[test]
+fn test_flatten() {
+    use crate::expr;
+
+    let f = Symbol::new("Global`f");
+
+    assert_eq!(expr!(f[f[1, 2], 3, f[]]).flatten(&f), expr!(f[1, 2, 3]));
+    assert_eq!(expr!(f[f[f[1]], 2]).flatten(&f), expr!(f[f[1], 2]));
+    assert_eq!(expr!(f[f[f[1]], 2]).flatten_all(&f), expr!(f[1, 2]));
+
+    // Unrelated heads are untouched.
+    assert_eq!(expr!(f[g[1, 2], 3]).flatten(&f), expr!(f[g[1, 2], 3]));
+    assert_eq!(
+        expr!(f[g[f[1]], f[2]]).flatten_all(&f),
+        expr!(f[g[f[1]], 2])
+    );
+    assert_eq!(expr!(g[f[1], 2]).flatten(&f), expr!(g[f[1], 2]));
+    assert_eq!(expr!(g[f[1], 2]).flatten_all(&f), expr!(g[f[1], 2]));
+
+    // Heads that are not symbols are not spliced.
+    assert_eq!(expr!(f[f[x][1]]).flatten_all(&f), expr!(f[f[x][1]]));
+
+    // Atoms are returned unchanged.
+    assert_eq!(expr!(1).flatten(&f), expr!(1));
+    assert_eq!(expr!(f).flatten_all(&f), expr!(f));
+}
+
+#[test]
+fn test_outer() {
+    use crate::expr;
+
+    let f = Symbol::new("Global`f");
+
+    assert_eq!(
+        Expr::outer(&f, &[expr!(System::List[1, 2])]),
+        Some(expr!(System::List[f[1], f[2]]))
+    );
+    assert_eq!(
+        Expr::outer(&f, &[
+            expr!(System::List[1, 2]),
+            expr!(System::List[x]),
+            expr!(System::List[a, b]),
+        ]),
+        Some(expr!(System::List[
+            System::List[System::List[f[1, x, a], f[1, x, b]]],
+            System::List[System::List[f[2, x, a], f[2, x, b]]]
+        ]))
+    );
+
+    // Nested lists are treated as elements.
+    assert_eq!(
+        Expr::outer(&f, &[
+            expr!(System::List[System::List[1, 2]]),
+            expr!(System::List[x])
+        ]),
+        Some(expr!(System::List[System::List[f[System::List[1, 2], x]]]))
+    );
+
+    assert_eq!(
+        Expr::outer(&f, &[expr!(System::List[1, 2]), expr!(System::List[])]),
+        Some(expr!(System::List[System::List[], System::List[]]))
+    );
+
+    assert_eq!(Expr::outer(&f, &[]), None);
+    assert_eq!(
+        Expr::outer(&f, &[expr!(System::List[1]), expr!(g[1])]),
+        None
+    );
+    assert_eq!(Expr::outer(&f, &[expr!(x)]), None);
+}
+
+#[test]
+fn test_thread() {
+    use crate::expr;
+
+    assert_eq!(
+        expr!(f[System::List[1, 2, 3], System::List[a, b, c]]).thread(),
+        Some(expr!(System::List[f[1, a], f[2, b], f[3, c]]))
+    );
+
+    // Non-list elements are broadcast.
+    assert_eq!(
+        expr!(f[x, System::List[1, 2], y, System::List[a, b]]).thread(),
+        Some(expr!(System::List[f[x, 1, y, a], f[x, 2, y, b]]))
+    );
+    assert_eq!(
+        expr!(f[g[1, 2], System::List[a, b]]).thread(),
+        Some(expr!(System::List[f[g[1, 2], a], f[g[1, 2], b]]))
+    );
+
+    // Empty lists.
+    assert_eq!(
+        expr!(f[System::List[], x]).thread(),
+        Some(expr!(System::List[]))
+    );
+
+    // No list elements.
+    assert_eq!(expr!(f[1, 2]).thread(), Some(expr!(f[1, 2])));
+    assert_eq!(expr!(f[]).thread(), Some(expr!(f[])));
+
+    // Length mismatch.
+    assert_eq!(
+        expr!(f[System::List[1, 2], System::List[a, b, c]]).thread(),
+        None
+    );
+
+    // Atoms.
+    assert_eq!(expr!(x).thread(), None);
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_tally() {
+    use crate::expr;
+
+    assert_eq!(
+        expr!(System::List[1, x, 1.0, "1", f[1], 1, f[1], x, 1]).tally(),
+        Some(expr!(System::List[
+            System::List[1, 3],
+            System::List[x, 2],
+            System::List[1.0, 1],
+            System::List["1", 1],
+            System::List[f[1], 2]
+        ]))
+    );
+
+    // Any head is allowed, but the result is always a list.
+    assert_eq!(
+        expr!(f[a, a]).tally(),
+        Some(expr!(System::List[System::List[a, 2]]))
+    );
+    assert_eq!(expr!(System::List[]).tally(), Some(expr!(System::List[])));
+
+    assert_eq!(expr!(x).tally(), None);
+}
+
+#[test]
+fn test_with_head() {
+    use crate::expr;
+
+    let expr = expr!(f[1, g[2]]);
+
+    assert_eq!(expr.with_head(expr!(g)), Some(expr!(g[1, g[2]])));
+    assert_eq!(expr.with_head(expr!(h[x])), Some(expr!(h[x][1, g[2]])));
+    assert_eq!(expr!(f[]).with_head(expr!(g)), Some(expr!(g[])));
+    assert_eq!(expr!(1).with_head(expr!(g)), None);
+
+    // Elements are shared, not copied.
+    let replaced = expr.with_head(expr!(g)).unwrap();
+    assert!(alloc::sync::Arc::ptr_eq(
+        &replaced.normal_part(1).unwrap().inner,
+        &expr.normal_part(1).unwrap().inner
+    ));
+
+    let normal = expr.try_as_normal().unwrap();
+    assert_eq!(
+        Expr::from(normal.map_head(|head| expr!(p[#head]))),
+        expr!(p[f][1, g[2]])
+    );
+}
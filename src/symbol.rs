@@ -19,6 +19,9 @@
 //! * [`ContextRef`]
 // * TODO: `RelativeContextRef`
 //!
+//! [`SymbolTable`] can be used to share a single allocation between symbols with the
+//! same name.
+//!
 //! ## Related Links
 //!
 //! * [Input Syntax: Symbol Names and Contexts][ref/SymbolNamesAndContexts]
@@ -26,13 +29,19 @@
 //! [ref/SymbolNamesAndContexts]: https://reference.wolfram.com/language/tutorial/InputSyntax.html#6562
 
 pub(crate) mod parse;
+#[cfg(feature = "std")]
+mod table;
 
-use std::{
+use alloc::{borrow::ToOwned, format, string::String, sync::Arc, vec::Vec};
+use core::{
     fmt::{self, Debug, Display},
     mem,
-    sync::Arc,
+    str::FromStr,
 };
 
+#[cfg(feature = "std")]
+pub use self::table::SymbolTable;
+
 
 /* Notes
 
@@ -86,6 +95,25 @@ pub struct Context(Arc<String>);
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct RelativeContext(Arc<String>);
 
+/// Error returned when parsing a string as a [`Symbol`], [`SymbolName`], [`Context`],
+/// or [`RelativeContext`] fails.
+///
+/// ```
+/// use wolfram_expr::Symbol;
+///
+/// let err = "Plus".parse::<Symbol>().unwrap_err();
+///
+/// assert_eq!(err.input(), "Plus");
+/// assert_eq!(err.to_string(), r#"expected a symbol, got a symbol name: "Plus""#);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SymbolParseError {
+    input: String,
+    expected: &'static str,
+    /// What `input` is instead, or `None` if it is not valid symbol syntax.
+    found: Option<&'static str>,
+}
+
 // By using `usize` here, we guarantee that we can later change this to be a pointer
 // instead without changing the sizes of a lot of Expr types. This is good for FFI/ABI
 // compatibility if I decide to change the way Symbol works.
@@ -154,19 +182,173 @@ impl Symbol {
     }
 
     /// Get a borrowed [`SymbolRef`] from this [`Symbol`].
-    pub fn as_symbol_ref(&self) -> SymbolRef {
+    pub fn as_symbol_ref(&self) -> SymbolRef<'_> {
         SymbolRef(self.0.as_str())
     }
 
     /// Get the context path part of a symbol as an [`ContextRef`].
-    pub fn context(&self) -> ContextRef {
+    pub fn context(&self) -> ContextRef<'_> {
         self.as_symbol_ref().context()
     }
 
     /// Get the symbol name part of a symbol as a [`SymbolNameRef`].
-    pub fn symbol_name(&self) -> SymbolNameRef {
+    pub fn symbol_name(&self) -> SymbolNameRef<'_> {
         self.as_symbol_ref().symbol_name()
     }
+
+    /// Split this symbol into owned copies of its context and symbol name parts.
+    ///
+    /// This is the inverse of [`Symbol::from_parts()`].
+    ///
+    /// ```
+    /// use wolfram_expr::Symbol;
+    ///
+    /// let (context, name) = Symbol::new("MyPackage`Private`foo").split();
+    ///
+    /// assert!(context.as_str() == "MyPackage`Private`");
+    /// assert!(name.as_str() == "foo");
+    /// ```
+    pub fn split(&self) -> (Context, SymbolName) {
+        (
+            self.context().to_context(),
+            self.symbol_name().to_symbol_name(),
+        )
+    }
+
+    /// Construct the symbol with symbol name `name` in `context`.
+    ///
+    /// ```
+    /// use wolfram_expr::{symbol::{Context, SymbolName}, Symbol};
+    ///
+    /// let name = SymbolName::try_new("foo").unwrap();
+    ///
+    /// assert!(Symbol::from_parts(&Context::global(), &name) == Symbol::new("Global`foo"));
+    /// ```
+    pub fn from_parts(context: &Context, name: &SymbolName) -> Self {
+        // SAFETY: An absolute context followed by a symbol name is always a valid
+        //         absolute symbol.
+        unsafe { Symbol::unchecked_new(format!("{}{}", context, name)) }
+    }
+
+    /// Construct the symbol with the same symbol name as this symbol in `context`.
+    ///
+    /// ```
+    /// use wolfram_expr::{symbol::Context, Symbol};
+    ///
+    /// let symbol = Symbol::new("Global`foo").with_context(&Context::new("MyPkg`"));
+    ///
+    /// assert!(symbol == Symbol::new("MyPkg`foo"));
+    /// ```
+    pub fn with_context(&self, context: &Context) -> Self {
+        Self::try_new(&format!("{}{}", context, self.symbol_name().as_str()))
+            .expect("Symbol::with_context(): invalid Symbol")
+    }
+
+    /// Returns `true` if the symbol name of this symbol is `name`, ignoring its context.
+    ///
+    /// ```
+    /// use wolfram_expr::Symbol;
+    ///
+    /// assert!(Symbol::new("System`Plus").name_is("Plus"));
+    /// assert!(Symbol::new("MyPkg`Private`Plus").name_is("Plus"));
+    /// assert!(!Symbol::new("System`Plus").name_is("System`Plus"));
+    /// ```
+    pub fn name_is(&self, name: &str) -> bool {
+        self.symbol_name().as_str() == name
+    }
+
+    /// Returns `true` if the context of this symbol is exactly `context`.
+    ///
+    /// Symbols in subcontexts of `context` are not in `context`.
+    ///
+    /// ```
+    /// use wolfram_expr::{symbol::Context, Symbol};
+    ///
+    /// let my_pkg = Context::new("MyPkg`");
+    ///
+    /// assert!(Symbol::new("MyPkg`f").in_context(&my_pkg));
+    /// assert!(!Symbol::new("MyPkg`Private`f").in_context(&my_pkg));
+    /// ```
+    pub fn in_context(&self, context: &Context) -> bool {
+        self.context().as_str() == context.as_str()
+    }
+
+    /// Returns `true` if this symbol is in the `` System` `` context.
+    ///
+    /// ```
+    /// use wolfram_expr::Symbol;
+    ///
+    /// assert!(Symbol::new("System`Plus").is_system());
+    /// assert!(!Symbol::new("Global`Plus").is_system());
+    /// ```
+    pub fn is_system(&self) -> bool {
+        self.context().as_str() == "System`"
+    }
+
+    /// Returns `true` if this symbol is in the `` Global` `` context.
+    ///
+    /// ```
+    /// use wolfram_expr::Symbol;
+    ///
+    /// assert!(Symbol::new("Global`x").is_global());
+    /// assert!(!Symbol::new("System`x").is_global());
+    /// ```
+    pub fn is_global(&self) -> bool {
+        self.context().as_str() == "Global`"
+    }
+
+    /// Format this symbol as it would be written in `current_context`, with
+    /// `context_path` as the list of contexts searched for symbol names.
+    ///
+    /// This mirrors how Wolfram Language formats symbols for output, based on
+    /// [`$Context`](https://reference.wolfram.com/language/ref/$Context.html)<sub>WL</sub>
+    /// and
+    /// [`$ContextPath`](https://reference.wolfram.com/language/ref/$ContextPath.html)<sub>WL</sub>.
+    /// A symbol name without a context is looked up in `current_context` first, and then
+    /// in each context in `context_path` in turn, so the context of this symbol can be
+    /// omitted only if that lookup would find this symbol.
+    ///
+    /// This function cannot know which symbols exist, so it treats `context_path`
+    /// conservatively. The context is omitted if:
+    ///
+    /// * the context of this symbol is `current_context`, or
+    /// * the context of this symbol is the first context in `context_path`. A symbol in
+    ///   a later context of `context_path` could be shadowed by a symbol with the same
+    ///   name in an earlier context, so its short name is ambiguous.
+    ///
+    /// Shadowing by a symbol with the same name in `current_context` is not considered.
+    /// Otherwise, the full symbol is returned.
+    ///
+    /// ```
+    /// use wolfram_expr::{symbol::Context, Symbol};
+    ///
+    /// let global = Context::global();
+    /// let path = [Context::system()];
+    ///
+    /// assert_eq!(Symbol::new("System`Plus").format_in(&global, &path), "Plus");
+    /// assert_eq!(Symbol::new("Global`x").format_in(&global, &path), "x");
+    /// assert_eq!(Symbol::new("MyPkg`f").format_in(&global, &path), "MyPkg`f");
+    ///
+    /// // `Plus` could refer to `MyPkg`Plus`.
+    /// let path = [Context::new("MyPkg`"), Context::system()];
+    /// assert_eq!(Symbol::new("System`Plus").format_in(&global, &path), "System`Plus");
+    /// ```
+    pub fn format_in(
+        &self,
+        current_context: &Context,
+        context_path: &[Context],
+    ) -> String {
+        let name = self.symbol_name().as_str();
+
+        if self.in_context(current_context) {
+            return String::from(name);
+        }
+
+        match context_path.first() {
+            Some(first) if self.in_context(first) => String::from(name),
+            _ => String::from(self.as_str()),
+        }
+    }
 }
 
 impl SymbolName {
@@ -180,7 +362,7 @@ impl SymbolName {
     }
 
     /// Get a borrowed [`SymbolNameRef`] from this `SymbolName`.
-    pub fn as_symbol_name_ref(&self) -> SymbolNameRef {
+    pub fn as_symbol_name_ref(&self) -> SymbolNameRef<'_> {
         SymbolNameRef(self.as_str())
     }
 }
@@ -257,8 +439,8 @@ impl Context {
     /// assert!(components[1].as_str() == "Sub");
     /// assert!(components[2].as_str() == "Module");
     /// ```
-    pub fn components(&self) -> Vec<SymbolNameRef> {
-        let comps: Vec<SymbolNameRef> = self
+    pub fn components(&self) -> Vec<SymbolNameRef<'_>> {
+        let comps: Vec<SymbolNameRef<'_>> = self
             .0
             .split('`')
             // Remove the last component, which will always be the empty string
@@ -272,8 +454,40 @@ impl Context {
         comps
     }
 
+    /// Return the context containing this context, with the last component removed, or
+    /// `None` if this context has a single component.
+    ///
+    /// ```
+    /// use wolfram_expr::symbol::Context;
+    ///
+    /// let context = Context::new("MyPackage`Sub`");
+    ///
+    /// assert_eq!(context.parent(), Some(Context::new("MyPackage`")));
+    /// assert_eq!(Context::system().parent(), None);
+    /// ```
+    pub fn parent(&self) -> Option<Context> {
+        // Strip the trailing '`' of the last component, then the last component.
+        let without_last = self.0.strip_suffix('`')?;
+        let end = without_last.rfind('`')? + 1;
+
+        // SAFETY: Every prefix of a context that ends at a '`' is a valid context.
+        Some(unsafe { Context::unchecked_new(&self.0[..end]) })
+    }
+
+    /// Return the number of components of this [`Context`].
+    ///
+    /// ```
+    /// use wolfram_expr::symbol::Context;
+    ///
+    /// assert_eq!(Context::new("MyPackage`Sub`Module`").depth(), 3);
+    /// assert_eq!(Context::global().depth(), 1);
+    /// ```
+    pub fn depth(&self) -> usize {
+        self.0.matches('`').count()
+    }
+
     /// Get a borrowed [`ContextRef`] from this `Context`.
-    pub fn as_context_ref(&self) -> ContextRef {
+    pub fn as_context_ref(&self) -> ContextRef<'_> {
         ContextRef(self.as_str())
     }
 
@@ -302,7 +516,7 @@ impl RelativeContext {
     /// assert!(components[0].as_str() == "Sub");
     /// assert!(components[1].as_str() == "Module");
     /// ```
-    pub fn components(&self) -> Vec<SymbolNameRef> {
+    pub fn components(&self) -> Vec<SymbolNameRef<'_>> {
         self.0
             .split('`')
             // Remove the last component, which will always be the empty string
@@ -313,6 +527,28 @@ impl RelativeContext {
             })
             .collect()
     }
+
+    /// Returns `true` if this relative context has more than one component, like
+    /// ``"`Sub`Module`"``.
+    pub fn is_multi_level(&self) -> bool {
+        self.0.matches('`').count() > 2
+    }
+
+    /// Resolve this relative context against `base`, by appending the components of
+    /// this context to `base`.
+    ///
+    /// ```
+    /// use wolfram_expr::symbol::{Context, RelativeContext};
+    ///
+    /// let relative = RelativeContext::try_new("`Sub`Module`").unwrap();
+    ///
+    /// assert!(relative.resolve(&Context::new("MyPkg`")) == Context::new("MyPkg`Sub`Module`"));
+    /// ```
+    pub fn resolve(&self, base: &Context) -> Context {
+        // `self` begins with a '`', which `base` already ends with.
+        Context::try_new(&format!("{}{}", base, &self.0[1..]))
+            .expect("RelativeContext::resolve(): invalid Context")
+    }
 }
 
 macro_rules! common_impls {
@@ -470,6 +706,77 @@ impl<'s> ContextRef<'s> {
     }
 }
 
+//======================================
+// Parsing impls
+//======================================
+
+impl SymbolParseError {
+    fn new(input: &str, expected: &'static str) -> Self {
+        SymbolParseError {
+            input: input.to_owned(),
+            expected,
+            found: parse::describe_symbol_like(input),
+        }
+    }
+
+    /// The string that could not be parsed.
+    pub fn input(&self) -> &str {
+        &self.input
+    }
+}
+
+impl FromStr for Symbol {
+    type Err = SymbolParseError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        Symbol::try_new(input).ok_or_else(|| SymbolParseError::new(input, "a symbol"))
+    }
+}
+
+impl FromStr for SymbolName {
+    type Err = SymbolParseError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        SymbolName::try_new(input)
+            .ok_or_else(|| SymbolParseError::new(input, "a symbol name"))
+    }
+}
+
+impl FromStr for Context {
+    type Err = SymbolParseError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        Context::try_new(input).ok_or_else(|| SymbolParseError::new(input, "a context"))
+    }
+}
+
+impl FromStr for RelativeContext {
+    type Err = SymbolParseError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        RelativeContext::try_new(input)
+            .ok_or_else(|| SymbolParseError::new(input, "a relative context"))
+    }
+}
+
+/// Formats as e.g. `expected a context, got a symbol name: "Foo"`.
+impl Display for SymbolParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let SymbolParseError {
+            input,
+            expected,
+            found,
+        } = self;
+
+        let found = found.unwrap_or("invalid syntax");
+
+        write!(f, "expected {}, got {}: {:?}", expected, found, input)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for SymbolParseError {}
+
 //======================================
 // Formatting impls
 //======================================
@@ -1,6 +1,8 @@
 // TODO(!): Replace all of this symbol parsing logic with functionality from
 //          wolfram-code-parse, once that is available.
 
+use alloc::{borrow::ToOwned, vec::Vec};
+
 use crate::symbol::{ContextRef, RelativeContext, SymbolNameRef, SymbolRef};
 
 #[allow(non_snake_case)]
@@ -40,6 +42,21 @@ pub(super) fn RelativeContext_try_new(input: &str) -> Option<RelativeContext> {
     }
 }
 
+/// Describe the kind of symbol-like string `input` is, for use in error messages.
+///
+/// Returns `None` if `input` is not symbol-like.
+pub(super) fn describe_symbol_like(input: &str) -> Option<&'static str> {
+    let description = match parse_symbol_like(input)? {
+        SymbolLike::AbsoluteSymbol => "a symbol",
+        SymbolLike::SymbolName => "a symbol name",
+        SymbolLike::RelativeSymbol => "a relative symbol",
+        SymbolLike::AbsoluteContext => "a context",
+        SymbolLike::RelativeContext => "a relative context",
+    };
+
+    Some(description)
+}
+
 #[derive(Debug, PartialEq)]
 enum SymbolLike {
     /// `` ctx`foo ``
@@ -62,7 +79,7 @@ fn parse_symbol_like(input: &str) -> Option<SymbolLike> {
     let components: Vec<&str> = input.split("`").collect();
 
     let like = match components.as_slice() {
-        [only] if is_symbol_component(*only) => SymbolLike::SymbolName,
+        [only] if is_symbol_component(only) => SymbolLike::SymbolName,
         // "`...`"
         ["", inner @ .., ""] if inner.iter().copied().all(is_symbol_component) => {
             SymbolLike::RelativeContext
@@ -106,7 +123,7 @@ fn is_symbol_component(str: &str) -> bool {
         match char {
             '_' | '-' => return false,
             _ if char.is_alphabetic() => (),
-            _ if char.is_digit(10) => (),
+            _ if char.is_ascii_digit() => (),
             '$' => (),
             _ => return false,
         }
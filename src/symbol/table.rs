@@ -0,0 +1,133 @@
+use std::{borrow::Borrow, collections::HashSet, sync::Mutex};
+
+use crate::symbol::Symbol;
+
+/// Interner that deduplicates [`Symbol`] allocations.
+///
+/// Every [`Symbol`] returned by [`SymbolTable::intern()`] for a given name shares a
+/// single allocation, so constructing many copies of a frequently used symbol (like
+/// `` System`List ``) costs a reference count increment instead of a string allocation.
+///
+/// `SymbolTable` can be shared between threads.
+///
+/// This type requires the `std` crate feature.
+///
+/// # Example
+///
+/// ```
+/// use wolfram_expr::symbol::SymbolTable;
+///
+/// let table = SymbolTable::new();
+///
+/// let a = table.intern("System`Plus");
+/// let b = table.intern("System`Plus");
+///
+/// assert_eq!(a, b);
+/// assert_eq!(table.len(), 1);
+/// ```
+#[derive(Debug, Default)]
+pub struct SymbolTable {
+    symbols: Mutex<HashSet<Interned>>,
+}
+
+/// [`Symbol`] that can be looked up in a [`HashSet`] by its `&str` value.
+#[derive(Debug, PartialEq, Eq, Hash)]
+struct Interned(Symbol);
+
+impl Borrow<str> for Interned {
+    fn borrow(&self) -> &str {
+        self.0.as_str()
+    }
+}
+
+impl SymbolTable {
+    /// Construct a new, empty symbol table.
+    pub fn new() -> Self {
+        SymbolTable::default()
+    }
+
+    /// Get the symbol named `name`, sharing the allocation of any previous symbol
+    /// interned with the same name.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if `name` is not a valid absolute symbol, like
+    /// [`Symbol::new()`].
+    #[track_caller]
+    pub fn intern(&self, name: &str) -> Symbol {
+        let mut symbols = self.lock();
+
+        if let Some(Interned(symbol)) = symbols.get(name) {
+            return symbol.clone();
+        }
+
+        let symbol = Symbol::new(name);
+        symbols.insert(Interned(symbol.clone()));
+        symbol
+    }
+
+    /// Returns the number of distinct symbols in this table.
+    pub fn len(&self) -> usize {
+        self.lock().len()
+    }
+
+    /// Returns `true` if no symbols have been interned in this table.
+    pub fn is_empty(&self) -> bool {
+        self.lock().is_empty()
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashSet<Interned>> {
+        // The set is never left in an inconsistent state, so a panic in another
+        // thread while holding the lock can be ignored.
+        self.symbols
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+#[test]
+fn test_symbol_table() {
+    use alloc::sync::Arc;
+
+    let table = SymbolTable::new();
+    assert!(table.is_empty());
+
+    let a = table.intern("System`Plus");
+    let b = table.intern("System`Plus");
+    let c = table.intern("Global`Plus");
+
+    assert_eq!(a, Symbol::new("System`Plus"));
+    assert_eq!(c, Symbol::new("Global`Plus"));
+    assert!(Arc::ptr_eq(&a.0, &b.0));
+    assert!(!Arc::ptr_eq(&a.0, &c.0));
+    assert_eq!(table.len(), 2);
+}
+
+#[test]
+fn test_symbol_table_threads() {
+    use alloc::sync::Arc;
+
+    let table = SymbolTable::new();
+
+    let symbols: Vec<Symbol> = std::thread::scope(|scope| {
+        let handles: Vec<_> = (0..4)
+            .map(|_| scope.spawn(|| table.intern("Global`x")))
+            .collect();
+
+        handles
+            .into_iter()
+            .map(|handle| handle.join().unwrap())
+            .collect()
+    });
+
+    assert!(symbols
+        .iter()
+        .all(|symbol| Arc::ptr_eq(&symbol.0, &symbols[0].0)));
+    assert_eq!(table.len(), 1);
+}
+
+#[test]
+#[should_panic(expected = "string is not parseable as a symbol: Plus")]
+fn test_symbol_table_invalid() {
+    SymbolTable::new().intern("Plus");
+}
@@ -1,4 +1,9 @@
-use crate::symbol::{ContextRef, RelativeContext, SymbolNameRef, SymbolRef};
+use crate::{
+    symbol::{
+        Context, ContextRef, RelativeContext, SymbolName, SymbolNameRef, SymbolRef,
+    },
+    Expr, Symbol,
+};
 
 /// `(input, is Symbol, is SymbolName, is Context, is RelativeContext)`
 #[rustfmt::skip]
@@ -37,6 +42,39 @@ const DATA: &[(&str, bool, bool, bool, bool)] = &[
     ("`foo`bar`",   false, false, false, true),
 ];
 
+/// Minimal deterministic pseudo-random number generator (xorshift64), so that
+/// randomized tests are reproducible without depending on a random number crate.
+pub(crate) struct Rng(pub u64);
+
+impl Rng {
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    /// A random number less than `n`.
+    pub fn below(&mut self, n: usize) -> usize {
+        (self.next_u64() % n as u64) as usize
+    }
+
+    /// A random element of `items`.
+    pub fn pick<T: Copy>(&mut self, items: &[T]) -> T {
+        items[self.below(items.len())]
+    }
+}
+
+/// The expression `f[f[...f[0]...]]`, with `depth` nested normal expressions, for
+/// checking that operations on deeply nested expressions don't overflow the stack.
+pub(crate) fn nested(depth: usize) -> Expr {
+    let mut expr = Expr::from(0);
+    for _ in 0..depth {
+        expr = Expr::normal(Symbol::new("Global`f"), vec![expr]);
+    }
+    expr
+}
+
 #[test]
 pub fn test_symbol_like_parsing() {
     for (input, is_symbol, is_symbol_name, is_context, is_rel_context) in
@@ -49,3 +87,580 @@ pub fn test_symbol_like_parsing() {
         assert_eq!(RelativeContext::try_new(input).is_some(), is_rel_context);
     }
 }
+
+#[test]
+fn test_symbol_split_from_parts() {
+    for (input, is_symbol, ..) in DATA {
+        if !is_symbol {
+            continue;
+        }
+
+        let symbol = Symbol::new(input);
+        let (context, name) = symbol.split();
+
+        assert_eq!(context.as_str(), symbol.context().as_str());
+        assert_eq!(name.as_str(), symbol.symbol_name().as_str());
+        assert_eq!(Symbol::from_parts(&context, &name), symbol);
+    }
+}
+
+#[test]
+fn test_symbol_with_context() {
+    let symbol = Symbol::new("Global`foo");
+
+    let moved = symbol.with_context(&Context::new("MyPkg`Private`Impl`"));
+    assert_eq!(moved.as_str(), "MyPkg`Private`Impl`foo");
+    assert_eq!(moved.context().as_str(), "MyPkg`Private`Impl`");
+    assert_eq!(moved.symbol_name().as_str(), "foo");
+
+    assert_eq!(
+        Symbol::new("A`B`$x1").with_context(&Context::system()),
+        Symbol::new("System`$x1")
+    );
+    assert_eq!(symbol.with_context(&Context::global()), symbol);
+}
+
+#[test]
+fn test_symbol_format_in() {
+    let global = Context::global();
+    let my_pkg = Context::new("MyPkg`");
+    let system_path = [Context::system()];
+    let package_path = [my_pkg.clone(), Context::system()];
+
+    let format =
+        |symbol: &str, path: &[Context]| Symbol::new(symbol).format_in(&global, path);
+
+    // Symbols in the current context.
+    assert_eq!(format("Global`x", &[]), "x");
+    assert_eq!(format("Global`x", &system_path), "x");
+    assert_eq!(format("Global`Plus", &system_path), "Plus");
+
+    // Symbols in the context path.
+    assert_eq!(format("System`Plus", &system_path), "Plus");
+    assert_eq!(format("MyPkg`f", &package_path), "f");
+
+    // Shadowing: `Plus` could refer to `MyPkg`Plus`, so the context is kept.
+    assert_eq!(format("System`Plus", &package_path), "System`Plus");
+
+    // Symbols in other contexts, including subcontexts of the current context.
+    assert_eq!(format("MyPkg`f", &system_path), "MyPkg`f");
+    assert_eq!(format("Global`Private`x", &system_path), "Global`Private`x");
+    assert_eq!(
+        Symbol::new("MyPkg`Private`x").format_in(&my_pkg, &[]),
+        "MyPkg`Private`x"
+    );
+}
+
+#[test]
+fn test_symbol_context_predicates() {
+    let my_pkg = Context::new("MyPkg`");
+
+    assert!(Symbol::new("System`Plus").is_system());
+    assert!(Symbol::new("Global`x").is_global());
+    assert!(Symbol::new("MyPkg`f").in_context(&my_pkg));
+    assert!(Symbol::new("System`Plus").in_context(&Context::system()));
+
+    // Subcontexts and parent contexts are different contexts.
+    for symbol in ["System`Private`x", "Global`System`x", "MyPkg`System`x"] {
+        assert!(!Symbol::new(symbol).is_system(), "{}", symbol);
+    }
+    for symbol in ["Global`Private`x", "System`Global`x", "MyPkg`Global`x"] {
+        assert!(!Symbol::new(symbol).is_global(), "{}", symbol);
+    }
+    assert!(!Symbol::new("MyPkg`Private`f").in_context(&my_pkg));
+    assert!(!Symbol::new("Other`MyPkg`f").in_context(&my_pkg));
+    assert!(!Symbol::new("MyPkg`f").in_context(&Context::new("MyPkg`f`")));
+}
+
+#[test]
+fn test_symbol_name_is() {
+    for symbol in ["System`Plus", "Global`Plus", "MyPkg`Private`Plus"] {
+        assert!(Symbol::new(symbol).name_is("Plus"), "{}", symbol);
+        assert!(!Symbol::new(symbol).name_is("Times"), "{}", symbol);
+        assert!(!Symbol::new(symbol).name_is(symbol), "{}", symbol);
+    }
+
+    assert!(!Symbol::new("System`Plus").name_is("Plu"));
+    assert!(!Symbol::new("System`Plus").name_is(""));
+}
+
+#[test]
+fn test_has_head_str() {
+    use crate::expr;
+
+    let plus = expr!(System::Plus[1, 2]);
+    assert!(plus.has_head_str("System`Plus"));
+    assert_eq!(
+        plus.has_head_str("System`Plus"),
+        plus.has_normal_head(&Symbol::new("System`Plus"))
+    );
+
+    // The context of the head is respected.
+    assert!(!plus.has_head_str("Plus"));
+    assert!(!plus.has_head_str("Global`Plus"));
+    assert!(!expr!(Global::Plus[1, 2]).has_head_str("System`Plus"));
+
+    // Atoms and normal expressions with non-symbol heads.
+    assert!(!expr!(System::Plus).has_head_str("System`Plus"));
+    assert!(!expr!("System`Plus").has_head_str("System`Plus"));
+    assert!(!expr!(System::Plus[1][2]).has_head_str("System`Plus"));
+}
+
+#[test]
+fn test_matches_head() {
+    use crate::expr;
+
+    let plus = SymbolRef::try_new("System`Plus").unwrap();
+
+    assert!(expr!(System::Plus[1, 2]).matches_head(plus));
+    assert!(expr!(System::Plus[]).matches_head(plus));
+    assert!(!expr!(Global::Plus[1, 2]).matches_head(plus));
+    assert!(!expr!(System::Times[1, 2]).matches_head(plus));
+    assert!(!expr!(System::Plus).matches_head(plus));
+    assert!(!expr!(System::Plus[1][2]).matches_head(plus));
+
+    let symbol = Symbol::new("System`Plus");
+    assert!(expr!(System::Plus[1, 2]).matches_head(symbol.as_symbol_ref()));
+}
+
+#[test]
+fn test_relative_context_resolve() {
+    let sub = RelativeContext::try_new("`Sub`").unwrap();
+    assert!(!sub.is_multi_level());
+    assert_eq!(
+        sub.resolve(&Context::new("MyPkg`")),
+        Context::new("MyPkg`Sub`")
+    );
+
+    let nested = RelativeContext::try_new("`Sub`Module`").unwrap();
+    assert!(nested.is_multi_level());
+    assert_eq!(
+        nested.resolve(&Context::new("MyPkg`Private`")),
+        Context::new("MyPkg`Private`Sub`Module`")
+    );
+    assert_eq!(
+        nested.resolve(&Context::global()).components().len(),
+        1 + nested.components().len()
+    );
+}
+
+#[test]
+fn test_context_parent_depth() {
+    let context = Context::new("MyPackage`Sub`Module`");
+    assert_eq!(context.depth(), 3);
+    assert_eq!(context.depth(), context.components().len());
+
+    let parent = context.parent().unwrap();
+    assert_eq!(parent.as_str(), "MyPackage`Sub`");
+    assert_eq!(parent.depth(), 2);
+
+    let grandparent = parent.parent().unwrap();
+    assert_eq!(grandparent.as_str(), "MyPackage`");
+    assert_eq!(grandparent.depth(), 1);
+    assert_eq!(grandparent.parent(), None);
+
+    assert_eq!(Context::system().parent(), None);
+    assert_eq!(Context::system().depth(), 1);
+}
+
+#[test]
+fn test_symbol_like_from_str() {
+    for (input, is_symbol, is_symbol_name, is_context, is_relative_context) in DATA {
+        assert_eq!(input.parse::<Symbol>().is_ok(), *is_symbol);
+        assert_eq!(input.parse::<SymbolName>().is_ok(), *is_symbol_name);
+        assert_eq!(input.parse::<Context>().is_ok(), *is_context);
+        assert_eq!(
+            input.parse::<RelativeContext>().is_ok(),
+            *is_relative_context
+        );
+    }
+
+    assert_eq!("System`Plus".parse(), Ok(Symbol::new("System`Plus")));
+
+    let message = |err: crate::symbol::SymbolParseError| err.to_string();
+
+    assert_eq!(
+        message("Foo".parse::<Context>().unwrap_err()),
+        r#"expected a context, got a symbol name: "Foo""#
+    );
+    assert_eq!(
+        message("`Foo`".parse::<SymbolName>().unwrap_err()),
+        r#"expected a symbol name, got a relative context: "`Foo`""#
+    );
+    assert_eq!(
+        message("a-b`c".parse::<Symbol>().unwrap_err()),
+        r#"expected a symbol, got invalid syntax: "a-b`c""#
+    );
+}
+
+#[test]
+fn test_association_lookup() {
+    let x = Expr::symbol(Symbol::new("Global`x"));
+
+    let assoc = Expr::association(vec![
+        (Expr::from("a"), Expr::from(1)),
+        (x.clone(), Expr::from(2)),
+        (Expr::from("a"), Expr::from(3)),
+    ]);
+
+    assert_eq!(
+        assoc.to_string(),
+        "System`Association[System`Rule[\"a\", 1], System`Rule[Global`x, 2], \
+         System`Rule[\"a\", 3]]"
+    );
+
+    // The last rule for a key takes precedence.
+    assert_eq!(
+        assoc.association_lookup(&Expr::from("a")),
+        Some(&Expr::from(3))
+    );
+    assert_eq!(assoc.association_lookup(&x), Some(&Expr::from(2)));
+    assert_eq!(assoc.association_lookup(&Expr::from("b")), None);
+
+    // RuleDelayed elements are also looked up.
+    let delayed =
+        Expr::normal(Symbol::new("System`Association"), vec![Expr::rule_delayed(
+            x.clone(),
+            Expr::from(4),
+        )]);
+    assert_eq!(delayed.association_lookup(&x), Some(&Expr::from(4)));
+
+    // Non-associations never contain a key.
+    let list = Expr::list(vec![Expr::rule(x.clone(), Expr::from(5))]);
+    assert_eq!(list.association_lookup(&x), None);
+    assert_eq!(x.association_lookup(&x), None);
+}
+
+#[test]
+fn test_normal_element_access() {
+    use crate::expr;
+
+    let mut normal = expr!(f[1, g[2], 3]).try_as_normal().unwrap().clone();
+
+    assert_eq!(normal.get(1), Some(&expr!(g[2])));
+    assert_eq!(normal.get(3), None);
+
+    *normal.get_mut(0).unwrap() = expr!(x);
+    assert!(normal.get_mut(3).is_none());
+
+    assert_eq!(normal.set(2, expr!("a")), Some(expr!(3)));
+    assert_eq!(normal.set(3, expr!(y)), None);
+
+    assert_eq!(Expr::from(normal), expr!(f[x, g[2], "a"]));
+}
+
+#[test]
+fn test_expr_elements_len() {
+    use crate::expr;
+
+    let expr = expr!(f[1, g[2, 3]][x]);
+    assert_eq!(expr.elements(), [expr!(x)]);
+    assert_eq!(expr.len(), 1);
+    assert!(!expr.is_empty());
+
+    assert!(expr!(f[]).elements().is_empty());
+    assert_eq!(expr!(f[]).len(), 0);
+    assert!(expr!(f[]).is_empty());
+
+    for atom in [expr!(1), expr!(2.5), expr!("ab"), expr!(x)] {
+        assert!(atom.elements().is_empty());
+        assert_eq!(atom.len(), 0);
+        assert!(atom.is_empty());
+    }
+}
+
+#[test]
+fn test_index() {
+    use crate::expr;
+
+    let expr = expr!(f[1, g[x, "a"]][y]);
+    assert_eq!(expr[0], expr!(y));
+
+    let normal = expr!(f[1, g[x, "a"]]).try_as_normal().unwrap().clone();
+    assert_eq!(normal[0], expr!(1));
+    assert_eq!(normal[1], expr!(g[x, "a"]));
+    assert_eq!(normal[1][1], expr!("a"));
+}
+
+#[test]
+#[should_panic(expected = "index out of bounds")]
+fn test_index_out_of_bounds() {
+    use crate::expr;
+
+    let normal = expr!(f[1, 2]).try_as_normal().unwrap().clone();
+    let _ = &normal[2];
+}
+
+#[test]
+#[should_panic(expected = "index out of bounds")]
+fn test_index_atom() {
+    let _ = &Expr::from(1)[0];
+}
+
+#[test]
+fn test_part() {
+    use crate::expr;
+
+    let expr = expr!(f[g[1, 2, 3], x][y]);
+
+    assert_eq!(expr.part(&[]), Some(&expr));
+    assert_eq!(expr.part(&[1]), Some(&expr!(y)));
+    assert_eq!(expr.part(&[0, 1, 2]), Some(&expr!(2)));
+
+    // Negative indices count from the end.
+    let head = expr.part(&[0]).unwrap();
+    assert_eq!(head.part(&[-1]), Some(&expr!(x)));
+    assert_eq!(head.part(&[-2]), Some(&expr!(g[1, 2, 3])));
+    assert_eq!(head.part(&[1, -3]), Some(&expr!(1)));
+    assert_eq!(head.part(&[1, -4]), None);
+    assert_eq!(head.part(&[i64::MIN]), None);
+
+    // Index 0 selects the head.
+    assert_eq!(head.part(&[0]), Some(&expr!(f)));
+    assert_eq!(head.part(&[-2, 0]), Some(&expr!(g)));
+    assert_eq!(expr.part(&[0, 0]), Some(&expr!(f)));
+
+    // Out of range, or indexing into an atom.
+    assert_eq!(head.part(&[3]), None);
+    assert_eq!(head.part(&[i64::MAX]), None);
+    assert_eq!(head.part(&[2, 1]), None);
+    assert_eq!(head.part(&[0, 0]), None);
+    assert_eq!(expr!(1).part(&[1]), None);
+    assert_eq!(expr!(f[]).part(&[-1]), None);
+}
+
+#[test]
+fn test_normal_elements_mut() {
+    use crate::{expr, ExprKind};
+
+    // A unique expression is modified in place.
+    let mut expr = expr!(f[1, g[2]]);
+    let ptr = alloc::sync::Arc::as_ptr(&expr.inner);
+    let ExprKind::Normal(normal) = expr.kind_mut() else {
+        panic!("expected a normal expression");
+    };
+    normal.elements_mut()[0] = expr!(x);
+    normal.push(expr!("a"));
+    assert_eq!(expr, expr!(f[x, g[2], "a"]));
+    assert_eq!(alloc::sync::Arc::as_ptr(&expr.inner), ptr);
+
+    // A shared expression is copied, and the other reference is unchanged.
+    let original = expr!(f[1, g[2]]);
+    let mut expr = original.clone();
+    let ExprKind::Normal(normal) = expr.kind_mut() else {
+        panic!("expected a normal expression");
+    };
+    for elem in normal.elements_mut() {
+        *elem = expr!(h[#elem]);
+    }
+    assert_eq!(expr, expr!(f[h[1], h[g[2]]]));
+    assert_eq!(original, expr!(f[1, g[2]]));
+}
+
+#[test]
+#[cfg(not(feature = "smallvec"))]
+fn test_normal_clone_shares_elements() {
+    use crate::expr;
+
+    let original = expr!(f[1, g[2]]).try_as_normal().unwrap().clone();
+
+    let mut normal = original.clone();
+    assert_eq!(normal.elements().as_ptr(), original.elements().as_ptr());
+
+    // Mutating a clone copies the elements, and the original is unchanged.
+    normal.elements_mut()[1] = expr!(x);
+    assert_ne!(normal.elements().as_ptr(), original.elements().as_ptr());
+    assert_eq!(normal.elements(), [expr!(1), expr!(x)]);
+    assert_eq!(original.elements(), [expr!(1), expr!(g[2])]);
+
+    // Unique elements are mutated in place.
+    let ptr = normal.elements().as_ptr();
+    normal.elements_mut()[0] = expr!(y);
+    assert_eq!(normal.elements().as_ptr(), ptr);
+    assert_eq!(normal.into_elements(), vec![expr!(y), expr!(x)]);
+}
+
+#[test]
+#[cfg(feature = "smallvec")]
+fn test_normal_inline_elements() {
+    use crate::expr;
+
+    let mut normal = expr!(f[1, 2, 3, 4]).try_as_normal().unwrap().clone();
+    assert!(!normal.contents.spilled());
+
+    normal.push(expr!(5));
+    assert!(normal.contents.spilled());
+    assert_eq!(
+        normal.into_elements(),
+        (1..=5).map(Expr::from).collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn test_drop_deeply_nested() {
+    drop(nested(100_000));
+
+    // Deeply nested heads, e.g. f[1][1][1]...
+    let mut expr = Expr::symbol(Symbol::new("Global`f"));
+    for _ in 0..100_000 {
+        expr = Expr::normal(expr, vec![Expr::from(1)]);
+    }
+    drop(expr);
+
+    // Elements shared between clones of a normal expression.
+    let expr = nested(100_000);
+    let normal = expr.try_as_normal().unwrap().clone();
+    drop(expr);
+    drop(normal);
+}
+
+#[test]
+fn test_drop_shared_subexpression() {
+    let shared = Expr::list(vec![Expr::list(vec![Expr::from(1)])]);
+
+    let mut expr = shared.clone();
+    for _ in 0..10 {
+        expr = Expr::list(vec![expr, shared.clone()]);
+    }
+    drop(expr);
+
+    assert_eq!(shared.ref_count(), 1);
+    assert_eq!(shared, Expr::list(vec![Expr::list(vec![Expr::from(1)])]));
+}
+
+#[test]
+fn test_expr_eq_primitives() {
+    use crate::expr;
+
+    // Integers.
+    assert!(expr!(5) == 5);
+    assert!(expr!(-5) == -5);
+    assert!(expr!(5) != 6);
+    assert!(expr!(5) != 5.0);
+    assert!(expr!(5) != "5");
+
+    // Reals.
+    assert!(expr!(2.5) == 2.5);
+    assert!(Expr::real(-0.0) == 0.0);
+    assert!(expr!(5.0) != 5);
+    assert!(expr!(2.5) != "2.5");
+
+    // Strings.
+    assert!(expr!("hello") == "hello");
+    assert!(expr!("hello") == *"hello");
+    assert!(expr!("hello") != "world");
+    assert!(expr!("5") != 5);
+
+    // Symbols and normal expressions are not equal to primitive values.
+    assert!(expr!(x) != "Global`x");
+    assert!(expr!(x) != "x");
+    assert!(expr!(f[5]) != 5);
+    assert!(expr!(System::List["a"]) != "a");
+
+    #[cfg(feature = "bigint")]
+    assert!(Expr::big_integer(crate::BigInt::from(i64::MAX) + 1u8) != i64::MIN);
+}
+
+#[test]
+fn test_try_real() {
+    use crate::{NanError, Number};
+
+    assert_eq!(Expr::try_real(-1.5), Ok(Expr::real(-1.5)));
+    assert_eq!(Expr::try_real(f64::INFINITY), Ok(Expr::real(f64::INFINITY)));
+    assert_eq!(Expr::try_real(f64::NAN), Err(NanError));
+
+    assert_eq!(Number::try_real(0.0), Ok(Number::real(0.0)));
+    assert_eq!(Number::try_real(-f64::NAN), Err(NanError));
+
+    assert_eq!(NanError.to_string(), "real number is NaN");
+}
+
+#[test]
+fn test_normal_display() {
+    use crate::expr;
+
+    assert_eq!(expr!(f[]).to_string(), "Global`f[]");
+    assert_eq!(expr!(f[a]).to_string(), "Global`f[Global`a]");
+    assert_eq!(
+        expr!(f[a, b, c]).to_string(),
+        "Global`f[Global`a, Global`b, Global`c]"
+    );
+    assert_eq!(
+        expr!(f[g[], h[1, 2]]).to_string(),
+        "Global`f[Global`g[], Global`h[1, 2]]"
+    );
+
+    // Normal expressions as heads.
+    assert_eq!(expr!(g[x][y]).to_string(), "Global`g[Global`x][Global`y]");
+    assert_eq!(expr!(g[][][]).to_string(), "Global`g[][][]");
+    assert_eq!(
+        expr!(f[a][b, c][]).to_string(),
+        "Global`f[Global`a][Global`b, Global`c][]"
+    );
+}
+
+#[test]
+fn test_real_display() {
+    assert_eq!(Expr::real(2.0).to_string(), "2.0");
+    assert_eq!(Expr::real(-0.25).to_string(), "-0.25");
+    assert_eq!(Expr::real(1e-15).to_string(), "1.*^-15");
+    assert_eq!(Expr::real(-2.5e30).to_string(), "-2.5*^30");
+    assert_eq!(Expr::real(f64::MAX).to_string(), "1.7976931348623157*^308");
+    assert_eq!(Expr::real(f64::INFINITY).to_string(), "inf");
+}
+
+#[test]
+fn test_number_to_f64_to_i64() {
+    use crate::Number;
+
+    assert_eq!(Number::Integer(7).to_f64(), 7.0);
+    assert_eq!(Number::real(-0.25).to_f64(), -0.25);
+    assert_eq!(Number::real(f64::INFINITY).to_f64(), f64::INFINITY);
+    // Rounded to the nearest `f64`.
+    assert_eq!(Number::Integer((1 << 53) + 1).to_f64(), 9007199254740992.0);
+
+    assert_eq!(Number::Integer(i64::MIN).to_i64(), Some(i64::MIN));
+    assert_eq!(Number::real(-4.0).to_i64(), Some(-4));
+    assert_eq!(Number::real(-0.0).to_i64(), Some(0));
+    assert_eq!(Number::real(i64::MIN as f64).to_i64(), Some(i64::MIN));
+
+    // Non-integral reals are not rounded or truncated.
+    for real in [0.5, -0.5, 2.000001, 1e-300, -1234.75] {
+        assert_eq!(Number::real(real).to_i64(), None, "{}", real);
+    }
+
+    // Out of range, including 2^63, the smallest `f64` greater than `i64::MAX`.
+    for real in [
+        9223372036854775808.0,
+        -1e19,
+        f64::INFINITY,
+        f64::NEG_INFINITY,
+    ] {
+        assert_eq!(Number::real(real).to_i64(), None, "{}", real);
+    }
+
+    #[cfg(feature = "bigint")]
+    {
+        let big = Number::BigInteger(crate::BigInt::from(i64::MAX) + 1u8);
+        assert_eq!(big.to_i64(), None);
+        assert_eq!(big.to_f64(), 9223372036854775808.0);
+        assert_eq!(Number::BigInteger(crate::BigInt::from(5)).to_i64(), Some(5));
+    }
+
+    #[cfg(feature = "rational")]
+    {
+        assert_eq!(Number::rational(1, 4).to_i64(), None);
+        assert_eq!(Number::rational(1, 4).to_f64(), 0.25);
+    }
+
+    #[cfg(feature = "bigreal")]
+    {
+        let big_real =
+            |input: &str| Number::BigReal(crate::BigReal::parse(input).unwrap());
+
+        assert_eq!(big_real("12.`30").to_i64(), Some(12));
+        assert_eq!(big_real("-12.5`30").to_i64(), None);
+        assert_eq!(big_real("1.`30*^30").to_i64(), None);
+        assert_eq!(big_real("-12.5`30").to_f64(), -12.5);
+    }
+}
@@ -0,0 +1,667 @@
+//! Operations that traverse an expression and transform its subexpressions.
+
+use alloc::{vec, vec::Vec};
+
+use crate::{Expr, ExprKind, Normal};
+
+
+/// Wolfram Language [level specification][ref/LevelSpecification].
+///
+/// The *level* of a subexpression is the number of indices needed to specify it. The
+/// whole expression is at level 0, its elements are at level 1, the elements of those
+/// elements are at level 2, and so on. Heads are not part of any level.
+///
+/// [ref/LevelSpecification]: https://reference.wolfram.com/language/tutorial/LevelsInExpressions.html
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum LevelSpec {
+    /// `n` — levels `1` through `n`.
+    UpTo(usize),
+    /// `{n}` — level `n` only.
+    Exactly(usize),
+    /// `{min, max}` — levels `min` through `max`.
+    Between(usize, usize),
+    /// `Infinity` — levels `1` and greater.
+    Infinity,
+}
+
+impl LevelSpec {
+    /// Returns `true` if `level` is included by this level specification.
+    pub fn contains(&self, level: usize) -> bool {
+        match *self {
+            LevelSpec::UpTo(max) => 1 <= level && level <= max,
+            LevelSpec::Exactly(n) => level == n,
+            LevelSpec::Between(min, max) => min <= level && level <= max,
+            LevelSpec::Infinity => 1 <= level,
+        }
+    }
+
+    /// The deepest level included by this level specification, or `None` if there is
+    /// no upper bound.
+    fn max_level(&self) -> Option<usize> {
+        match *self {
+            LevelSpec::UpTo(max)
+            | LevelSpec::Exactly(max)
+            | LevelSpec::Between(_, max) => Some(max),
+            LevelSpec::Infinity => None,
+        }
+    }
+}
+
+/// Action returned by the closure passed to [`Expr::transform()`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Transform {
+    /// Use this expression in place of the subexpression, without descending into it.
+    Replace(Expr),
+    /// Keep the head of the subexpression, and transform each of its elements.
+    Descend,
+    /// Keep the subexpression unchanged, without descending into it.
+    Keep,
+}
+
+impl Expr {
+    /// Apply `f` to each subexpression at the levels specified by `levelspec`.
+    ///
+    /// This is equivalent to
+    /// [`Map`](https://reference.wolfram.com/language/ref/Map.html)<sub>WL</sub>
+    /// with an explicit level specification. Subexpressions are transformed
+    /// bottom-up: `f` is applied to a subexpression after its own elements have been
+    /// transformed.
+    ///
+    /// # Example
+    ///
+    /// Compute `Map[f, {{1, 2}, 3}, {2}]`:
+    ///
+    /// ```
+    /// use wolfram_expr::{Expr, LevelSpec, Symbol};
+    ///
+    /// let expr = Expr::list(vec![
+    ///     Expr::list(vec![Expr::from(1), Expr::from(2)]),
+    ///     Expr::from(3),
+    /// ]);
+    ///
+    /// let result = expr.map_at_levels(LevelSpec::Exactly(2), |e| {
+    ///     Expr::normal(Symbol::new("Global`f"), vec![e.clone()])
+    /// });
+    ///
+    /// assert_eq!(
+    ///     result.to_string(),
+    ///     "System`List[System`List[Global`f[1], Global`f[2]], 3]"
+    /// );
+    /// ```
+    pub fn map_at_levels<F: FnMut(&Expr) -> Expr>(
+        &self,
+        levelspec: LevelSpec,
+        mut f: F,
+    ) -> Expr {
+        map_at_levels(self, 0, &levelspec, &mut f)
+    }
+
+    /// Apply `f` to each element of this expression, collecting the results into the
+    /// elements of a new expression with the same head.
+    ///
+    /// This is equivalent to
+    /// [`Map`](https://reference.wolfram.com/language/ref/Map.html)<sub>WL</sub> at
+    /// level 1. The head of the new expression is shared with `self`. If `self` is not
+    /// a [`Normal`] expression, a clone of `self` is returned.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use wolfram_expr::{expr, Expr, Symbol};
+    ///
+    /// let g = |elem: &Expr| Expr::normal(Symbol::new("Global`g"), vec![elem.clone()]);
+    ///
+    /// assert_eq!(expr!(f[1, x]).map_elements(g), expr!(f[g[1], g[x]]));
+    /// assert_eq!(expr!(x).map_elements(g), expr!(x));
+    /// ```
+    pub fn map_elements<F: FnMut(&Expr) -> Expr>(&self, f: F) -> Expr {
+        let ExprKind::Normal(normal) = self.kind() else {
+            return self.clone();
+        };
+
+        let elements = normal.elements().iter().map(f).collect();
+
+        Expr::from(Normal::new(normal.head().clone(), elements))
+    }
+
+    /// Apply `f` to each element of this expression, splicing the returned expressions
+    /// into the elements of a new expression with the same head.
+    ///
+    /// Unlike a one-to-one map, `f` can replace an element with any number of new
+    /// elements, including none. If `self` is not a [`Normal`] expression, a clone of
+    /// `self` is returned.
+    ///
+    /// # Example
+    ///
+    /// Splice the contents of `Sequence[...]` elements into the parent expression:
+    ///
+    /// ```
+    /// use wolfram_expr::{expr, Expr, Symbol};
+    ///
+    /// let expr = expr!(f[1, System::Sequence[2, 3], System::Sequence[], 4]);
+    ///
+    /// let spliced = expr.flat_map_elements(|elem| match elem.try_as_normal() {
+    ///     Some(seq) if seq.has_head(&Symbol::new("System`Sequence")) => seq.elements().to_vec(),
+    ///     _ => vec![elem.clone()],
+    /// });
+    ///
+    /// assert_eq!(spliced, expr!(f[1, 2, 3, 4]));
+    /// ```
+    pub fn flat_map_elements<I, F>(&self, f: F) -> Expr
+    where
+        I: IntoIterator<Item = Expr>,
+        F: FnMut(&Expr) -> I,
+    {
+        let ExprKind::Normal(normal) = self.kind() else {
+            return self.clone();
+        };
+
+        let elements = normal.elements().iter().flat_map(f).collect();
+
+        Expr::from(Normal::new(normal.head().clone(), elements))
+    }
+
+    /// Apply a fallible function `f` to each element of this expression, collecting the
+    /// results into the elements of a new expression with the same head.
+    ///
+    /// Returns the first error returned by `f`, without calling `f` on the remaining
+    /// elements. If `self` is not a [`Normal`] expression, a clone of `self` is
+    /// returned.
+    ///
+    /// # Example
+    ///
+    /// Double each element, failing if any element is not an integer:
+    ///
+    /// ```
+    /// use wolfram_expr::{expr, Expr, ExprKind};
+    ///
+    /// let double = |elem: &Expr| match elem.kind() {
+    ///     ExprKind::Integer(int) => Ok(Expr::from(int * 2)),
+    ///     _ => Err(format!("not an integer: {}", elem)),
+    /// };
+    ///
+    /// assert_eq!(expr!(f[1, 2]).try_map_elements(double), Ok(expr!(f[2, 4])));
+    /// assert_eq!(
+    ///     expr!(f[1, x, "a"]).try_map_elements(double),
+    ///     Err("not an integer: Global`x".to_owned())
+    /// );
+    /// ```
+    pub fn try_map_elements<E, F>(&self, f: F) -> Result<Expr, E>
+    where
+        F: FnMut(&Expr) -> Result<Expr, E>,
+    {
+        let ExprKind::Normal(normal) = self.kind() else {
+            return Ok(self.clone());
+        };
+
+        let elements = normal.elements().iter().map(f).collect::<Result<_, E>>()?;
+
+        Ok(Expr::from(Normal::new(normal.head().clone(), elements)))
+    }
+
+    /// Transform this expression top-down, using `f` to decide how to handle each
+    /// subexpression.
+    ///
+    /// `f` is first called on `self`, and then on the elements of each subexpression for
+    /// which it returned [`Transform::Descend`]. Subexpressions for which `f` returns
+    /// [`Transform::Replace`] or [`Transform::Keep`] are not descended into, so `f` can
+    /// prune parts of the expression it doesn't need to visit. Heads are never
+    /// visited.
+    ///
+    /// Subexpressions that contain no replacements are shared with `self` instead of
+    /// being rebuilt.
+    ///
+    /// # Example
+    ///
+    /// Replace `x` with `1`, except inside `Hold[...]`:
+    ///
+    /// ```
+    /// use wolfram_expr::{expr, Expr, Symbol, Transform};
+    ///
+    /// let expr = expr!(f[x, System::Hold[x], g[x]]);
+    ///
+    /// let result = expr.transform(|e| {
+    ///     if *e == expr!(x) {
+    ///         Transform::Replace(expr!(1))
+    ///     } else if e.has_normal_head(&Symbol::new("System`Hold")) {
+    ///         Transform::Keep
+    ///     } else {
+    ///         Transform::Descend
+    ///     }
+    /// });
+    ///
+    /// assert_eq!(result, expr!(f[1, System::Hold[x], g[1]]));
+    /// ```
+    pub fn transform<F: FnMut(&Expr) -> Transform>(&self, mut f: F) -> Expr {
+        transform(self, &mut f).unwrap_or_else(|| self.clone())
+    }
+
+    /// Mutate each subexpression of this expression in place, including heads.
+    ///
+    /// `f` is called on each subexpression before descending into it, so changes `f`
+    /// makes to a subexpression are visible when its contents are visited.
+    ///
+    /// Subexpressions are accessed using [`Expr::kind_mut()`]: uniquely owned
+    /// subexpressions are modified in place, and shared subexpressions are cloned
+    /// before being modified, leaving other references to them unchanged.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use wolfram_expr::{expr, ExprKind};
+    ///
+    /// let mut expr = expr!(f[1, g[2, x]]);
+    ///
+    /// expr.walk_mut(|kind| {
+    ///     if let ExprKind::Integer(int) = kind {
+    ///         *int *= 10;
+    ///     }
+    /// });
+    ///
+    /// assert_eq!(expr, expr!(f[10, g[20, x]]));
+    /// ```
+    pub fn walk_mut<F: FnMut(&mut ExprKind)>(&mut self, mut f: F) {
+        walk_mut(self, &mut f)
+    }
+
+    /// Iterate over every subexpression of this expression, in depth-first pre-order.
+    ///
+    /// The iterator yields `self`, and then, if `self` is a [`Normal`] expression, the
+    /// subexpressions of its head, followed by the subexpressions of each element in
+    /// turn. Heads are included, so every atom in the expression is yielded exactly
+    /// once, like in
+    /// [`Level[expr, {0, Infinity}, Heads -> True]`](https://reference.wolfram.com/language/ref/Level.html)<sub>WL</sub>
+    /// (which uses post-order instead).
+    ///
+    /// The iterator uses an explicit stack, so it will not overflow the call stack on
+    /// deeply nested expressions.
+    ///
+    /// # Example
+    ///
+    /// Collect the symbols in an expression:
+    ///
+    /// ```
+    /// use wolfram_expr::{expr, Symbol};
+    ///
+    /// let expr = expr!(f[x, g[y]]);
+    ///
+    /// let symbols: Vec<&Symbol> = expr
+    ///     .iter_subexpressions()
+    ///     .filter_map(|e| e.try_as_symbol())
+    ///     .collect();
+    ///
+    /// assert_eq!(symbols, [
+    ///     &Symbol::new("Global`f"),
+    ///     &Symbol::new("Global`x"),
+    ///     &Symbol::new("Global`g"),
+    ///     &Symbol::new("Global`y"),
+    /// ]);
+    /// ```
+    pub fn iter_subexpressions(&self) -> impl Iterator<Item = &Expr> {
+        let mut stack = vec![self];
+
+        core::iter::from_fn(move || {
+            let expr = stack.pop()?;
+
+            if let ExprKind::Normal(normal) = expr.kind() {
+                stack.extend(normal.elements().iter().rev());
+                stack.push(normal.head());
+            }
+
+            Some(expr)
+        })
+    }
+}
+
+fn walk_mut<F: FnMut(&mut ExprKind)>(expr: &mut Expr, f: &mut F) {
+    let kind = expr.kind_mut();
+
+    f(kind);
+
+    if let ExprKind::Normal(normal) = kind {
+        walk_mut(&mut normal.head, f);
+
+        for elem in normal.elements_mut() {
+            walk_mut(elem, f);
+        }
+    }
+}
+
+/// Returns `None` if `expr` is unchanged by `f`.
+fn transform<F: FnMut(&Expr) -> Transform>(expr: &Expr, f: &mut F) -> Option<Expr> {
+    match f(expr) {
+        Transform::Replace(replacement) => Some(replacement),
+        Transform::Keep => None,
+        Transform::Descend => {
+            let ExprKind::Normal(normal) = expr.kind() else {
+                return None;
+            };
+
+            let elements: Vec<Option<Expr>> = normal
+                .elements()
+                .iter()
+                .map(|elem| transform(elem, f))
+                .collect();
+
+            if elements.iter().all(Option::is_none) {
+                return None;
+            }
+
+            let elements = elements
+                .into_iter()
+                .zip(normal.elements())
+                .map(|(new, old)| new.unwrap_or_else(|| old.clone()))
+                .collect();
+
+            Some(Expr::from(Normal::new(normal.head().clone(), elements)))
+        },
+    }
+}
+
+fn map_at_levels<F: FnMut(&Expr) -> Expr>(
+    expr: &Expr,
+    level: usize,
+    levelspec: &LevelSpec,
+    f: &mut F,
+) -> Expr {
+    let descend = match levelspec.max_level() {
+        Some(max) => level < max,
+        None => true,
+    };
+
+    let expr = match expr.kind() {
+        ExprKind::Normal(normal) if descend => {
+            let elements = normal
+                .elements()
+                .iter()
+                .map(|elem| map_at_levels(elem, level + 1, levelspec, f))
+                .collect();
+
+            Expr::from(Normal::new(normal.head().clone(), elements))
+        },
+        _ => expr.clone(),
+    };
+
+    if levelspec.contains(level) {
+        f(&expr)
+    } else {
+        expr
+    }
+}
+
+#[test]
+fn test_map_at_levels() {
+    use crate::Symbol;
+
+    let f = |e: &Expr| Expr::normal(Symbol::new("Global`f"), vec![e.clone()]);
+    let a = Expr::symbol(Symbol::new("Global`a"));
+    let b = Expr::symbol(Symbol::new("Global`b"));
+    let c = Expr::symbol(Symbol::new("Global`c"));
+
+    // {{a, b}, c}
+    let expr = Expr::list(vec![Expr::list(vec![a, b]), c]);
+
+    // Map[f, {{a, b}, c}, {0}]
+    assert_eq!(
+        expr.map_at_levels(LevelSpec::Exactly(0), f).to_string(),
+        "Global`f[System`List[System`List[Global`a, Global`b], Global`c]]"
+    );
+
+    // Map[f, {{a, b}, c}, 1]
+    assert_eq!(
+        expr.map_at_levels(LevelSpec::UpTo(1), f).to_string(),
+        "System`List[Global`f[System`List[Global`a, Global`b]], Global`f[Global`c]]"
+    );
+
+    // Map[f, {{a, b}, c}, 2]
+    assert_eq!(
+        expr.map_at_levels(LevelSpec::UpTo(2), f).to_string(),
+        "System`List[Global`f[System`List[Global`f[Global`a], Global`f[Global`b]]], \
+         Global`f[Global`c]]"
+    );
+
+    // Map[f, {{a, b}, c}, {2}]
+    assert_eq!(
+        expr.map_at_levels(LevelSpec::Exactly(2), f).to_string(),
+        "System`List[System`List[Global`f[Global`a], Global`f[Global`b]], Global`c]"
+    );
+
+    // Map[f, {{a, b}, c}, {0, 1}]
+    assert_eq!(
+        expr.map_at_levels(LevelSpec::Between(0, 1), f).to_string(),
+        "Global`f[System`List[Global`f[System`List[Global`a, Global`b]], \
+         Global`f[Global`c]]]"
+    );
+
+    // Map[f, {{a, b}, c}, Infinity]
+    assert_eq!(
+        expr.map_at_levels(LevelSpec::Infinity, f).to_string(),
+        "System`List[Global`f[System`List[Global`f[Global`a], Global`f[Global`b]]], \
+         Global`f[Global`c]]"
+    );
+
+    // Map[f, {{a, b}, c}, {3}]
+    assert_eq!(expr.map_at_levels(LevelSpec::Exactly(3), f), expr);
+}
+
+#[test]
+fn test_map_at_levels_skips_heads() {
+    use crate::Symbol;
+
+    let f = |e: &Expr| Expr::normal(Symbol::new("Global`f"), vec![e.clone()]);
+
+    // g[x][y]
+    let expr = Expr::normal(
+        Expr::normal(Symbol::new("Global`g"), vec![Expr::from(1)]),
+        vec![Expr::from(2)],
+    );
+
+    // Map[f, g[x][y], Infinity]
+    assert_eq!(
+        expr.map_at_levels(LevelSpec::Infinity, f).to_string(),
+        "Global`g[1][Global`f[2]]"
+    );
+}
+
+#[test]
+fn test_map_elements() {
+    use alloc::sync::Arc;
+
+    use crate::{expr, Symbol};
+
+    let g = |elem: &Expr| Expr::normal(Symbol::new("Global`g"), vec![elem.clone()]);
+
+    let expr = expr!(f[1, "a", h[x]]);
+    let mapped = expr.map_elements(g);
+    assert_eq!(mapped, expr!(f[g[1], g["a"], g[h[x]]]));
+    assert_eq!(expr!(f[]).map_elements(g), expr!(f[]));
+
+    // The head is shared, not cloned.
+    let head = |expr: &Expr| expr.try_as_normal().unwrap().head().clone();
+    let expr = expr!(f[1][2]);
+    assert!(Arc::ptr_eq(
+        &head(&expr).inner,
+        &head(&expr.map_elements(g)).inner
+    ));
+
+    // Atoms are returned unchanged, without calling `f`.
+    for atom in [expr!(x), expr!(1), expr!(2.5), expr!("a")] {
+        let result = atom.map_elements(|_| unreachable!());
+        assert!(Arc::ptr_eq(&result.inner, &atom.inner));
+    }
+}
+
+#[test]
+fn test_flat_map_elements() {
+    use crate::expr;
+
+    let expr = expr!(f[1, 2, 3]);
+
+    assert_eq!(
+        expr.flat_map_elements(|elem| vec![elem.clone(), elem.clone()]),
+        expr!(f[1, 1, 2, 2, 3, 3])
+    );
+    assert_eq!(
+        expr.flat_map_elements(|elem| Some(elem.clone()).filter(|e| *e != Expr::from(2))),
+        expr!(f[1, 3])
+    );
+    assert_eq!(expr.flat_map_elements(|_| None), expr!(f[]));
+
+    // Atoms are returned unchanged.
+    assert_eq!(expr!(x).flat_map_elements(|_| None), expr!(x));
+}
+
+#[test]
+fn test_try_map_elements() {
+    use crate::{expr, Symbol};
+
+    let g = |elem: &Expr| Expr::normal(Symbol::new("Global`g"), vec![elem.clone()]);
+
+    let mut calls = 0;
+    let result = expr!(f[1, x, 2, y]).try_map_elements(|elem| {
+        calls += 1;
+        match elem.kind() {
+            ExprKind::Symbol(_) => Err(elem.clone()),
+            _ => Ok(g(elem)),
+        }
+    });
+
+    // `f` is not called after the first error.
+    assert_eq!(result, Err(expr!(x)));
+    assert_eq!(calls, 2);
+
+    assert_eq!(
+        expr!(f[1, 2][3]).try_map_elements(|elem| Ok::<_, ()>(g(elem))),
+        Ok(expr!(f[1, 2][g[3]]))
+    );
+    assert_eq!(expr!(f[]).try_map_elements(|_| Err(())), Ok(expr!(f[])));
+
+    // Atoms are returned unchanged.
+    assert_eq!(expr!(x).try_map_elements(|_| Err(())), Ok(expr!(x)));
+}
+
+#[test]
+fn test_transform() {
+    use crate::expr;
+
+    let expr = expr!(f[x, g[x, h[x]], x[x]]);
+
+    // Heads are not visited.
+    let replace_x = |e: &Expr| {
+        if *e == expr!(x) {
+            Transform::Replace(expr!(1))
+        } else {
+            Transform::Descend
+        }
+    };
+    assert_eq!(expr.transform(replace_x), expr!(f[1, g[1, h[1]], x[1]]));
+
+    // Pruned subexpressions are not visited.
+    let mut visited = Vec::new();
+    let result = expr.transform(|e| {
+        visited.push(e.clone());
+        if e.has_normal_head(&crate::Symbol::new("Global`g")) {
+            Transform::Keep
+        } else {
+            Transform::Descend
+        }
+    });
+    assert_eq!(result, expr);
+    assert_eq!(visited, [
+        expr.clone(),
+        expr!(x),
+        expr!(g[x, h[x]]),
+        expr!(x[x]),
+        expr!(x)
+    ]);
+
+    // The whole expression can be replaced.
+    assert_eq!(expr.transform(|_| Transform::Replace(expr!(y))), expr!(y));
+
+    // Unchanged subexpressions are shared.
+    let expr = expr!(f[g[1, 2], x]);
+    let result = expr.transform(replace_x);
+    assert_eq!(result, expr!(f[g[1, 2], 1]));
+    assert!(alloc::sync::Arc::ptr_eq(
+        &result.normal_part(0).unwrap().inner,
+        &expr.normal_part(0).unwrap().inner
+    ));
+}
+
+#[test]
+fn test_walk_mut() {
+    use alloc::sync::Arc;
+
+    use crate::{expr, Symbol};
+
+    // Heads are visited, after the expression containing them.
+    let mut expr = expr!(f[g[x]][x]);
+    let mut visited = Vec::new();
+    expr.walk_mut(|kind| {
+        visited.push(Expr::new(kind.clone()));
+        if let ExprKind::Symbol(sym) = kind {
+            if sym.as_str() == "Global`x" {
+                *sym = Symbol::new("Global`y");
+            }
+        }
+    });
+    assert_eq!(expr, expr!(f[g[y]][y]));
+    assert_eq!(visited, [
+        expr!(f[g[x]][x]),
+        expr!(f[g[x]]),
+        expr!(f),
+        expr!(g[x]),
+        expr!(g),
+        expr!(x),
+        expr!(x)
+    ]);
+
+    // Uniquely owned subexpressions are modified in place.
+    let mut expr = expr!(f[g[1]]);
+    let inner = Arc::as_ptr(&expr.normal_part(0).unwrap().inner);
+    expr.walk_mut(|kind| {
+        if let ExprKind::Integer(int) = kind {
+            *int += 1;
+        }
+    });
+    assert_eq!(expr, expr!(f[g[2]]));
+    assert_eq!(Arc::as_ptr(&expr.normal_part(0).unwrap().inner), inner);
+
+    // Shared subexpressions are cloned before being modified.
+    let shared = expr!(g[1]);
+    let mut expr = Expr::normal(Symbol::new("Global`f"), vec![shared.clone()]);
+    expr.walk_mut(|kind| {
+        if let ExprKind::Integer(int) = kind {
+            *int += 1;
+        }
+    });
+    assert_eq!(expr, expr!(f[g[2]]));
+    assert_eq!(shared, expr!(g[1]));
+}
+
+#[test]
+fn test_iter_subexpressions() {
+    use crate::expr;
+
+    let expr = expr!(f[1, g[x, "a"]][h[]]);
+
+    assert_eq!(expr.iter_subexpressions().cloned().collect::<Vec<_>>(), [
+        expr!(f[1, g[x, "a"]][h[]]),
+        expr!(f[1, g[x, "a"]]),
+        expr!(f),
+        expr!(1),
+        expr!(g[x, "a"]),
+        expr!(g),
+        expr!(x),
+        expr!("a"),
+        expr!(h[]),
+        expr!(h),
+    ]);
+
+    assert_eq!(expr!(x).iter_subexpressions().collect::<Vec<_>>(), [
+        &expr!(x)
+    ]);
+
+    let deep = crate::tests::nested(100_000);
+    assert_eq!(deep.iter_subexpressions().count(), 200_001);
+}
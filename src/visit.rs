@@ -0,0 +1,385 @@
+//! Traits for writing passes over an expression.
+//!
+//! [`Visitor`] traverses an expression without modifying it, and [`Transformer`]
+//! constructs a new expression from an existing one. Implementors override the methods
+//! for the kinds of expressions they are interested in, and inherit a default
+//! traversal of everything else.
+//!
+//! # Examples
+//!
+//! Collect every string in an expression:
+//!
+//! ```
+//! use wolfram_expr::{expr, visit::Visitor};
+//!
+//! #[derive(Default)]
+//! struct Strings(Vec<String>);
+//!
+//! impl Visitor for Strings {
+//!     fn visit_string(&mut self, string: &str) {
+//!         self.0.push(string.to_owned());
+//!     }
+//! }
+//!
+//! let mut strings = Strings::default();
+//! strings.visit_expr(&expr!(f["a", g["b", 1]]["c"]));
+//!
+//! assert_eq!(strings.0, ["a", "b", "c"]);
+//! ```
+//!
+//! Fold `Plus` applied to integers into a single integer:
+//!
+//! ```
+//! use wolfram_expr::{expr, visit::Transformer, Expr, Normal, Symbol};
+//!
+//! struct FoldPlus;
+//!
+//! impl Transformer for FoldPlus {
+//!     fn transform_normal(&mut self, normal: &Normal) -> Option<Expr> {
+//!         // Fold the elements first, so that nested sums are folded too.
+//!         let folded = self.transform_children(normal);
+//!         let normal = folded.as_ref().unwrap_or(normal);
+//!
+//!         if normal.has_head(&Symbol::new("System`Plus")) {
+//!             let ints: Option<Vec<i64>> =
+//!                 normal.elements().iter().map(|elem| elem.as_i64()).collect();
+//!
+//!             if let Some(ints) = ints {
+//!                 return Some(Expr::from(ints.iter().sum::<i64>()));
+//!             }
+//!         }
+//!
+//!         folded.map(Expr::from)
+//!     }
+//! }
+//!
+//! assert_eq!(
+//!     FoldPlus.transform(&expr!(f[System::Plus[1, System::Plus[2, 3]], System::Plus[x, 1]])),
+//!     expr!(f[6, System::Plus[x, 1]])
+//! );
+//! ```
+
+use alloc::{sync::Arc, vec::Vec};
+
+#[cfg(feature = "bigint")]
+use crate::BigInt;
+#[cfg(feature = "rational")]
+use crate::BigRational;
+#[cfg(feature = "bigreal")]
+use crate::BigReal;
+use crate::{Expr, ExprKind, Normal, Symbol, F64};
+
+
+/// Read-only traversal of an expression.
+///
+/// Traversal starts by calling [`Visitor::visit_expr()`] on the root expression. By
+/// default, `visit_expr()` calls [`Visitor::walk()`], which calls the `visit_*` method
+/// for the kind of the expression. The default [`Visitor::visit_normal()`] visits the
+/// head of a normal expression, followed by each of its elements, so every
+/// subexpression is visited, including heads.
+///
+/// Every method has a default implementation, so implementors only override the
+/// methods for the expressions they are interested in. An overriding
+/// `visit_normal()` or `visit_expr()` can call [`Visitor::walk_normal()`] or
+/// [`Visitor::walk()`] to continue the default traversal.
+///
+/// See the [module documentation](self) for an example.
+pub trait Visitor {
+    /// Visit any expression.
+    fn visit_expr(&mut self, expr: &Expr) {
+        self.walk(expr)
+    }
+
+    /// Visit a machine integer.
+    fn visit_integer(&mut self, _int: i64) {}
+
+    /// Visit an arbitrary-precision integer.
+    ///
+    /// This method requires the `bigint` crate feature.
+    #[cfg(feature = "bigint")]
+    fn visit_big_integer(&mut self, _int: &BigInt) {}
+
+    /// Visit an exact rational number.
+    ///
+    /// This method requires the `rational` crate feature.
+    #[cfg(feature = "rational")]
+    fn visit_rational(&mut self, _rational: &BigRational) {}
+
+    /// Visit a machine real.
+    fn visit_real(&mut self, _real: F64) {}
+
+    /// Visit an arbitrary-precision real.
+    ///
+    /// This method requires the `bigreal` crate feature.
+    #[cfg(feature = "bigreal")]
+    fn visit_big_real(&mut self, _real: &BigReal) {}
+
+    /// Visit a string.
+    fn visit_string(&mut self, _string: &str) {}
+
+    /// Visit a symbol.
+    fn visit_symbol(&mut self, _symbol: &Symbol) {}
+
+    /// Visit a normal expression.
+    ///
+    /// By default, this calls [`Visitor::walk_normal()`].
+    fn visit_normal(&mut self, normal: &Normal) {
+        self.walk_normal(normal)
+    }
+
+    /// Call the `visit_*` method for the kind of `expr`.
+    fn walk(&mut self, expr: &Expr) {
+        match expr.kind() {
+            ExprKind::Integer(int) => self.visit_integer(*int),
+            #[cfg(feature = "bigint")]
+            ExprKind::BigInteger(int) => self.visit_big_integer(int),
+            #[cfg(feature = "rational")]
+            ExprKind::Rational(rational) => self.visit_rational(rational),
+            ExprKind::Real(real) => self.visit_real(*real),
+            #[cfg(feature = "bigreal")]
+            ExprKind::BigReal(real) => self.visit_big_real(real),
+            ExprKind::String(string) => self.visit_string(string),
+            ExprKind::Symbol(symbol) => self.visit_symbol(symbol),
+            ExprKind::Normal(normal) => self.visit_normal(normal),
+        }
+    }
+
+    /// Call [`Visitor::visit_expr()`] on the head of `normal`, and then on each of its
+    /// elements.
+    fn walk_normal(&mut self, normal: &Normal) {
+        self.visit_expr(normal.head());
+
+        for elem in normal.elements() {
+            self.visit_expr(elem);
+        }
+    }
+}
+
+/// Construction of a new expression from an existing one.
+///
+/// [`Transformer::transform()`] returns the transformed copy of an expression. Each
+/// `transform_*` method returns the replacement for an expression of its kind, or
+/// `None` to keep the expression unchanged. By default, atomic expressions are kept
+/// unchanged, and [`Transformer::transform_normal()`] transforms the head and elements
+/// of a normal expression.
+///
+/// Unchanged subexpressions are not copied: the transformed expression shares them
+/// with the original expression.
+///
+/// See the [module documentation](self) for an example.
+pub trait Transformer {
+    /// Transform any expression.
+    ///
+    /// By default, this calls [`Transformer::transform_kind()`], and returns a clone of
+    /// `expr` if it is unchanged.
+    fn transform(&mut self, expr: &Expr) -> Expr {
+        self.transform_kind(expr).unwrap_or_else(|| expr.clone())
+    }
+
+    /// Transform a machine integer.
+    fn transform_integer(&mut self, _int: i64) -> Option<Expr> {
+        None
+    }
+
+    /// Transform an arbitrary-precision integer.
+    ///
+    /// This method requires the `bigint` crate feature.
+    #[cfg(feature = "bigint")]
+    fn transform_big_integer(&mut self, _int: &BigInt) -> Option<Expr> {
+        None
+    }
+
+    /// Transform an exact rational number.
+    ///
+    /// This method requires the `rational` crate feature.
+    #[cfg(feature = "rational")]
+    fn transform_rational(&mut self, _rational: &BigRational) -> Option<Expr> {
+        None
+    }
+
+    /// Transform a machine real.
+    fn transform_real(&mut self, _real: F64) -> Option<Expr> {
+        None
+    }
+
+    /// Transform an arbitrary-precision real.
+    ///
+    /// This method requires the `bigreal` crate feature.
+    #[cfg(feature = "bigreal")]
+    fn transform_big_real(&mut self, _real: &BigReal) -> Option<Expr> {
+        None
+    }
+
+    /// Transform a string.
+    fn transform_string(&mut self, _string: &str) -> Option<Expr> {
+        None
+    }
+
+    /// Transform a symbol.
+    fn transform_symbol(&mut self, _symbol: &Symbol) -> Option<Expr> {
+        None
+    }
+
+    /// Transform a normal expression.
+    ///
+    /// By default, this calls [`Transformer::transform_children()`].
+    fn transform_normal(&mut self, normal: &Normal) -> Option<Expr> {
+        self.transform_children(normal).map(Expr::from)
+    }
+
+    /// Call the `transform_*` method for the kind of `expr`.
+    ///
+    /// Returns `None` if `expr` is unchanged.
+    fn transform_kind(&mut self, expr: &Expr) -> Option<Expr> {
+        match expr.kind() {
+            ExprKind::Integer(int) => self.transform_integer(*int),
+            #[cfg(feature = "bigint")]
+            ExprKind::BigInteger(int) => self.transform_big_integer(int),
+            #[cfg(feature = "rational")]
+            ExprKind::Rational(rational) => self.transform_rational(rational),
+            ExprKind::Real(real) => self.transform_real(*real),
+            #[cfg(feature = "bigreal")]
+            ExprKind::BigReal(real) => self.transform_big_real(real),
+            ExprKind::String(string) => self.transform_string(string),
+            ExprKind::Symbol(symbol) => self.transform_symbol(symbol),
+            ExprKind::Normal(normal) => self.transform_normal(normal),
+        }
+    }
+
+    /// Call [`Transformer::transform()`] on the head of `normal`, and then on each of
+    /// its elements.
+    ///
+    /// Returns `None` if the head and every element are unchanged.
+    fn transform_children(&mut self, normal: &Normal) -> Option<Normal> {
+        let head = self.transform(normal.head());
+        let elements: Vec<Expr> = normal
+            .elements()
+            .iter()
+            .map(|elem| self.transform(elem))
+            .collect();
+
+        let unchanged = is_same(&head, normal.head())
+            && elements
+                .iter()
+                .zip(normal.elements())
+                .all(|(new, old)| is_same(new, old));
+
+        if unchanged {
+            return None;
+        }
+
+        Some(Normal::new(head, elements))
+    }
+}
+
+/// Returns `true` if `a` and `b` share the same allocation.
+fn is_same(a: &Expr, b: &Expr) -> bool {
+    Arc::ptr_eq(&a.inner, &b.inner)
+}
+
+#[test]
+fn test_visitor() {
+    use alloc::{string::String, vec::Vec};
+
+    use crate::expr;
+
+    /// Records each visited expression, and the depth of normal expressions.
+    #[derive(Default)]
+    struct Log {
+        depth: usize,
+        events: Vec<String>,
+    }
+
+    impl Visitor for Log {
+        fn visit_integer(&mut self, int: i64) {
+            self.events
+                .push(format!("{}int {}", "  ".repeat(self.depth), int));
+        }
+
+        fn visit_real(&mut self, real: F64) {
+            self.events
+                .push(format!("{}real {}", "  ".repeat(self.depth), real));
+        }
+
+        fn visit_symbol(&mut self, symbol: &Symbol) {
+            let indent = "  ".repeat(self.depth);
+            self.events.push(format!("{}symbol {}", indent, symbol));
+        }
+
+        fn visit_normal(&mut self, normal: &Normal) {
+            self.depth += 1;
+            self.walk_normal(normal);
+            self.depth -= 1;
+        }
+    }
+
+    let mut log = Log::default();
+    log.visit_expr(&expr!(f[1, g[x]["a"], 2.5]));
+
+    // Strings are not recorded.
+    assert_eq!(log.events, [
+        "  symbol Global`f",
+        "  int 1",
+        "      symbol Global`g",
+        "      symbol Global`x",
+        "  real 2.5",
+    ]);
+}
+
+#[test]
+fn test_visitor_visit_expr() {
+    use crate::expr;
+
+    /// Counts normal expressions, without descending into `Hold[...]`.
+    #[derive(Default)]
+    struct CountNormals(usize);
+
+    impl Visitor for CountNormals {
+        fn visit_expr(&mut self, expr: &Expr) {
+            if let Some(normal) = expr.try_as_normal() {
+                if normal.has_head(&Symbol::new("System`Hold")) {
+                    return;
+                }
+                self.0 += 1;
+            }
+
+            self.walk(expr)
+        }
+    }
+
+    let mut count = CountNormals::default();
+    count.visit_expr(&expr!(f[g[1], System::Hold[h[2]], k[l[]][3]]));
+
+    // `f[...]`, `g[1]`, `k[l[]][3]`, `k[l[]]`, and `l[]`.
+    assert_eq!(count.0, 5);
+}
+
+#[test]
+fn test_transformer() {
+    use crate::expr;
+
+    struct Increment;
+
+    impl Transformer for Increment {
+        fn transform_integer(&mut self, int: i64) -> Option<Expr> {
+            Some(Expr::from(int + 1))
+        }
+    }
+
+    assert_eq!(
+        Increment.transform(&expr!(f[1, g[2, x], 3][4])),
+        expr!(f[2, g[3, x], 4][5])
+    );
+    assert_eq!(Increment.transform(&expr!(-1)), expr!(0));
+    assert_eq!(Increment.transform(&expr!("a")), expr!("a"));
+
+    // Unchanged subexpressions are shared with the original expression.
+    let unchanged = expr!(g[x, "a"]);
+    let expr = expr!(f[1, #unchanged]);
+    let transformed = Increment.transform(&expr);
+    assert_eq!(transformed, expr!(f[2, g[x, "a"]]));
+    assert!(is_same(&transformed[1], &unchanged));
+
+    let transformed = Increment.transform(&unchanged);
+    assert!(is_same(&transformed, &unchanged));
+}
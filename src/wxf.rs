@@ -0,0 +1,634 @@
+//! Serialization of expressions in the [Wolfram Exchange Format][WXF] (WXF).
+//!
+//! WXF is the binary format used by
+//! [`BinarySerialize`](https://reference.wolfram.com/language/ref/BinarySerialize.html)<sub>WL</sub>
+//! and
+//! [`BinaryDeserialize`](https://reference.wolfram.com/language/ref/BinaryDeserialize.html)<sub>WL</sub>.
+//!
+//! [WXF]: https://reference.wolfram.com/language/tutorial/WXFFormatDescription.html
+
+use std::{convert::TryFrom, error::Error, fmt, io, io::Read};
+
+use crate::{
+    builder::NestedBuilder,
+    symbol::{SymbolNameRef, SymbolRef},
+    Expr, ExprKind, Normal, Number, Symbol,
+};
+
+
+/// The header that begins every uncompressed WXF byte sequence.
+const HEADER: &[u8] = b"8:";
+
+//======================================
+// Token tags
+//======================================
+
+const FUNCTION: u8 = b'f';
+const SYMBOL: u8 = b's';
+const STRING: u8 = b'S';
+const INTEGER_8: u8 = b'C';
+const INTEGER_16: u8 = b'j';
+const INTEGER_32: u8 = b'i';
+const INTEGER_64: u8 = b'L';
+const REAL_64: u8 = b'r';
+const BIG_INTEGER: u8 = b'I';
+const BIG_REAL: u8 = b'R';
+const ASSOCIATION: u8 = b'A';
+const PACKED_ARRAY: u8 = 0xC1;
+const NUMERIC_ARRAY: u8 = 0xC2;
+
+/// Error that can occur while reading WXF data.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum WxfError {
+    /// An I/O error occurred while reading from the underlying reader.
+    ///
+    /// This includes reaching the end of the input in the middle of an expression.
+    Io(io::Error),
+    /// The input did not begin with the uncompressed WXF header `8:`.
+    InvalidHeader,
+    /// The input uses a WXF feature that is not supported by this crate.
+    Unsupported(&'static str),
+    /// The input contained a byte that is not a valid WXF token tag.
+    UnknownToken(u8),
+    /// A symbol token contained a string that is not a valid symbol.
+    InvalidSymbol(String),
+    /// A string or symbol token contained bytes that are not valid UTF-8.
+    InvalidUtf8,
+    /// A machine real token contained a NaN value.
+    NaN,
+    /// A big real token contained a string that is not a valid arbitrary-precision
+    /// real number.
+    InvalidBigReal(String),
+    /// A big integer token contained a string that is not a valid integer.
+    InvalidBigInteger(String),
+    /// A length prefix was malformed or did not fit in 64 bits.
+    InvalidLength,
+    /// The input contained bytes after the end of the expression.
+    TrailingData,
+}
+
+impl Expr {
+    /// Serialize this expression as WXF, writing the bytes to `writer` incrementally.
+    ///
+    /// The output is equivalent to `BinarySerialize[expr]` with
+    /// `PerformanceGoal -> "Speed"`: only the uncompressed `8:` header is written, never
+    /// the `8C:` compression wrapper.
+    ///
+    /// Symbols are always written with their full context.
+    ///
+    /// This function issues many small writes. When writing to a file or socket, wrap
+    /// `writer` in an [`io::BufWriter`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use wolfram_expr::{Expr, Symbol};
+    ///
+    /// let expr = Expr::normal(Symbol::new("Global`f"), vec![Expr::from(1)]);
+    ///
+    /// let mut bytes = Vec::new();
+    /// expr.write_wxf(&mut bytes).unwrap();
+    ///
+    /// assert_eq!(bytes, b"8:f\x01s\x08Global`fC\x01");
+    /// ```
+    pub fn write_wxf<W: io::Write>(&self, mut writer: W) -> io::Result<()> {
+        writer.write_all(HEADER)?;
+
+        write_expr(&mut writer, self)
+    }
+
+    /// Deserialize an expression from WXF data read incrementally from `reader`.
+    ///
+    /// Reading stops at the end of the first complete expression; any bytes after it
+    /// are left unread. The length of the input does not need to be known in advance.
+    ///
+    /// Symbols without a context are resolved in the `` System` `` context, matching
+    /// the output of `BinarySerialize` for built-in symbols.
+    ///
+    /// Compressed WXF (`8C:`), associations, and packed and numeric arrays are not
+    /// supported, and return [`WxfError::Unsupported`]. Big integers and big reals are
+    /// supported only if the `bigint` and `bigreal` crate features are enabled. If the
+    /// `rational` crate feature is enabled, `Rational[n, d]` is read as a rational
+    /// number.
+    ///
+    /// This function issues many small reads. When reading from a file or socket, wrap
+    /// `reader` in an [`io::BufReader`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use wolfram_expr::{Expr, Symbol};
+    ///
+    /// let expr = Expr::read_wxf(&b"8:f\x01s\x08Global`fC\x01"[..]).unwrap();
+    ///
+    /// assert_eq!(expr, Expr::normal(Symbol::new("Global`f"), vec![Expr::from(1)]));
+    /// ```
+    pub fn read_wxf<R: io::Read>(mut reader: R) -> Result<Expr, WxfError> {
+        let mut header = [0; 2];
+        reader.read_exact(&mut header)?;
+
+        match &header {
+            b"8:" => (),
+            b"8C" => return Err(WxfError::Unsupported("compressed WXF")),
+            _ => return Err(WxfError::InvalidHeader),
+        }
+
+        read_expr(&mut reader)
+    }
+
+    /// Serialize this expression as WXF.
+    ///
+    /// This is equivalent to
+    /// [`BinarySerialize`](https://reference.wolfram.com/language/ref/BinarySerialize.html)<sub>WL</sub>.
+    /// See [`Expr::write_wxf()`] for details of the output.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use wolfram_expr::expr;
+    ///
+    /// assert_eq!(expr!(f[1]).to_wxf(), b"8:f\x01s\x08Global`fC\x01");
+    /// ```
+    pub fn to_wxf(&self) -> Vec<u8> {
+        let mut bytes = Vec::new();
+
+        self.write_wxf(&mut bytes)
+            .expect("writing WXF to a Vec<u8> cannot fail");
+
+        bytes
+    }
+
+    /// Deserialize an expression from WXF data.
+    ///
+    /// This is equivalent to
+    /// [`BinaryDeserialize`](https://reference.wolfram.com/language/ref/BinaryDeserialize.html)<sub>WL</sub>.
+    /// See [`Expr::read_wxf()`] for the supported subset of WXF. Unlike `read_wxf()`,
+    /// `bytes` must contain exactly one expression: any bytes after it return
+    /// [`WxfError::TrailingData`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use wolfram_expr::{expr, Expr};
+    ///
+    /// let expr = expr!(System::List[1, 2.5, "a", x]);
+    ///
+    /// assert_eq!(Expr::from_wxf(&expr.to_wxf()).unwrap(), expr);
+    /// ```
+    pub fn from_wxf(mut bytes: &[u8]) -> Result<Expr, WxfError> {
+        let expr = Expr::read_wxf(&mut bytes)?;
+
+        if !bytes.is_empty() {
+            return Err(WxfError::TrailingData);
+        }
+
+        Ok(expr)
+    }
+}
+
+//======================================
+// Writing
+//======================================
+
+fn write_expr<W: io::Write>(writer: &mut W, expr: &Expr) -> io::Result<()> {
+    match expr.kind() {
+        ExprKind::Integer(int) => write_integer(writer, *int),
+        #[cfg(feature = "bigint")]
+        ExprKind::BigInteger(int) => {
+            write_bytes(writer, BIG_INTEGER, int.to_string().as_bytes())
+        },
+        // WXF has no rational token; rationals are written as `Rational[n, d]`.
+        #[cfg(feature = "rational")]
+        ExprKind::Rational(rational) => {
+            writer.write_all(&[FUNCTION])?;
+            write_varint(writer, 2)?;
+
+            write_bytes(writer, SYMBOL, b"System`Rational")?;
+            write_expr(writer, &Expr::big_integer(rational.numer().clone()))?;
+            write_expr(writer, &Expr::big_integer(rational.denom().clone()))
+        },
+        ExprKind::Real(real) => {
+            writer.write_all(&[REAL_64])?;
+            writer.write_all(&real.into_inner().to_le_bytes())
+        },
+        #[cfg(feature = "bigreal")]
+        ExprKind::BigReal(real) => {
+            write_bytes(writer, BIG_REAL, real.to_string().as_bytes())
+        },
+        ExprKind::String(string) => write_bytes(writer, STRING, string.as_bytes()),
+        ExprKind::Symbol(symbol) => {
+            write_bytes(writer, SYMBOL, symbol.as_str().as_bytes())
+        },
+        ExprKind::Normal(normal) => {
+            writer.write_all(&[FUNCTION])?;
+            write_varint(writer, normal.elements().len() as u64)?;
+
+            write_expr(writer, normal.head())?;
+            for elem in normal.elements() {
+                write_expr(writer, elem)?;
+            }
+
+            Ok(())
+        },
+    }
+}
+
+/// Write `int` using the narrowest integer token that can represent it.
+fn write_integer<W: io::Write>(writer: &mut W, int: i64) -> io::Result<()> {
+    if let Ok(int) = i8::try_from(int) {
+        writer.write_all(&[INTEGER_8])?;
+        writer.write_all(&int.to_le_bytes())
+    } else if let Ok(int) = i16::try_from(int) {
+        writer.write_all(&[INTEGER_16])?;
+        writer.write_all(&int.to_le_bytes())
+    } else if let Ok(int) = i32::try_from(int) {
+        writer.write_all(&[INTEGER_32])?;
+        writer.write_all(&int.to_le_bytes())
+    } else {
+        writer.write_all(&[INTEGER_64])?;
+        writer.write_all(&int.to_le_bytes())
+    }
+}
+
+/// Write a length-prefixed token.
+fn write_bytes<W: io::Write>(writer: &mut W, tag: u8, bytes: &[u8]) -> io::Result<()> {
+    writer.write_all(&[tag])?;
+    write_varint(writer, bytes.len() as u64)?;
+    writer.write_all(bytes)
+}
+
+/// Write `value` as a variable-length integer: 7 bits per byte, least significant group
+/// first, with the high bit set on every byte except the last.
+fn write_varint<W: io::Write>(writer: &mut W, mut value: u64) -> io::Result<()> {
+    loop {
+        let byte = (value & 0x7F) as u8;
+        value >>= 7;
+
+        if value == 0 {
+            return writer.write_all(&[byte]);
+        }
+
+        writer.write_all(&[byte | 0x80])?;
+    }
+}
+
+//======================================
+// Reading
+//======================================
+
+/// Read one expression.
+///
+/// Normal expressions are read using a [`NestedBuilder`] rather than by recursion, so
+/// that deeply nested input cannot overflow the stack.
+fn read_expr<R: io::Read>(reader: &mut R) -> Result<Expr, WxfError> {
+    let mut builder = NestedBuilder::new(normal_to_expr);
+
+    loop {
+        let expr = match read_byte(reader)? {
+            INTEGER_8 => Expr::from(i8::from_le_bytes(read_array(reader)?)),
+            INTEGER_16 => Expr::from(i16::from_le_bytes(read_array(reader)?)),
+            INTEGER_32 => Expr::from(i32::from_le_bytes(read_array(reader)?)),
+            INTEGER_64 => Expr::from(i64::from_le_bytes(read_array(reader)?)),
+            REAL_64 => {
+                let real = f64::from_le_bytes(read_array(reader)?);
+                let real = ordered_float::NotNan::new(real).map_err(|_| WxfError::NaN)?;
+
+                Expr::number(Number::Real(real))
+            },
+            STRING => Expr::string(read_string(reader)?),
+            SYMBOL => Expr::symbol(read_symbol(reader)?),
+            FUNCTION => {
+                let len = read_varint(reader)?;
+
+                // Don't trust `len` for pre-allocation; the input may be malformed. The
+                // builder also bounds the total across all levels.
+                builder.begin(len, len.min(1024) as usize);
+                continue;
+            },
+            #[cfg(feature = "bigint")]
+            BIG_INTEGER => {
+                let string = read_string(reader)?;
+
+                match crate::big_integer::parse_big_integer(&string) {
+                    Some(int) => Expr::big_integer(int),
+                    None => return Err(WxfError::InvalidBigInteger(string)),
+                }
+            },
+            #[cfg(not(feature = "bigint"))]
+            BIG_INTEGER => return Err(WxfError::Unsupported("big integers")),
+            #[cfg(feature = "bigreal")]
+            BIG_REAL => {
+                let string = read_string(reader)?;
+
+                match crate::BigReal::parse(&string) {
+                    Some(real) => Expr::big_real(real),
+                    None => return Err(WxfError::InvalidBigReal(string)),
+                }
+            },
+            #[cfg(not(feature = "bigreal"))]
+            BIG_REAL => return Err(WxfError::Unsupported("big reals")),
+            ASSOCIATION => return Err(WxfError::Unsupported("associations")),
+            PACKED_ARRAY => return Err(WxfError::Unsupported("packed arrays")),
+            NUMERIC_ARRAY => return Err(WxfError::Unsupported("numeric arrays")),
+            other => return Err(WxfError::UnknownToken(other)),
+        };
+
+        if let Some(expr) = builder.add(expr) {
+            return Ok(expr);
+        }
+    }
+}
+
+/// Construct the expression for a normal expression that has been read.
+///
+/// If the `rational` crate feature is enabled, `Rational[n, d]` is a rational number.
+fn normal_to_expr(normal: Normal) -> Expr {
+    #[cfg(feature = "rational")]
+    if let Some(rational) = crate::rational::rational_from_normal(&normal) {
+        return rational;
+    }
+
+    Expr::from(normal)
+}
+
+fn read_symbol<R: io::Read>(reader: &mut R) -> Result<Symbol, WxfError> {
+    let string = read_string(reader)?;
+
+    if let Some(symbol) = SymbolRef::try_new(&string) {
+        return Ok(symbol.to_symbol());
+    }
+
+    match SymbolNameRef::try_new(&string) {
+        Some(name) => Ok(Symbol::new(&format!("System`{}", name))),
+        None => Err(WxfError::InvalidSymbol(string)),
+    }
+}
+
+/// Read a length-prefixed UTF-8 string.
+fn read_string<R: io::Read>(reader: &mut R) -> Result<String, WxfError> {
+    let len = read_varint(reader)?;
+
+    // Read via `take()` instead of allocating a buffer of size `len` up front, so that a
+    // malformed length can't trigger a huge allocation.
+    let mut bytes = Vec::new();
+    reader.take(len).read_to_end(&mut bytes)?;
+
+    if bytes.len() as u64 != len {
+        return Err(WxfError::Io(io::ErrorKind::UnexpectedEof.into()));
+    }
+
+    String::from_utf8(bytes).map_err(|_| WxfError::InvalidUtf8)
+}
+
+fn read_varint<R: io::Read>(reader: &mut R) -> Result<u64, WxfError> {
+    let mut value: u64 = 0;
+
+    for shift in (0..64).step_by(7) {
+        let byte = read_byte(reader)?;
+
+        value |= u64::from(byte & 0x7F) << shift;
+
+        if byte & 0x80 == 0 {
+            return Ok(value);
+        }
+    }
+
+    Err(WxfError::InvalidLength)
+}
+
+fn read_byte<R: io::Read>(reader: &mut R) -> Result<u8, WxfError> {
+    let [byte] = read_array(reader)?;
+    Ok(byte)
+}
+
+fn read_array<R: io::Read, const N: usize>(reader: &mut R) -> Result<[u8; N], WxfError> {
+    let mut bytes = [0; N];
+    reader.read_exact(&mut bytes)?;
+    Ok(bytes)
+}
+
+//======================================
+// Error impls
+//======================================
+
+impl From<io::Error> for WxfError {
+    fn from(err: io::Error) -> Self {
+        WxfError::Io(err)
+    }
+}
+
+impl fmt::Display for WxfError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            WxfError::Io(err) => write!(f, "error reading WXF data: {}", err),
+            WxfError::InvalidHeader => write!(f, "missing WXF header"),
+            WxfError::Unsupported(feature) => {
+                write!(f, "unsupported WXF feature: {}", feature)
+            },
+            WxfError::UnknownToken(tag) => write!(f, "unknown WXF token: 0x{:02X}", tag),
+            WxfError::InvalidSymbol(string) => {
+                write!(f, "WXF symbol is not a valid symbol: {:?}", string)
+            },
+            WxfError::InvalidUtf8 => write!(f, "WXF string is not valid UTF-8"),
+            WxfError::NaN => write!(f, "WXF real is NaN"),
+            WxfError::InvalidBigReal(string) => {
+                write!(f, "WXF big real is not a valid real number: {:?}", string)
+            },
+            WxfError::InvalidBigInteger(string) => {
+                write!(f, "WXF big integer is not a valid integer: {:?}", string)
+            },
+            WxfError::InvalidLength => write!(f, "malformed WXF length"),
+            WxfError::TrailingData => write!(f, "unexpected data after WXF expression"),
+        }
+    }
+}
+
+impl Error for WxfError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            WxfError::Io(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+#[test]
+fn test_write_wxf() {
+    use crate::Symbol;
+
+    let wxf = |expr: Expr| {
+        let mut bytes = Vec::new();
+        expr.write_wxf(&mut bytes).unwrap();
+        bytes
+    };
+
+    assert_eq!(wxf(Expr::from(5)), b"8:C\x05");
+    assert_eq!(wxf(Expr::from(-200)), b"8:j\x38\xFF");
+    assert_eq!(wxf(Expr::from(100_000)), b"8:i\xA0\x86\x01\x00");
+    assert_eq!(
+        wxf(Expr::from(i64::MAX)),
+        b"8:L\xFF\xFF\xFF\xFF\xFF\xFF\xFF\x7F"
+    );
+    assert_eq!(wxf(Expr::real(1.5)), b"8:r\x00\x00\x00\x00\x00\x00\xF8\x3F");
+    assert_eq!(wxf(Expr::from("ab")), b"8:S\x02ab");
+    assert_eq!(
+        wxf(Expr::symbol(Symbol::new("System`List"))),
+        b"8:s\x0BSystem`List"
+    );
+
+    // g[1][]
+    assert_eq!(
+        wxf(Expr::normal(
+            Expr::normal(Symbol::new("Global`g"), vec![Expr::from(1)]),
+            vec![]
+        )),
+        b"8:f\x00f\x01s\x08Global`gC\x01"
+    );
+}
+
+#[test]
+fn test_write_varint() {
+    let varint = |value: u64| {
+        let mut bytes = Vec::new();
+        write_varint(&mut bytes, value).unwrap();
+        bytes
+    };
+
+    assert_eq!(varint(0), [0x00]);
+    assert_eq!(varint(127), [0x7F]);
+    assert_eq!(varint(128), [0x80, 0x01]);
+    assert_eq!(varint(300), [0xAC, 0x02]);
+}
+
+#[test]
+fn test_read_wxf() {
+    let read = |bytes: &[u8]| Expr::read_wxf(bytes);
+
+    assert_eq!(read(b"8:j\x38\xFF").unwrap(), Expr::from(-200));
+    assert_eq!(
+        read(b"8:r\x00\x00\x00\x00\x00\x00\xF8\x3F").unwrap(),
+        Expr::real(1.5)
+    );
+
+    // Context-less symbols are resolved in System`.
+    assert_eq!(
+        read(b"8:f\x00s\x04List").unwrap(),
+        Expr::normal(Symbol::new("System`List"), vec![])
+    );
+
+    assert!(matches!(read(b"7:C\x01"), Err(WxfError::InvalidHeader)));
+    assert!(matches!(read(b"8C:x\x9c"), Err(WxfError::Unsupported(_))));
+    assert!(matches!(
+        read(b"8:s\x03a_b"),
+        Err(WxfError::InvalidSymbol(_))
+    ));
+    assert!(matches!(read(b"8:S\x01\xFF"), Err(WxfError::InvalidUtf8)));
+    assert!(matches!(read(b"8:Z"), Err(WxfError::UnknownToken(b'Z'))));
+    assert!(matches!(
+        read(b"8:r\x00\x00\x00\x00\x00\x00\xF8\x7F"),
+        Err(WxfError::NaN)
+    ));
+
+    // Truncated input.
+    assert!(matches!(
+        read(b"8:f\x02s\x08Global`fC\x01"),
+        Err(WxfError::Io(_))
+    ));
+    assert!(matches!(read(b"8:S\xFF\x01abc"), Err(WxfError::Io(_))));
+}
+
+#[test]
+fn test_wxf_roundtrip() {
+    use crate::expr;
+
+    let exprs = [
+        expr!(0),
+        expr!(-129),
+        expr!(40000),
+        Expr::from(i64::MIN),
+        expr!(-0.25),
+        Expr::real(f64::INFINITY),
+        expr!(""),
+        expr!("αβ \"γ\""),
+        expr!(System::Plus),
+        expr!(f[]),
+        expr!(System::List[1, 2.5, "a", x, f[g[y]][z]]),
+    ];
+
+    for expr in exprs {
+        assert_eq!(Expr::from_wxf(&expr.to_wxf()).unwrap(), expr);
+    }
+
+    let mut bytes = expr!(f[1]).to_wxf();
+    bytes.push(0);
+    assert!(matches!(
+        Expr::from_wxf(&bytes),
+        Err(WxfError::TrailingData)
+    ));
+    assert!(matches!(Expr::from_wxf(b"8:"), Err(WxfError::Io(_))));
+    #[cfg(not(feature = "bigint"))]
+    assert!(matches!(
+        Expr::from_wxf(b"8:I\x1512345678901234567890"),
+        Err(WxfError::Unsupported(_))
+    ));
+}
+
+#[test]
+fn test_read_wxf_deep_nesting() {
+    // Each `f\x00` is a normal expression with no elements, whose head is the next
+    // expression.
+    let mut bytes = HEADER.to_vec();
+    bytes.extend(b"f\x00".repeat(1_000_000));
+
+    assert!(matches!(Expr::from_wxf(&bytes), Err(WxfError::Io(_))));
+
+    bytes.extend(b"C\x01");
+    assert_eq!(Expr::from_wxf(&bytes).unwrap().depth(), 1_000_001);
+}
+
+#[test]
+fn test_read_wxf_nested_large_counts() {
+    // Each `f\x80\x08` claims 1024 elements, but only its head follows.
+    let mut bytes = HEADER.to_vec();
+    bytes.extend(b"f\x80\x08".repeat(300_000));
+
+    assert!(matches!(Expr::from_wxf(&bytes), Err(WxfError::Io(_))));
+}
+
+#[cfg(feature = "bigint")]
+#[test]
+fn test_wxf_big_integer() {
+    use crate::BigInt;
+
+    let big = Expr::big_integer(BigInt::from(i64::MAX) * 10u8);
+    assert_eq!(big.to_wxf(), b"8:I\x1492233720368547758070");
+    assert_eq!(Expr::from_wxf(&big.to_wxf()).unwrap(), big);
+
+    let big = Expr::big_integer(-(BigInt::from(1u8) << 100u32));
+    assert_eq!(Expr::from_wxf(&big.to_wxf()).unwrap(), big);
+
+    // Big integer tokens in the range of `i64` are read as machine integers.
+    assert_eq!(Expr::from_wxf(b"8:I\x02-5").unwrap(), Expr::from(-5));
+    assert!(matches!(
+        Expr::from_wxf(b"8:I\x031.5"),
+        Err(WxfError::InvalidBigInteger(_))
+    ));
+}
+
+#[cfg(feature = "rational")]
+#[test]
+fn test_wxf_rational() {
+    let rational = Expr::rational(-2, 3);
+    assert_eq!(rational.to_wxf(), b"8:f\x02s\x0FSystem`RationalC\xFEC\x03");
+    assert_eq!(Expr::from_wxf(&rational.to_wxf()).unwrap(), rational);
+
+    // `BinarySerialize[2/3]` writes the symbol without a context.
+    assert_eq!(
+        Expr::from_wxf(b"8:f\x02s\x08RationalC\x02C\x03").unwrap(),
+        Expr::rational(2, 3)
+    );
+
+    let big = Expr::rational(crate::BigInt::from(1u8) << 70u32, 3);
+    assert_eq!(Expr::from_wxf(&big.to_wxf()).unwrap(), big);
+}
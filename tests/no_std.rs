@@ -0,0 +1,29 @@
+//! Check that the core API is usable with only the `alloc` crate.
+//!
+//! Run with `cargo test --no-default-features` to build `wolfram-expr` without `std`.
+
+#![no_std]
+
+extern crate alloc;
+
+use alloc::{string::ToString, vec};
+
+use wolfram_expr::{expr, Expr, ExprKind, Number, Symbol};
+
+#[test]
+fn test_no_std_core_api() {
+    let expr = Expr::normal(Symbol::new("System`Plus"), vec![
+        Expr::from(1),
+        Expr::real(2.5),
+        Expr::string("a"),
+    ]);
+
+    assert_eq!(expr, expr!(System::Plus[1, 2.5, "a"]));
+    assert_eq!(expr.to_string(), "System`Plus[1, 2.5, \"a\"]");
+    assert_eq!(expr.elements().len(), 3);
+    assert!(matches!(expr.elements()[0].kind(), ExprKind::Integer(1)));
+    assert_eq!(expr.elements()[1].try_as_number(), Some(Number::real(2.5)));
+
+    let bytes = expr.to_bytes();
+    assert_eq!(Expr::from_bytes(&bytes), Ok(expr));
+}
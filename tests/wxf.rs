@@ -0,0 +1,55 @@
+//! Round-trip expressions through the streaming WXF writer and reader.
+
+#![cfg(feature = "std")]
+
+use std::io::Cursor;
+
+use wolfram_expr::{Expr, Symbol};
+
+fn round_trip(expr: &Expr) -> Expr {
+    let mut cursor = Cursor::new(Vec::new());
+    expr.write_wxf(&mut cursor).unwrap();
+
+    cursor.set_position(0);
+    Expr::read_wxf(&mut cursor).unwrap()
+}
+
+#[test]
+fn test_wxf_round_trip() {
+    let exprs = vec![
+        Expr::from(0),
+        Expr::from(i8::MIN),
+        Expr::from(i16::MAX),
+        Expr::from(i32::MIN),
+        Expr::from(i64::MAX),
+        Expr::real(-0.1),
+        Expr::real(f64::INFINITY),
+        Expr::from(""),
+        Expr::from("multi\nline \"string\" ∑"),
+        Expr::symbol(Symbol::new("MyPackage`Private`$x1")),
+        Expr::list(vec![]),
+        Expr::normal(
+            Expr::normal(Symbol::new("Global`g"), vec![Expr::from("x")]),
+            vec![Expr::rule(Symbol::new("Global`a"), Expr::real(2.5))],
+        ),
+        // A string long enough to need a multi-byte length prefix.
+        Expr::from("a".repeat(1000)),
+        // A normal with enough elements to need a multi-byte length prefix.
+        Expr::list((0..300).map(Expr::from).collect()),
+    ];
+
+    for expr in &exprs {
+        assert_eq!(&round_trip(expr), expr);
+    }
+}
+
+#[test]
+fn test_wxf_reader_leaves_trailing_bytes() {
+    let mut cursor = Cursor::new(Vec::new());
+    Expr::from(1).write_wxf(&mut cursor).unwrap();
+    Expr::from(2).write_wxf(&mut cursor).unwrap();
+
+    cursor.set_position(0);
+    assert_eq!(Expr::read_wxf(&mut cursor).unwrap(), Expr::from(1));
+    assert_eq!(Expr::read_wxf(&mut cursor).unwrap(), Expr::from(2));
+}